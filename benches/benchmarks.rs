@@ -20,7 +20,37 @@ mod benchmarks {
     #[bench]
     fn bench_process_refresh(b: &mut Bencher) {
         // Benchmark process list refresh - most critical operation
+        // Steady state: known PIDs are updated incrementally
         let mut manager = ProcessManager::new();
+        manager.refresh().ok();
+        b.iter(|| {
+            manager.refresh().ok();
+        });
+    }
+
+    #[bench]
+    fn bench_process_refresh_cold(b: &mut Bencher) {
+        // Benchmark a refresh that rebuilds every entry and runs all enrichments
+        // (fd walk, cgroups, GPU) - what every refresh used to cost
+        let mut manager = ProcessManager::new();
+        b.iter(|| {
+            manager.invalidate();
+            manager.refresh().ok();
+        });
+    }
+
+    #[bench]
+    fn bench_process_refresh_minimal(b: &mut Bencher) {
+        // Benchmark refresh with network, container and GPU collection disabled
+        use process_manager::config::FeatureConfig;
+
+        let mut manager = ProcessManager::with_features(FeatureConfig {
+            gpu_monitoring: false,
+            network_monitoring: false,
+            container_detection: false,
+            ..FeatureConfig::default()
+        });
+        manager.refresh().ok();
         b.iter(|| {
             manager.refresh().ok();
         });
@@ -10,6 +10,7 @@
 //! - **Real-time Scheduling**: Set SCHED_FIFO, SCHED_RR, SCHED_DEADLINE policies
 //! - **I/O Priority**: Control disk I/O priority (idle, best-effort, real-time)
 //! - **Scheduling Policies**: View and modify CFS, real-time schedulers
+//!   (`sched_getscheduler`/`sched_setscheduler`)
 //! 
 //! ## Use Cases
 //! 
@@ -34,16 +35,18 @@
 //! ```
 
 use anyhow::{Context, Result};
-use std::fs;
+use serde::Serialize;
+use std::io;
 use nix::sched::{sched_getaffinity, sched_setaffinity, CpuSet};
 use nix::unistd::Pid;
+use crate::procfs::ProcStat;
 use tracing::{debug, info, warn, error};
 
 /// Complete information about a process's affinity and priority settings.
 /// 
 /// Contains all scheduling-related attributes including CPU affinity,
 /// nice values, real-time priority, and I/O priority.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AffinityInfo {
     /// List of CPU cores this process is allowed to run on
     pub cpu_affinity: Vec<usize>,
@@ -161,48 +164,31 @@ pub fn set_cpu_affinity(pid: u32, cpus: &[usize]) -> Result<()> {
 /// * `Err` - If process doesn't exist or /proc read fails
 pub fn get_priority_info(pid: u32) -> Result<AffinityInfo> {
     debug!("Getting priority info for pid {}", pid);
-    let stat_path = format!("/proc/{}/stat", pid);
-    let stat_content = fs::read_to_string(&stat_path)
-        .context("Failed to read /proc/{pid}/stat")?;
-    
-    // Parse stat file (fields: pid, comm, state, ppid, pgrp, session, tty_nr, tpgid,
-    // flags, minflt, cminflt, majflt, cmajflt, utime, stime, cutime, cstime,
-    // priority, nice, num_threads, itrealvalue, starttime...)
-    let parts: Vec<&str> = stat_content.split_whitespace().collect();
-    
-    let priority = if parts.len() > 17 {
-        parts[17].parse::<i32>().unwrap_or(0)
-    } else {
-        0
-    };
-    
-    let nice = if parts.len() > 18 {
-        parts[18].parse::<i32>().unwrap_or(0)
-    } else {
-        0
-    };
+    let stat = ProcStat::read(pid)?;
+    let priority = stat.priority as i32;
+    let nice = stat.nice as i32;
     
     // Get CPU affinity
     let cpu_affinity = get_cpu_affinity(pid).unwrap_or_else(|_| vec![]);
     
-    // Get scheduling policy
-    let sched_path = format!("/proc/{}/sched", pid);
-    let scheduling_policy = if let Ok(sched_content) = fs::read_to_string(&sched_path) {
-        extract_scheduling_policy(&sched_content)
-    } else {
-        "SCHED_OTHER".to_string()
-    };
+    // Get scheduling policy and real-time priority
+    let (policy, rt_priority) = get_scheduling_policy(pid).unwrap_or((SchedulingPolicy::Other, 0));
+    let scheduling_policy = policy.name().to_string();
     
-    // Get I/O priority (ionice)
-    let (io_class, io_level) = get_io_priority(pid).unwrap_or(("best-effort".to_string(), 4));
+    // Get I/O priority (ionice); without an explicit class it follows the nice value
+    let (io_class, io_level) = match get_io_priority(pid) {
+        Ok((IoPriorityClass::None, _)) => (IoPriorityClass::None, ((nice + 20) / 5).clamp(0, 7) as u32),
+        Ok(priority) => priority,
+        Err(_) => (IoPriorityClass::None, 4),
+    };
     
     let info = AffinityInfo {
         cpu_affinity: cpu_affinity.clone(),
         nice_value: nice,
         priority,
-        rt_priority: 0, // Would need sched_getparam for real-time priority
+        rt_priority,
         scheduling_policy: scheduling_policy.clone(),
-        io_priority_class: io_class,
+        io_priority_class: io_class.name().to_string(),
         io_priority_level: io_level,
     };
     
@@ -212,37 +198,241 @@ pub fn get_priority_info(pid: u32) -> Result<AffinityInfo> {
     Ok(info)
 }
 
-/// Extract scheduling policy from /proc/[pid]/sched
-fn extract_scheduling_policy(content: &str) -> String {
-    for line in content.lines() {
-        if line.contains("policy") {
-            if line.contains("0") {
-                return "SCHED_OTHER".to_string();
-            } else if line.contains("1") {
-                return "SCHED_FIFO".to_string();
-            } else if line.contains("2") {
-                return "SCHED_RR".to_string();
-            } else if line.contains("3") {
-                return "SCHED_BATCH".to_string();
-            } else if line.contains("5") {
-                return "SCHED_IDLE".to_string();
-            }
+/// Linux scheduling policy of a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SchedulingPolicy {
+    /// Default time-sharing scheduler (CFS/EEVDF)
+    Other,
+    /// Real-time first-in, first-out
+    Fifo,
+    /// Real-time round-robin
+    RoundRobin,
+    /// Time-sharing for CPU-bound batch work
+    Batch,
+    /// Runs only when nothing else wants the CPU
+    Idle,
+    /// Earliest-deadline-first; can be read but not set here
+    Deadline,
+}
+
+impl SchedulingPolicy {
+    /// Kernel name, e.g. `SCHED_FIFO`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchedulingPolicy::Other => "SCHED_OTHER",
+            SchedulingPolicy::Fifo => "SCHED_FIFO",
+            SchedulingPolicy::RoundRobin => "SCHED_RR",
+            SchedulingPolicy::Batch => "SCHED_BATCH",
+            SchedulingPolicy::Idle => "SCHED_IDLE",
+            SchedulingPolicy::Deadline => "SCHED_DEADLINE",
+        }
+    }
+    
+    /// Parse a policy name, with or without the `SCHED_` prefix, in any case
+    /// (`fifo`, `rr`, `SCHED_OTHER`, ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_uppercase();
+        match name.strip_prefix("SCHED_").unwrap_or(&name) {
+            "OTHER" | "NORMAL" => Some(SchedulingPolicy::Other),
+            "FIFO" => Some(SchedulingPolicy::Fifo),
+            "RR" => Some(SchedulingPolicy::RoundRobin),
+            "BATCH" => Some(SchedulingPolicy::Batch),
+            "IDLE" => Some(SchedulingPolicy::Idle),
+            "DEADLINE" => Some(SchedulingPolicy::Deadline),
+            _ => None,
+        }
+    }
+    
+    /// Whether the policy takes a real-time priority (1-99)
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, SchedulingPolicy::Fifo | SchedulingPolicy::RoundRobin)
+    }
+    
+    fn from_raw(policy: libc::c_int) -> Option<Self> {
+        // SCHED_RESET_ON_FORK may be or-ed into the policy
+        match policy & !libc::SCHED_RESET_ON_FORK {
+            libc::SCHED_OTHER => Some(SchedulingPolicy::Other),
+            libc::SCHED_FIFO => Some(SchedulingPolicy::Fifo),
+            libc::SCHED_RR => Some(SchedulingPolicy::RoundRobin),
+            libc::SCHED_BATCH => Some(SchedulingPolicy::Batch),
+            libc::SCHED_IDLE => Some(SchedulingPolicy::Idle),
+            6 => Some(SchedulingPolicy::Deadline),
+            _ => None,
+        }
+    }
+    
+    fn to_raw(self) -> libc::c_int {
+        match self {
+            SchedulingPolicy::Other => libc::SCHED_OTHER,
+            SchedulingPolicy::Fifo => libc::SCHED_FIFO,
+            SchedulingPolicy::RoundRobin => libc::SCHED_RR,
+            SchedulingPolicy::Batch => libc::SCHED_BATCH,
+            SchedulingPolicy::Idle => libc::SCHED_IDLE,
+            SchedulingPolicy::Deadline => 6,
         }
     }
-    "SCHED_OTHER".to_string()
 }
 
-/// Get I/O priority for a process
-fn get_io_priority(_pid: u32) -> Result<(String, u32)> {
-    // This would normally use ioprio_get syscall
-    // For now, return default
-    Ok(("best-effort".to_string(), 4))
+/// Get the scheduling policy and real-time priority of a process
+/// (`sched_getscheduler` and `sched_getparam`).
+/// 
+/// The real-time priority is 0 for non-real-time policies.
+pub fn get_scheduling_policy(pid: u32) -> Result<(SchedulingPolicy, u32)> {
+    // SAFETY: sched_getscheduler only takes a pid
+    let raw = unsafe { libc::sched_getscheduler(pid as libc::pid_t) };
+    if raw == -1 {
+        return Err(io::Error::last_os_error())
+            .with_context(|| format!("Failed to get scheduling policy of pid {}", pid));
+    }
+    let policy = SchedulingPolicy::from_raw(raw)
+        .with_context(|| format!("Unknown scheduling policy {} for pid {}", raw, pid))?;
+    
+    let mut param = libc::sched_param { sched_priority: 0 };
+    // SAFETY: param is a valid, writable sched_param
+    if unsafe { libc::sched_getparam(pid as libc::pid_t, &mut param) } == -1 {
+        return Err(io::Error::last_os_error())
+            .with_context(|| format!("Failed to get scheduling parameters of pid {}", pid));
+    }
+    
+    Ok((policy, param.sched_priority.max(0) as u32))
+}
+
+/// Set the scheduling policy of a process (`sched_setscheduler`).
+/// 
+/// # Arguments
+/// 
+/// * `pid` - Process ID
+/// * `policy` - New policy; `SCHED_DEADLINE` needs `sched_setattr` and is refused
+/// * `rt_priority` - 1-99 for `SCHED_FIFO`/`SCHED_RR`, 0 for the other policies
+/// 
+/// # Returns
+/// 
+/// * `Err` - If the priority does not fit the policy, or permission denied
+///   (real-time policies need `CAP_SYS_NICE`)
+pub fn set_scheduling_policy(pid: u32, policy: SchedulingPolicy, rt_priority: u32) -> Result<()> {
+    if policy == SchedulingPolicy::Deadline {
+        anyhow::bail!("SCHED_DEADLINE cannot be set with sched_setscheduler");
+    }
+    if policy.is_realtime() && !(1..=99).contains(&rt_priority) {
+        anyhow::bail!("Real-time priority for {} must be between 1 and 99", policy.name());
+    }
+    if !policy.is_realtime() && rt_priority != 0 {
+        anyhow::bail!("{} does not take a real-time priority", policy.name());
+    }
+    
+    info!("Setting scheduling policy for pid {} to {} (priority {})", pid, policy.name(), rt_priority);
+    let param = libc::sched_param { sched_priority: rt_priority as libc::c_int };
+    // SAFETY: param is a valid sched_param
+    if unsafe { libc::sched_setscheduler(pid as libc::pid_t, policy.to_raw(), &param) } == -1 {
+        let e = io::Error::last_os_error();
+        error!("Failed to set scheduling policy {} for pid {}: {}", policy.name(), pid, e);
+        return Err(e).with_context(|| format!("Failed to set scheduling policy of pid {}", pid));
+    }
+    Ok(())
+}
+
+/// I/O scheduling class of a process, as used by `ionice`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum IoPriorityClass {
+    /// No class set; I/O priority follows the nice value
+    None,
+    /// Served first, levels 0-7
+    RealTime,
+    /// Default class, levels 0-7
+    BestEffort,
+    /// Only served when no other process needs the disk
+    Idle,
+}
+
+impl IoPriorityClass {
+    /// Name as shown by `ionice`
+    pub fn name(&self) -> &'static str {
+        match self {
+            IoPriorityClass::None => "none",
+            IoPriorityClass::RealTime => "realtime",
+            IoPriorityClass::BestEffort => "best-effort",
+            IoPriorityClass::Idle => "idle",
+        }
+    }
+    
+    /// Parse a class name or `ionice -c` number (`best-effort`, `be`, `2`, ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "none" | "0" => Some(IoPriorityClass::None),
+            "realtime" | "real-time" | "rt" | "1" => Some(IoPriorityClass::RealTime),
+            "best-effort" | "besteffort" | "be" | "2" => Some(IoPriorityClass::BestEffort),
+            "idle" | "3" => Some(IoPriorityClass::Idle),
+            _ => None,
+        }
+    }
+}
+
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: u32 = 13;
+const IOPRIO_PRIO_MASK: u32 = (1 << IOPRIO_CLASS_SHIFT) - 1;
+
+/// Get the I/O scheduling class and level of a process (`ioprio_get`)
+pub fn get_io_priority(pid: u32) -> Result<(IoPriorityClass, u32)> {
+    // SAFETY: ioprio_get takes two integers and has no memory arguments
+    let raw = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid as libc::c_int) };
+    if raw == -1 {
+        return Err(io::Error::last_os_error())
+            .with_context(|| format!("Failed to get I/O priority of pid {}", pid));
+    }
+    Ok(decode_io_priority(raw as u32))
+}
+
+/// Set the I/O scheduling class and level of a process (`ioprio_set`).
+/// 
+/// `level` must be 0-7 (0 = highest) for the real-time and best-effort
+/// classes and is ignored otherwise. The real-time class needs `CAP_SYS_ADMIN`.
+pub fn set_io_priority(pid: u32, class: IoPriorityClass, level: u32) -> Result<()> {
+    let level = match class {
+        IoPriorityClass::RealTime | IoPriorityClass::BestEffort if level > 7 => {
+            anyhow::bail!("I/O priority level must be between 0 and 7");
+        }
+        IoPriorityClass::RealTime | IoPriorityClass::BestEffort => level,
+        IoPriorityClass::None | IoPriorityClass::Idle => 0,
+    };
+    
+    info!("Setting I/O priority for pid {} to {} {}", pid, class.name(), level);
+    let raw = encode_io_priority(class, level);
+    // SAFETY: ioprio_set takes three integers and has no memory arguments
+    let result = unsafe {
+        libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid as libc::c_int, raw as libc::c_int)
+    };
+    if result == -1 {
+        let e = io::Error::last_os_error();
+        error!("Failed to set I/O priority {} {} for pid {}: {}", class.name(), level, pid, e);
+        return Err(e).with_context(|| format!("Failed to set I/O priority of pid {}", pid));
+    }
+    Ok(())
+}
+
+fn decode_io_priority(raw: u32) -> (IoPriorityClass, u32) {
+    let class = match raw >> IOPRIO_CLASS_SHIFT {
+        1 => IoPriorityClass::RealTime,
+        2 => IoPriorityClass::BestEffort,
+        3 => IoPriorityClass::Idle,
+        _ => IoPriorityClass::None,
+    };
+    (class, raw & IOPRIO_PRIO_MASK)
+}
+
+fn encode_io_priority(class: IoPriorityClass, level: u32) -> u32 {
+    let class = match class {
+        IoPriorityClass::None => 0,
+        IoPriorityClass::RealTime => 1,
+        IoPriorityClass::BestEffort => 2,
+        IoPriorityClass::Idle => 3,
+    };
+    (class << IOPRIO_CLASS_SHIFT) | (level & IOPRIO_PRIO_MASK)
 }
 
 /// Set process nice value (priority)
 pub fn set_nice_value(pid: u32, nice: i32) -> Result<()> {
     // Validate nice value range (-20 to 19)
-    if nice < -20 || nice > 19 {
+    if !(-20..=19).contains(&nice) {
         warn!("Invalid nice value {} for pid {} (must be -20 to 19)", nice, pid);
         anyhow::bail!("Nice value must be between -20 and 19");
     }
@@ -257,8 +447,9 @@ pub fn set_nice_value(pid: u32, nice: i32) -> Result<()> {
         );
         
         if result == -1 {
-            error!("Failed to set nice value {} for pid {}", nice, pid);
-            anyhow::bail!("Failed to set nice value");
+            let e = io::Error::last_os_error();
+            error!("Failed to set nice value {} for pid {}: {}", nice, pid, e);
+            return Err(e).context("Failed to set nice value");
         }
     }
     
@@ -305,7 +496,7 @@ pub fn format_affinity_list(cpus: &[usize]) -> String {
 pub fn parse_affinity_string(affinity_str: &str) -> Result<Vec<usize>> {
     let mut cpus = Vec::new();
     
-    for part in affinity_str.split(',') {
+    for part in affinity_str.split(',').map(str::trim) {
         if part.contains('-') {
             let range: Vec<&str> = part.split('-').collect();
             if range.len() == 2 {
@@ -323,6 +514,36 @@ pub fn parse_affinity_string(affinity_str: &str) -> Result<Vec<usize>> {
     Ok(cpus)
 }
 
+/// Parse a scheduling policy with an optional real-time priority, e.g.
+/// `fifo 10`, `SCHED_RR 50` or `batch`
+pub fn parse_scheduling_string(input: &str) -> Result<(SchedulingPolicy, u32)> {
+    let mut parts = input.split_whitespace();
+    let name = parts.next().context("Missing scheduling policy")?;
+    let policy = SchedulingPolicy::from_name(name)
+        .with_context(|| format!("Unknown scheduling policy '{}'", name))?;
+    let rt_priority = match parts.next() {
+        Some(priority) => priority.parse()
+            .with_context(|| format!("Invalid real-time priority '{}'", priority))?,
+        None => 0,
+    };
+    Ok((policy, rt_priority))
+}
+
+/// Parse an I/O class with an optional level, e.g. `best-effort 4`, `rt 0`
+/// or `idle`; the level defaults to 4
+pub fn parse_io_priority_string(input: &str) -> Result<(IoPriorityClass, u32)> {
+    let mut parts = input.split_whitespace();
+    let name = parts.next().context("Missing I/O class")?;
+    let class = IoPriorityClass::from_name(name)
+        .with_context(|| format!("Unknown I/O class '{}'", name))?;
+    let level = match parts.next() {
+        Some(level) => level.parse()
+            .with_context(|| format!("Invalid I/O priority level '{}'", level))?,
+        None => 4,
+    };
+    Ok((class, level))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,6 +561,69 @@ mod tests {
         assert_eq!(parse_affinity_string("0-3").unwrap(), vec![0, 1, 2, 3]);
         assert_eq!(parse_affinity_string("0,2,4").unwrap(), vec![0, 2, 4]);
         assert_eq!(parse_affinity_string("0-1,3-5").unwrap(), vec![0, 1, 3, 4, 5]);
+        assert_eq!(parse_affinity_string("0, 2").unwrap(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_parse_scheduling_and_io_priority_strings() {
+        assert_eq!(parse_scheduling_string("fifo 10").unwrap(), (SchedulingPolicy::Fifo, 10));
+        assert_eq!(parse_scheduling_string("SCHED_BATCH").unwrap(), (SchedulingPolicy::Batch, 0));
+        assert!(parse_scheduling_string("fifo ten").is_err());
+        assert!(parse_scheduling_string("").is_err());
+        assert_eq!(parse_io_priority_string("be 2").unwrap(), (IoPriorityClass::BestEffort, 2));
+        assert_eq!(parse_io_priority_string("idle").unwrap(), (IoPriorityClass::Idle, 4));
+        assert!(parse_io_priority_string("fast 1").is_err());
+    }
+
+    #[test]
+    fn test_scheduling_policy_names() {
+        assert_eq!(SchedulingPolicy::from_name("fifo"), Some(SchedulingPolicy::Fifo));
+        assert_eq!(SchedulingPolicy::from_name("SCHED_RR"), Some(SchedulingPolicy::RoundRobin));
+        assert_eq!(SchedulingPolicy::from_name("normal"), Some(SchedulingPolicy::Other));
+        assert_eq!(SchedulingPolicy::from_name("fast"), None);
+        assert_eq!(
+            SchedulingPolicy::from_raw(libc::SCHED_RR | libc::SCHED_RESET_ON_FORK),
+            Some(SchedulingPolicy::RoundRobin)
+        );
+        assert!(set_scheduling_policy(1, SchedulingPolicy::Fifo, 0).is_err());
+        assert!(set_scheduling_policy(1, SchedulingPolicy::Batch, 10).is_err());
+        assert!(set_scheduling_policy(1, SchedulingPolicy::Deadline, 0).is_err());
+    }
+
+    #[test]
+    fn test_io_priority_encoding() {
+        let raw = encode_io_priority(IoPriorityClass::BestEffort, 4);
+        assert_eq!(raw, (2 << 13) | 4);
+        assert_eq!(decode_io_priority(raw), (IoPriorityClass::BestEffort, 4));
+        assert_eq!(decode_io_priority(3 << 13), (IoPriorityClass::Idle, 0));
+        assert_eq!(IoPriorityClass::from_name("be"), Some(IoPriorityClass::BestEffort));
+        assert_eq!(IoPriorityClass::from_name("3"), Some(IoPriorityClass::Idle));
+        assert!(set_io_priority(1, IoPriorityClass::BestEffort, 8).is_err());
+    }
+
+    #[test]
+    fn test_own_scheduling_round_trip() {
+        let pid = std::process::id();
+        let (policy, rt_priority) = get_scheduling_policy(pid).unwrap();
+        assert!(!policy.is_realtime());
+        assert_eq!(rt_priority, 0);
+
+        // Lowering a child's I/O priority needs no privileges, and leaves the
+        // test process's own priority alone
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let child_pid = child.id();
+        let set = set_io_priority(child_pid, IoPriorityClass::BestEffort, 7);
+        let read_back = get_io_priority(child_pid);
+        let info = get_priority_info(child_pid);
+        child.kill().ok();
+        child.wait().ok();
+
+        set.unwrap();
+        assert_eq!(read_back.unwrap(), (IoPriorityClass::BestEffort, 7));
+        let info = info.unwrap();
+        assert_eq!(info.io_priority_class, "best-effort");
+        assert_eq!(info.io_priority_level, 7);
+        assert_eq!(info.scheduling_policy, policy.name());
     }
 
     #[test]
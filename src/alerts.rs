@@ -10,7 +10,12 @@
 //! - **Severity Levels**: Info, Warning, Critical
 //! - **Cooldown Prevention**: Avoid alert storms
 //! - **Process Filtering**: Alert on specific processes or patterns
-//! - **Async Processing**: Non-blocking alert delivery
+//! - **Async Processing**: Non-blocking alert delivery, with retries for
+//!   failed sends (see [`crate::notifications`])
+//! - **History**: Fired, cleared and acknowledged alerts are available as
+//!   [`AlertEvent`]s for storage (see [`AlertManager::take_events`])
+//! - **Muting**: Acknowledgements, silences and maintenance windows
+//!   (see [`crate::silences`])
 //! 
 //! ## Alert Types
 //! 
@@ -20,6 +25,10 @@
 //! - Anomaly detection triggers
 //! - Custom alerts
 //! 
+//! In API mode the rules are built from `[alerts]` with [`rules_from_config`]
+//! and every collector snapshot is checked with
+//! [`AlertManager::check_processes`].
+//!
 //! ## Example
 //! 
 //! ```rust,ignore
@@ -27,29 +36,55 @@
 //! 
 //! # #[tokio::main]
 //! # async fn main() {
-//! let config = NotificationConfig::default();
-//! let manager = AlertManager::new(config);
-//! 
-//! // Add rule: alert if any process uses >80% CPU
+//! // Alert if any process stays above 80% CPU for 30 seconds
 //! let rule = AlertRule {
+//!     name: "cpu".to_string(),
 //!     enabled: true,
 //!     alert_type: AlertType::HighCpu,
 //!     threshold: 80.0,
 //!     duration_secs: 30,
 //!     cooldown_secs: 300,
 //!     process_filter: None,
+//!     command_pattern: None,
+//!     expr: None,
+//!     severity: None,
 //! };
-//! manager.add_rule(rule).await;
+//!
+//! let config = NotificationConfig::default();
+//! let (mut manager, rx) = AlertManager::new(vec![rule], config.clone());
+//! tokio::spawn(AlertManager::process_alerts(rx, config));
+//!
+//! // Call with each new sample of the process list
+//! manager.check_processes(&processes).await?;
 //! # }
 //! ```
 
-use anyhow::{Context, Result};
+use crate::anomaly::Anomaly;
+use crate::columns::{format_bytes, format_elapsed};
+use crate::config::AlertConfig;
+use crate::notifications::Notifier;
+use crate::process::ProcessInfo;
+use crate::rules::{parse_duration, Expr};
+use crate::silences::{self, MaintenanceWindow, Silence};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn, error};
 
+/// Number of sent alerts kept for [`AlertManager::recent_alerts`]
+const MAX_RECENT_ALERTS: usize = 500;
+
+/// Alerts waiting for the notifier; once full, further alerts are recorded
+/// but not notified (see [`AlertManager::dropped_notifications`])
+const NOTIFICATION_QUEUE_SIZE: usize = 1000;
+
+/// Events kept for [`AlertManager::take_events`] if nobody takes them
+const MAX_PENDING_EVENTS: usize = 1000;
+
 /// Types of alerts that can be triggered.
 /// 
 /// Each type corresponds to a specific monitoring condition or event.
@@ -66,40 +101,158 @@ pub enum AlertType {
 /// Alert severity levels
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AlertSeverity {
+    #[serde(alias = "info")]
     Info,
+    #[serde(alias = "warning")]
     Warning,
+    #[serde(alias = "critical")]
     Critical,
 }
 
 /// Alert notification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
+    /// Assigned by the [`AlertManager`] when the alert is raised; repeats
+    /// while the condition stays active keep the ID
+    #[serde(default)]
+    pub id: u64,
     pub alert_type: AlertType,
     pub severity: AlertSeverity,
+    /// Name of the rule that raised the alert
+    #[serde(default)]
+    pub rule: String,
     pub process_name: String,
     pub pid: u32,
+    /// Owner of the process, if known
+    #[serde(default)]
+    pub user: String,
     pub message: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub value: Option<f64>,
     pub threshold: Option<f64>,
+    /// Exit or restart details for `ProcessTerminated`/`ProcessStarted` alerts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<LifecycleInfo>,
+    /// Set once someone acknowledges the alert; repeats of an acknowledged
+    /// condition are recorded but not notified until it clears
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged: Option<Acknowledgement>,
+    /// Silence or maintenance window that kept the alert from being notified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed_by: Option<String>,
 }
 
-/// Alert rule configuration
+/// Who acknowledged an alert, and when
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Acknowledgement {
+    pub by: String,
+    pub at: DateTime<Utc>,
+    #[serde(default)]
+    pub comment: String,
+}
+
+/// What happened to an alert, as recorded in the alert history
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEventKind {
+    /// Raised, including repeats while the condition stays active
+    Fired,
+    /// The condition no longer holds (or its rule was removed)
+    Cleared,
+    Acknowledged,
+}
+
+impl AlertEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertEventKind::Fired => "fired",
+            AlertEventKind::Cleared => "cleared",
+            AlertEventKind::Acknowledged => "acknowledged",
+        }
+    }
+
+    /// Parse `fired`, `cleared` or `acknowledged`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fired" => Some(AlertEventKind::Fired),
+            "cleared" => Some(AlertEventKind::Cleared),
+            "acknowledged" => Some(AlertEventKind::Acknowledged),
+            _ => None,
+        }
+    }
+}
+
+/// An alert as it was when it fired, cleared or was acknowledged
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub kind: AlertEventKind,
+    pub timestamp: DateTime<Utc>,
+    pub alert: Alert,
+}
+
+/// What was known about a watched process when it exited or appeared.
+/// 
+/// Exits are noticed on the first sample without the process, so the exit
+/// happened between `last_seen` and the alert timestamp.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LifecycleInfo {
+    /// Rule that matched the process
+    pub rule: String,
+    pub command: String,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+    /// Last sample that saw the process running (exits)
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Running time at `last_seen` (exits)
+    pub uptime_secs: Option<u64>,
+    /// CPU usage in the last sample, in percent (exits)
+    pub last_cpu_usage: Option<f32>,
+    /// Resident memory in the last sample, in bytes (exits)
+    pub last_memory_bytes: Option<u64>,
+    /// Cumulative CPU time at `last_seen`, in seconds (exits)
+    pub cpu_time_secs: Option<f64>,
+    /// Processes matching the rule that are still running (exits)
+    pub remaining: Option<usize>,
+    /// PID of the process this one replaced (restarts)
+    pub previous_pid: Option<u32>,
+    /// Upper bound on the downtime: from the last sample that saw the
+    /// previous process to the start of this one (restarts)
+    pub downtime_secs: Option<u64>,
+}
+
+/// Alert rule configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AlertRule {
+    /// Identifies the rule in logs and `GET /api/alerts/rules`
+    #[serde(default)]
+    pub name: String,
     pub enabled: bool,
     pub alert_type: AlertType,
     pub threshold: f64,
     pub duration_secs: u64,
     pub cooldown_secs: u64,
+    /// Only processes whose name contains this string
     pub process_filter: Option<String>,
+    /// Only processes whose command line matches this regex
+    #[serde(default)]
+    pub command_pattern: Option<String>,
+    /// Condition in the [`crate::rules`] language; replaces `threshold`
+    #[serde(default)]
+    pub expr: Option<String>,
+    /// Severity of the alerts; by default CPU and memory alerts are critical
+    /// at 1.5 times the threshold and warnings below
+    #[serde(default)]
+    pub severity: Option<AlertSeverity>,
 }
 
-/// Notification channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Notification channel configuration (`[alerts.notifications]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NotificationConfig {
+    #[serde(default)]
     pub email: Option<EmailConfig>,
+    #[serde(default)]
     pub webhook: Option<WebhookConfig>,
+    #[serde(default)]
     pub desktop: bool,
 }
 
@@ -118,18 +271,229 @@ pub struct EmailConfig {
 pub struct WebhookConfig {
     pub enabled: bool,
     pub url: String,
+    #[serde(default)]
     pub headers: HashMap<String, String>,
 }
 
+/// Severity (0.0 to 1.0) from which anomalies are raised as alerts
+const ANOMALY_ALERT_SEVERITY: f64 = 0.5;
+
+/// Build the alert rules described by `[alerts]`.
+///
+/// `cpu_threshold` and `memory_threshold` become rules for every process that
+/// must hold for `duration_secs`. Each bookmarked process with
+/// `alert_on_high_cpu` or `alert_on_high_memory` gets its own rule with the
+/// same threshold that fires as soon as it is crossed, and `alert_on_exit` and
+/// `alert_on_start` add lifecycle rules. Bookmark rules match on the process
+/// name and, when set, the bookmark's `command_pattern`. Each
+/// `[[alerts.rules]]` entry becomes an expression rule with alert type
+/// `Custom(name)`. The `enabled` switch is not applied here.
+pub fn rules_from_config(config: &AlertConfig) -> Vec<AlertRule> {
+    let mut rules = vec![
+        AlertRule {
+            name: "cpu".to_string(),
+            enabled: true,
+            alert_type: AlertType::HighCpu,
+            threshold: config.cpu_threshold as f64,
+            duration_secs: config.duration_secs,
+            cooldown_secs: config.cooldown_secs,
+            process_filter: None,
+            command_pattern: None,
+            expr: None,
+            severity: None,
+        },
+        AlertRule {
+            name: "memory".to_string(),
+            enabled: true,
+            alert_type: AlertType::HighMemory,
+            threshold: config.memory_threshold as f64,
+            duration_secs: config.duration_secs,
+            cooldown_secs: config.cooldown_secs,
+            process_filter: None,
+            command_pattern: None,
+            expr: None,
+            severity: None,
+        },
+    ];
+
+    if config.alert_on_anomalies {
+        rules.push(AlertRule {
+            name: "anomaly".to_string(),
+            enabled: true,
+            alert_type: AlertType::AnomalyDetected,
+            threshold: ANOMALY_ALERT_SEVERITY,
+            duration_secs: 0,
+            cooldown_secs: config.cooldown_secs,
+            process_filter: None,
+            command_pattern: None,
+            expr: None,
+            severity: None,
+        });
+    }
+
+    for rule in &config.rules {
+        let seconds = |text: &Option<String>, default: u64| {
+            text.as_deref().and_then(|t| parse_duration(t).ok()).unwrap_or(default)
+        };
+        rules.push(AlertRule {
+            name: rule.name.clone(),
+            enabled: rule.enabled,
+            alert_type: AlertType::Custom(rule.name.clone()),
+            threshold: 0.0,
+            duration_secs: seconds(&rule.for_duration, 0),
+            cooldown_secs: seconds(&rule.cooldown, config.cooldown_secs),
+            process_filter: None,
+            command_pattern: None,
+            expr: Some(rule.expr.clone()),
+            severity: Some(rule.severity.clone()),
+        });
+    }
+
+    for bookmark in &config.bookmarked_processes {
+        let watched = [
+            (bookmark.alert_on_high_cpu, "cpu", AlertType::HighCpu, config.cpu_threshold),
+            (bookmark.alert_on_high_memory, "memory", AlertType::HighMemory, config.memory_threshold),
+            (bookmark.alert_on_exit, "exit", AlertType::ProcessTerminated, 0.0),
+            (bookmark.alert_on_start, "start", AlertType::ProcessStarted, 0.0),
+        ];
+        for (_, suffix, alert_type, threshold) in watched.into_iter().filter(|w| w.0) {
+            rules.push(AlertRule {
+                name: format!("bookmark:{}:{}", bookmark.name, suffix),
+                enabled: true,
+                alert_type,
+                threshold: threshold as f64,
+                duration_secs: 0,
+                cooldown_secs: config.cooldown_secs,
+                process_filter: Some(bookmark.name.clone()),
+                command_pattern: bookmark.command_pattern.clone(),
+                expr: None,
+                severity: None,
+            });
+        }
+    }
+
+    rules
+}
+
+fn lifecycle_alert(
+    rule: &AlertRule,
+    severity: AlertSeverity,
+    (name, pid, user): (&str, u32, &str),
+    message: String,
+    lifecycle: LifecycleInfo,
+) -> Alert {
+    Alert {
+        id: 0,
+        alert_type: rule.alert_type.clone(),
+        severity,
+        rule: rule.name.clone(),
+        process_name: name.to_string(),
+        pid,
+        user: user.to_string(),
+        message,
+        timestamp: Utc::now(),
+        value: None,
+        threshold: None,
+        lifecycle: Some(lifecycle),
+        acknowledged: None,
+        suppressed_by: None,
+    }
+}
+
+/// Compile the `command_pattern` of each rule; invalid patterns are logged
+/// and their rules never match
+fn compile_patterns(rules: &[AlertRule]) -> HashMap<String, Regex> {
+    let mut patterns = HashMap::new();
+    for pattern in rules.iter().filter_map(|rule| rule.command_pattern.as_ref()) {
+        match Regex::new(pattern) {
+            Ok(regex) => {
+                patterns.insert(pattern.clone(), regex);
+            }
+            Err(e) => warn!("Ignoring alert rules with invalid command pattern {:?}: {}", pattern, e),
+        }
+    }
+    patterns
+}
+
+/// Compile the `expr` of each rule; invalid expressions are logged and their
+/// rules never match
+fn compile_expressions(rules: &[AlertRule]) -> HashMap<String, Expr> {
+    let mut expressions = HashMap::new();
+    for source in rules.iter().filter_map(|rule| rule.expr.as_ref()) {
+        match Expr::parse(source) {
+            Ok(expr) => {
+                expressions.insert(source.clone(), expr);
+            }
+            Err(e) => warn!("Ignoring alert rules with invalid expression {:?}: {}", source, e),
+        }
+    }
+    expressions
+}
+
 /// Alert manager to handle alert rules and notifications
 pub struct AlertManager {
     rules: Vec<AlertRule>,
     #[allow(dead_code)]
     notification_config: NotificationConfig,
     alert_state: HashMap<(AlertType, u32), AlertState>,
+    /// Last alert sent for each condition that has not cleared yet
+    active: HashMap<(AlertType, u32), Alert>,
+    /// Sent alerts, oldest first
+    recent: VecDeque<Alert>,
+    /// Compiled `command_pattern` of the rules
+    patterns: HashMap<String, Regex>,
+    /// Compiled `expr` of the rules
+    expressions: HashMap<String, Expr>,
+    /// Processes in the previous sample; `None` until the first one
+    seen: Option<HashMap<u32, SeenProcess>>,
+    /// Exit of a process matched by each `ProcessTerminated` rule, kept until
+    /// a matching process appears again
+    exits: HashMap<String, (u32, DateTime<Utc>)>,
+    /// When each lifecycle rule last sent an alert
+    lifecycle_sent: HashMap<(String, AlertType), Instant>,
+    /// Silences that have not expired yet
+    silences: Vec<Silence>,
+    maintenance_windows: Vec<MaintenanceWindow>,
+    /// ID of the next alert raised
+    next_id: u64,
+    /// Fired, cleared and acknowledged alerts not yet taken with
+    /// [`AlertManager::take_events`], oldest first
+    events: VecDeque<AlertEvent>,
+    /// Alerts not notified because the notifier fell behind
+    dropped_notifications: u64,
     alert_tx: mpsc::Sender<Alert>,
 }
 
+/// Last sample of a process, kept to describe it once it exits
+#[derive(Debug, Clone)]
+struct SeenProcess {
+    name: String,
+    user: String,
+    command: String,
+    start_time: u64,
+    running_time: Duration,
+    cpu_usage: f32,
+    memory_usage: u64,
+    cpu_time: f64,
+    last_seen: DateTime<Utc>,
+}
+
+impl SeenProcess {
+    fn new(process: &ProcessInfo, now: DateTime<Utc>) -> Self {
+        Self {
+            name: process.name.clone(),
+            user: process.user.clone(),
+            command: process.command.clone(),
+            start_time: process.start_time,
+            running_time: process.running_time,
+            cpu_usage: process.cpu_usage,
+            memory_usage: process.memory_usage,
+            cpu_time: process.cpu_time.total(),
+            last_seen: now,
+        }
+    }
+}
+
 struct AlertState {
     triggered: bool,
     first_seen: Instant,
@@ -142,53 +506,231 @@ impl AlertManager {
         rules: Vec<AlertRule>,
         notification_config: NotificationConfig,
     ) -> (Self, mpsc::Receiver<Alert>) {
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(NOTIFICATION_QUEUE_SIZE);
         
         let manager = Self {
+            patterns: compile_patterns(&rules),
+            expressions: compile_expressions(&rules),
             rules,
             notification_config,
             alert_state: HashMap::new(),
+            active: HashMap::new(),
+            recent: VecDeque::new(),
+            seen: None,
+            exits: HashMap::new(),
+            lifecycle_sent: HashMap::new(),
+            silences: Vec::new(),
+            maintenance_windows: Vec::new(),
+            next_id: 1,
+            events: VecDeque::new(),
+            dropped_notifications: 0,
             alert_tx: tx,
         };
         
         (manager, rx)
     }
+
+    /// The rules currently evaluated
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// Replace the rules, e.g. after a config reload.
+    ///
+    /// Conditions whose alert type no longer has a rule are dropped along with
+    /// their active alerts; the others keep their duration and cooldown timers.
+    pub fn set_rules(&mut self, rules: Vec<AlertRule>) {
+        let types: HashSet<AlertType> = rules.iter()
+            .filter(|rule| rule.enabled)
+            .map(|rule| rule.alert_type.clone())
+            .collect();
+        let names: HashSet<&str> = rules.iter().map(|rule| rule.name.as_str()).collect();
+        self.alert_state.retain(|(alert_type, _), _| types.contains(alert_type));
+        self.clear_active(|(alert_type, _)| types.contains(alert_type));
+        self.exits.retain(|name, _| names.contains(name.as_str()));
+        self.lifecycle_sent.retain(|(name, _), _| names.contains(name.as_str()));
+        self.patterns = compile_patterns(&rules);
+        self.expressions = compile_expressions(&rules);
+        self.rules = rules;
+    }
+
+    /// Alerts whose condition still holds, newest first
+    pub fn active_alerts(&self) -> Vec<Alert> {
+        let mut alerts: Vec<Alert> = self.active.values().cloned().collect();
+        alerts.sort_by_key(|alert| std::cmp::Reverse(alert.timestamp));
+        alerts
+    }
+
+    /// Up to `limit` of the most recently sent alerts, newest first
+    pub fn recent_alerts(&self, limit: usize) -> Vec<Alert> {
+        self.recent.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Acknowledge the active alert with this ID. Returns `None` if no active
+    /// alert has it, e.g. because its condition cleared.
+    pub fn acknowledge(&mut self, id: u64, by: &str, comment: String) -> Option<Alert> {
+        let acknowledgement = Acknowledgement { by: by.to_string(), at: Utc::now(), comment };
+        let alert = self.active.values_mut().find(|alert| alert.id == id)?;
+        alert.acknowledged = Some(acknowledgement.clone());
+        let alert = alert.clone();
+        for recent in self.recent.iter_mut().filter(|recent| recent.id == id) {
+            recent.acknowledged = Some(acknowledgement.clone());
+        }
+        info!("Alert {} acknowledged by {}: {}", id, by, alert.message);
+        self.record_event(AlertEventKind::Acknowledged, alert.clone());
+        Some(alert)
+    }
+
+    /// Alerts that were not notified because the notification queue was full
+    pub fn dropped_notifications(&self) -> u64 {
+        self.dropped_notifications
+    }
+
+    /// Take the alerts fired, cleared or acknowledged since the last call,
+    /// oldest first, e.g. to store them in the history database. Only the
+    /// newest 1000 are kept between calls.
+    pub fn take_events(&mut self) -> Vec<AlertEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Continue numbering alerts after `last_id`, e.g. the highest ID in the
+    /// alert history, so IDs stay unique across restarts
+    pub fn resume_ids(&mut self, last_id: u64) {
+        self.next_id = self.next_id.max(last_id + 1);
+    }
+
+    fn record_event(&mut self, kind: AlertEventKind, alert: Alert) {
+        if self.events.len() == MAX_PENDING_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(AlertEvent { kind, timestamp: Utc::now(), alert });
+    }
+
+    /// Stop tracking the active alerts whose key fails `keep`, recording
+    /// them as cleared
+    fn clear_active(&mut self, keep: impl Fn(&(AlertType, u32)) -> bool) {
+        let mut cleared: Vec<Alert> = Vec::new();
+        self.active.retain(|key, alert| {
+            if keep(key) {
+                return true;
+            }
+            cleared.push(alert.clone());
+            false
+        });
+        cleared.sort_by_key(|alert| alert.id);
+        for alert in cleared {
+            self.record_event(AlertEventKind::Cleared, alert);
+        }
+    }
+
+    /// Silences that have not expired, in creation order
+    pub fn silences(&self) -> &[Silence] {
+        &self.silences
+    }
+
+    /// Add a silence. An `id` of 0 is replaced with the next free one.
+    pub fn add_silence(&mut self, mut silence: Silence) -> Silence {
+        if silence.id == 0 {
+            silence.id = self.silences.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+        }
+        self.silences.push(silence.clone());
+        silence
+    }
+
+    /// Remove a silence; returns whether it existed
+    pub fn remove_silence(&mut self, id: i64) -> bool {
+        let before = self.silences.len();
+        self.silences.retain(|s| s.id != id);
+        self.silences.len() != before
+    }
+
+    pub fn maintenance_windows(&self) -> &[MaintenanceWindow] {
+        &self.maintenance_windows
+    }
+
+    /// Replace the maintenance windows from the config file, keeping those
+    /// added with [`AlertManager::add_maintenance_window`]
+    pub fn set_config_maintenance_windows(&mut self, windows: Vec<MaintenanceWindow>) {
+        self.maintenance_windows.retain(|w| w.id.is_some());
+        self.maintenance_windows.extend(windows.into_iter().map(|w| MaintenanceWindow { id: None, ..w }));
+    }
+
+    /// Add a window. An `id` of `None` or 0 is replaced with the next free one.
+    pub fn add_maintenance_window(&mut self, mut window: MaintenanceWindow) -> MaintenanceWindow {
+        if window.id.unwrap_or(0) == 0 {
+            window.id = Some(self.maintenance_windows.iter().filter_map(|w| w.id).max().unwrap_or(0) + 1);
+        }
+        self.maintenance_windows.push(window.clone());
+        window
+    }
+
+    /// Remove a window added through [`AlertManager::add_maintenance_window`];
+    /// returns whether it existed
+    pub fn remove_maintenance_window(&mut self, id: i64) -> bool {
+        let before = self.maintenance_windows.len();
+        self.maintenance_windows.retain(|w| w.id != Some(id));
+        self.maintenance_windows.len() != before
+    }
+
+    /// Check every process in a sample against the rules.
+    ///
+    /// Compared with the previous sample, processes that disappeared or
+    /// appeared raise `ProcessTerminated` and `ProcessStarted` alerts for the
+    /// lifecycle rules they match; the first sample only sets the baseline.
+    /// Resource state for exited processes is forgotten, so their CPU and
+    /// memory alerts stop being active. Expired silences are dropped.
+    pub async fn check_processes(&mut self, processes: &[ProcessInfo]) -> Result<()> {
+        let now = Utc::now();
+        self.silences.retain(|s| !s.is_expired(now));
+        for process in processes {
+            self.check_process(process).await?;
+        }
+        self.check_lifecycle(processes).await?;
+
+        // Exit alerts stay active until the watched process is back
+        let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        self.alert_state.retain(|(_, pid), _| pids.contains(pid));
+        self.clear_active(|(alert_type, pid)| *alert_type == AlertType::ProcessTerminated || pids.contains(pid));
+        Ok(())
+    }
     
-    /// Check process against alert rules
-    pub async fn check_process(
-        &mut self,
-        pid: u32,
-        name: &str,
-        cpu_usage: f32,
-        memory_percent: f32,
-    ) -> Result<()> {
+    /// Check process against the CPU, memory and expression rules
+    pub async fn check_process(&mut self, process: &ProcessInfo) -> Result<()> {
+        let (pid, name) = (process.pid, process.name.as_str());
+        let (cpu_usage, memory_percent) = (process.cpu_usage, process.memory_percent);
         debug!("Checking process {} (pid {}) against alert rules: cpu={:.2}%, mem={:.2}%", 
                name, pid, cpu_usage, memory_percent);
         
         // Collect rules to check to avoid borrow issues
-        let checks: Vec<_> = self.rules.iter()
-            .filter(|rule| rule.enabled)
-            .filter_map(|rule| {
-                // Check process filter
-                if let Some(ref filter) = rule.process_filter {
-                    if !name.contains(filter) {
-                        return None;
+        let mut checks: Vec<(AlertRule, bool, Option<f64>)> = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.enabled) {
+            // Check process filter
+            if !self.rule_matches(rule, name, &process.command) {
+                continue;
+            }
+
+            let (triggered, value) = match (&rule.expr, &rule.alert_type) {
+                (Some(expr), _) => (self.expressions.get(expr).is_some_and(|e| e.matches(process)), None),
+                (None, AlertType::HighCpu) => (cpu_usage as f64 > rule.threshold, Some(cpu_usage as f64)),
+                (None, AlertType::HighMemory) => (memory_percent as f64 > rule.threshold, Some(memory_percent as f64)),
+                _ => continue,
+            };
+
+            // State is kept per alert type, so only one rule of each type
+            // applies; one scoped to this process wins over a global one
+            match checks.iter_mut().find(|(r, _, _)| r.alert_type == rule.alert_type) {
+                Some(check) => {
+                    if check.0.process_filter.is_none() && rule.process_filter.is_some() {
+                        *check = (rule.clone(), triggered, value);
                     }
                 }
-                
-                let (triggered, value) = match rule.alert_type {
-                    AlertType::HighCpu => (cpu_usage as f64 > rule.threshold, cpu_usage as f64),
-                    AlertType::HighMemory => (memory_percent as f64 > rule.threshold, memory_percent as f64),
-                    _ => return None,
-                };
-                
-                Some((rule.clone(), triggered, value))
-            })
-            .collect();
+                None => checks.push((rule.clone(), triggered, value)),
+            }
+        }
         
         for (rule, triggered, value) in checks {
             if triggered {
-                self.handle_trigger(&rule, pid, name, value).await?;
+                self.handle_trigger(&rule, process, value).await?;
             } else {
                 self.handle_clear(&rule, pid).await;
             }
@@ -200,17 +742,17 @@ impl AlertManager {
     async fn handle_trigger(
         &mut self,
         rule: &AlertRule,
-        pid: u32,
-        name: &str,
-        value: f64,
+        process: &ProcessInfo,
+        value: Option<f64>,
     ) -> Result<()> {
+        let (pid, name) = (process.pid, process.name.as_str());
         let key = (rule.alert_type.clone(), pid);
         let now = Instant::now();
         
         let state = self.alert_state.entry(key.clone()).or_insert(AlertState {
             triggered: false,
             first_seen: now,
-            last_sent: now - Duration::from_secs(rule.cooldown_secs + 1),
+            last_sent: now.checked_sub(Duration::from_secs(rule.cooldown_secs + 1)).unwrap_or(now),
             count: 0,
         });
         
@@ -220,23 +762,21 @@ impl AlertManager {
         }
         
         // Check cooldown
-        if now.duration_since(state.last_sent).as_secs() < rule.cooldown_secs {
+        if state.count > 0 && now.duration_since(state.last_sent).as_secs() < rule.cooldown_secs {
             return Ok(());
         }
         
         // Send alert
-        let severity = if value > rule.threshold * 1.5 {
-            AlertSeverity::Critical
-        } else {
-            AlertSeverity::Warning
-        };
+        let severity = rule.severity.clone().unwrap_or(
+            if value.is_some_and(|value| value > rule.threshold * 1.5) {
+                AlertSeverity::Critical
+            } else {
+                AlertSeverity::Warning
+            }
+        );
         
-        let alert = Alert {
-            alert_type: rule.alert_type.clone(),
-            severity: severity.clone(),
-            process_name: name.to_string(),
-            pid,
-            message: format!(
+        let message = match value {
+            Some(value) => format!(
                 "Process '{}' (PID: {}) exceeded {} threshold: {:.2} > {:.2}",
                 name, pid,
                 match rule.alert_type {
@@ -246,26 +786,42 @@ impl AlertManager {
                 },
                 value, rule.threshold
             ),
-            timestamp: chrono::Utc::now(),
-            value: Some(value),
-            threshold: Some(rule.threshold),
+            None => format!(
+                "Process '{}' (PID: {}) matched rule '{}'{}: {}",
+                name, pid, rule.name,
+                if rule.duration_secs > 0 { format!(" for {}s", rule.duration_secs) } else { String::new() },
+                rule.expr.as_deref().unwrap_or_default()
+            ),
         };
         
         match severity {
-            AlertSeverity::Critical => error!("CRITICAL alert triggered for {} (pid {}): {:.2} > {:.2}", 
-                                              name, pid, value, rule.threshold),
-            AlertSeverity::Warning => warn!("Warning alert triggered for {} (pid {}): {:.2} > {:.2}", 
-                                            name, pid, value, rule.threshold),
-            AlertSeverity::Info => info!("Info alert triggered for {} (pid {}): {:.2} > {:.2}", 
-                                        name, pid, value, rule.threshold),
+            AlertSeverity::Critical => error!("CRITICAL alert triggered: {}", message),
+            AlertSeverity::Warning => warn!("Warning alert triggered: {}", message),
+            AlertSeverity::Info => info!("Info alert triggered: {}", message),
         }
         
-        self.alert_tx.send(alert).await?;
+        let alert = Alert {
+            id: 0,
+            alert_type: rule.alert_type.clone(),
+            severity,
+            rule: rule.name.clone(),
+            process_name: name.to_string(),
+            pid,
+            user: process.user.clone(),
+            message,
+            timestamp: chrono::Utc::now(),
+            value,
+            threshold: value.map(|_| rule.threshold),
+            lifecycle: None,
+            acknowledged: None,
+            suppressed_by: None,
+        };
+        
         state.last_sent = now;
         state.count += 1;
         state.triggered = true;
         
-        Ok(())
+        self.deliver(alert, Some(key)).await
     }
     
     async fn handle_clear(&mut self, rule: &AlertRule, pid: u32) {
@@ -277,164 +833,289 @@ impl AlertManager {
             state.triggered = false;
             state.first_seen = Instant::now();
         }
+        if let Some(alert) = self.active.remove(&key) {
+            self.record_event(AlertEventKind::Cleared, alert);
+        }
     }
     
-    /// Process alert notifications in background
-    pub async fn process_alerts(
-        mut rx: mpsc::Receiver<Alert>,
-        config: NotificationConfig,
-    ) {
-        info!("Starting alert notification processor");
+    /// Whether `rule` applies to a process with this name and command line
+    fn rule_matches(&self, rule: &AlertRule, name: &str, command: &str) -> bool {
+        if let Some(ref filter) = rule.process_filter {
+            if !name.contains(filter.as_str()) {
+                return false;
+            }
+        }
+        match rule.command_pattern {
+            Some(ref pattern) => {
+                // Kernel threads have no command line
+                let command = if command.is_empty() { name } else { command };
+                self.patterns.get(pattern).is_some_and(|regex| regex.is_match(command))
+            }
+            None => true,
+        }
+    }
+    
+    /// Raise alerts for watched processes that exited, restarted or appeared
+    /// since the previous sample
+    async fn check_lifecycle(&mut self, processes: &[ProcessInfo]) -> Result<()> {
+        let now = Utc::now();
+        let current: HashMap<u32, SeenProcess> = processes.iter()
+            .map(|p| (p.pid, SeenProcess::new(p, now)))
+            .collect();
+        let previous = match self.seen.replace(current) {
+            Some(previous) => previous,
+            None => return Ok(()),
+        };
         
-        while let Some(alert) = rx.recv().await {
-            info!("Processing {:?} alert: {}", alert.severity, alert.message);
-            
-            // Send desktop notification
-            if config.desktop {
-                debug!("Sending desktop notification for alert");
-                if let Err(e) = send_desktop_notification(&alert) {
-                    error!("Failed to send desktop notification: {}", e);
-                } else {
-                    debug!("Desktop notification sent successfully");
-                }
+        let lifecycle_rules: Vec<AlertRule> = self.rules.iter()
+            .filter(|rule| rule.enabled)
+            .filter(|rule| matches!(rule.alert_type, AlertType::ProcessTerminated | AlertType::ProcessStarted))
+            .cloned()
+            .collect();
+        if lifecycle_rules.is_empty() {
+            return Ok(());
+        }
+        
+        // A PID seen with a different start time was reused: the old process
+        // exited and a new one started
+        let mut exited: Vec<(u32, SeenProcess)> = previous.iter()
+            .filter(|(pid, old)| processes.iter().all(|p| p.pid != **pid || p.start_time != old.start_time))
+            .map(|(pid, old)| (*pid, old.clone()))
+            .collect();
+        exited.sort_by_key(|(pid, _)| *pid);
+        let started: Vec<&ProcessInfo> = processes.iter()
+            .filter(|p| previous.get(&p.pid).is_none_or(|old| old.start_time != p.start_time))
+            .collect();
+        
+        for rule in lifecycle_rules.iter().filter(|rule| rule.alert_type == AlertType::ProcessTerminated) {
+            let matched: Vec<&(u32, SeenProcess)> = exited.iter()
+                .filter(|(_, old)| self.rule_matches(rule, &old.name, &old.command))
+                .collect();
+            if matched.is_empty() {
+                continue;
             }
+            let remaining = processes.iter()
+                .filter(|p| self.rule_matches(rule, &p.name, &p.command))
+                .count();
             
-            // Send email
-            if let Some(ref email_config) = config.email {
-                if email_config.enabled {
-                    debug!("Sending email notification to {:?}", email_config.to);
-                    if let Err(e) = send_email_notification(&alert, email_config).await {
-                        error!("Failed to send email notification: {}", e);
+            for (pid, old) in matched {
+                self.exits.insert(rule.name.clone(), (*pid, old.last_seen));
+                
+                let lifecycle = LifecycleInfo {
+                    rule: rule.name.clone(),
+                    command: old.command.clone(),
+                    start_time: old.start_time,
+                    last_seen: Some(old.last_seen),
+                    uptime_secs: Some(old.running_time.as_secs()),
+                    last_cpu_usage: Some(old.cpu_usage),
+                    last_memory_bytes: Some(old.memory_usage),
+                    cpu_time_secs: Some(old.cpu_time),
+                    remaining: Some(remaining),
+                    ..Default::default()
+                };
+                let message = format!(
+                    "Watched process '{}' (PID: {}) exited after running {}; last seen {} using {:.1}% CPU and {} memory{}",
+                    old.name, pid,
+                    format_elapsed(old.running_time.as_secs()),
+                    old.last_seen.format("%H:%M:%S UTC"),
+                    old.cpu_usage,
+                    format_bytes(old.memory_usage as f64),
+                    if remaining == 0 {
+                        " (no matching process left)".to_string()
                     } else {
-                        info!("Email notification sent successfully to {:?}", email_config.to);
-                    }
-                }
+                        format!(" ({} matching still running)", remaining)
+                    },
+                );
+                // The service is down until a matching process appears again
+                let severity = if remaining == 0 { AlertSeverity::Critical } else { AlertSeverity::Warning };
+                let alert = lifecycle_alert(rule, severity, (&old.name, *pid, &old.user), message, lifecycle);
+                let active_key = (remaining == 0).then_some((AlertType::ProcessTerminated, *pid));
+                self.send_lifecycle(rule, alert, active_key).await?;
             }
-            
-            // Send webhook
-            if let Some(ref webhook_config) = config.webhook {
-                if webhook_config.enabled {
-                    debug!("Sending webhook notification to {}", webhook_config.url);
-                    if let Err(e) = send_webhook_notification(&alert, webhook_config).await {
-                        error!("Failed to send webhook notification: {}", e);
-                    } else {
-                        info!("Webhook notification sent successfully to {}", webhook_config.url);
+        }
+        
+        for process in started {
+            let mut restarted = false;
+            for rule in lifecycle_rules.iter() {
+                if !self.rule_matches(rule, &process.name, &process.command) {
+                    continue;
+                }
+                let mut lifecycle = LifecycleInfo {
+                    rule: rule.name.clone(),
+                    command: process.command.clone(),
+                    start_time: process.start_time,
+                    ..Default::default()
+                };
+                
+                match rule.alert_type {
+                    AlertType::ProcessTerminated => {
+                        let Some((previous_pid, last_seen)) = self.exits.remove(&rule.name) else {
+                            continue;
+                        };
+                        if let Some(exit) = self.active.remove(&(AlertType::ProcessTerminated, previous_pid)) {
+                            self.record_event(AlertEventKind::Cleared, exit);
+                        }
+                        let downtime = (process.start_time as i64 - last_seen.timestamp()).max(0) as u64;
+                        lifecycle.previous_pid = Some(previous_pid);
+                        lifecycle.downtime_secs = Some(downtime);
+                        let message = format!(
+                            "Watched process '{}' restarted as PID {} (PID {} exited, down at most {})",
+                            process.name, process.pid, previous_pid, format_elapsed(downtime),
+                        );
+                        restarted = true;
+                        let mut alert = lifecycle_alert(rule, AlertSeverity::Warning, (&process.name, process.pid, &process.user), message, lifecycle);
+                        alert.alert_type = AlertType::ProcessStarted;
+                        self.send_lifecycle(rule, alert, None).await?;
                     }
+                    // A restart alert already covers this process
+                    AlertType::ProcessStarted if !restarted => {
+                        let message = format!(
+                            "Watched process '{}' started (PID: {})",
+                            process.name, process.pid,
+                        );
+                        let alert = lifecycle_alert(rule, AlertSeverity::Info, (&process.name, process.pid, &process.user), message, lifecycle);
+                        self.send_lifecycle(rule, alert, None).await?;
+                    }
+                    _ => {}
                 }
             }
         }
         
-        info!("Alert notification processor terminated");
+        Ok(())
     }
-}
-
-/// Send desktop notification
-fn send_desktop_notification(alert: &Alert) -> Result<()> {
-    #[cfg(target_os = "linux")]
-    {
-        use notify_rust::{Notification, Urgency};
+    
+    /// Send a lifecycle alert unless `rule` sent one within its cooldown
+    async fn send_lifecycle(
+        &mut self,
+        rule: &AlertRule,
+        alert: Alert,
+        active_key: Option<(AlertType, u32)>,
+    ) -> Result<()> {
+        let now = Instant::now();
+        let sent_key = (rule.name.clone(), alert.alert_type.clone());
+        if self.lifecycle_sent.get(&sent_key)
+            .is_some_and(|sent| now.duration_since(*sent).as_secs() < rule.cooldown_secs)
+        {
+            debug!("Suppressed within cooldown of rule {}: {}", rule.name, alert.message);
+            return Ok(());
+        }
+        self.lifecycle_sent.insert(sent_key, now);
         
-        let urgency = match alert.severity {
-            AlertSeverity::Critical => Urgency::Critical,
-            AlertSeverity::Warning => Urgency::Normal,
-            AlertSeverity::Info => Urgency::Low,
+        match alert.severity {
+            AlertSeverity::Critical => error!("{}", alert.message),
+            AlertSeverity::Warning => warn!("{}", alert.message),
+            AlertSeverity::Info => info!("{}", alert.message),
+        }
+        self.deliver(alert, active_key).await
+    }
+    
+    /// Raise `AnomalyDetected` alerts for anomalies at or above the anomaly
+    /// rule's severity threshold
+    pub async fn report_anomalies(&mut self, anomalies: &[Anomaly]) -> Result<()> {
+        let rule = match self.rules.iter().find(|r| r.enabled && r.alert_type == AlertType::AnomalyDetected) {
+            Some(rule) => rule.clone(),
+            None => return Ok(()),
         };
         
-        Notification::new()
-            .summary("Process Manager Alert")
-            .body(&alert.message)
-            .urgency(urgency)
-            .timeout(5000)
-            .show()
-            .context("Failed to show desktop notification")?;
+        for anomaly in anomalies.iter().filter(|a| a.severity as f64 >= rule.threshold) {
+            if !self.rule_matches(&rule, &anomaly.process_name, "") {
+                continue;
+            }
+            let key = (AlertType::AnomalyDetected, anomaly.pid);
+            let now = Instant::now();
+            if self.alert_state.get(&key)
+                .is_some_and(|state| now.duration_since(state.last_sent).as_secs() < rule.cooldown_secs)
+            {
+                continue;
+            }
+            self.alert_state.insert(key, AlertState { triggered: true, first_seen: now, last_sent: now, count: 1 });
+            
+            let severity = if anomaly.severity >= 0.8 { AlertSeverity::Critical } else { AlertSeverity::Warning };
+            warn!("Anomaly alert for {} (pid {}): {}", anomaly.process_name, anomaly.pid, anomaly.description);
+            let user = self.seen.as_ref()
+                .and_then(|seen| seen.get(&anomaly.pid))
+                .map(|p| p.user.clone())
+                .unwrap_or_default();
+            let alert = Alert {
+                id: 0,
+                alert_type: AlertType::AnomalyDetected,
+                severity,
+                rule: rule.name.clone(),
+                process_name: anomaly.process_name.clone(),
+                pid: anomaly.pid,
+                user,
+                message: format!(
+                    "Anomaly in '{}' (PID: {}): {}",
+                    anomaly.process_name, anomaly.pid, anomaly.description
+                ),
+                timestamp: anomaly.timestamp,
+                value: Some(anomaly.current_value),
+                threshold: Some(anomaly.threshold),
+                lifecycle: None,
+                acknowledged: None,
+                suppressed_by: None,
+            };
+            self.deliver(alert, None).await?;
+        }
+        
+        Ok(())
     }
     
-    Ok(())
-}
-
-/// Send email notification
-async fn send_email_notification(alert: &Alert, config: &EmailConfig) -> Result<()> {
-    use lettre::{
-        Message, SmtpTransport, Transport,
-        transport::smtp::authentication::Credentials,
-    };
-    
-    let subject = format!(
-        "[{}] Process Manager Alert: {}",
-        match alert.severity {
-            AlertSeverity::Critical => "CRITICAL",
-            AlertSeverity::Warning => "WARNING",
-            AlertSeverity::Info => "INFO",
-        },
-        alert.process_name
-    );
-    
-    let body = format!(
-        "Alert Details:\n\n\
-         Type: {:?}\n\
-         Process: {} (PID: {})\n\
-         Message: {}\n\
-         Time: {}\n\
-         Value: {:?}\n\
-         Threshold: {:?}\n",
-        alert.alert_type,
-        alert.process_name,
-        alert.pid,
-        alert.message,
-        alert.timestamp,
-        alert.value,
-        alert.threshold,
-    );
-    
-    for recipient in &config.to {
-        let email = Message::builder()
-            .from(config.from.parse()?)
-            .to(recipient.parse()?)
-            .subject(&subject)
-            .body(body.clone())?;
-        
-        let creds = Credentials::new(
-            config.username.clone(),
-            config.password.clone(),
-        );
+    /// Queue `alert` for notification and record it as recent and as a fired
+    /// event (and as active under `active_key`, if given).
+    /// 
+    /// Alerts matched by a silence or maintenance window, and repeats of an
+    /// acknowledged alert, are recorded without being notified. The notifier
+    /// is never waited for: when its queue is full the alert is recorded and
+    /// counted in [`AlertManager::dropped_notifications`].
+    async fn deliver(&mut self, mut alert: Alert, active_key: Option<(AlertType, u32)>) -> Result<()> {
+        match active_key.as_ref().and_then(|key| self.active.get(key)) {
+            Some(active) => {
+                alert.id = active.id;
+                alert.acknowledged = active.acknowledged.clone();
+            }
+            None => {
+                alert.id = self.next_id;
+                self.next_id += 1;
+            }
+        }
+        alert.suppressed_by = silences::suppression(&alert, &self.silences, &self.maintenance_windows, Utc::now());
         
-        let mailer = SmtpTransport::relay(&config.smtp_server)?
-            .credentials(creds)
-            .port(config.smtp_port)
-            .build();
+        match (&alert.suppressed_by, &alert.acknowledged) {
+            (Some(reason), _) => info!("Alert suppressed by {}: {}", reason, alert.message),
+            (None, Some(ack)) => debug!("Alert already acknowledged by {}: {}", ack.by, alert.message),
+            // Never wait for the notifier: callers hold the manager's lock
+            (None, None) => match self.alert_tx.try_send(alert.clone()) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    self.dropped_notifications += 1;
+                    warn!("Notification queue full, alert not notified: {}", alert.message);
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => anyhow::bail!("Alert notifier has stopped"),
+            },
+        }
         
-        mailer.send(&email)?;
+        if let Some(key) = active_key {
+            self.active.insert(key, alert.clone());
+        }
+        self.record_event(AlertEventKind::Fired, alert.clone());
+        if self.recent.len() == MAX_RECENT_ALERTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(alert);
+        Ok(())
     }
-    
-    Ok(())
-}
 
-/// Send webhook notification
-async fn send_webhook_notification(alert: &Alert, config: &WebhookConfig) -> Result<()> {
-    let client = reqwest::Client::new();
-    let mut request = client.post(&config.url)
-        .json(&serde_json::json!({
-            "alert_type": format!("{:?}", alert.alert_type),
-            "severity": format!("{:?}", alert.severity),
-            "process_name": alert.process_name,
-            "pid": alert.pid,
-            "message": alert.message,
-            "timestamp": alert.timestamp.to_rfc3339(),
-            "value": alert.value,
-            "threshold": alert.threshold,
-        }));
-    
-    for (key, value) in &config.headers {
-        request = request.header(key, value);
-    }
-    
-    let response = request.send().await?;
-    
-    if !response.status().is_success() {
-        anyhow::bail!("Webhook returned error status: {}", response.status());
+    /// Send the alerts received on `rx` to the configured channels, retrying
+    /// failed sends from an in-memory queue. The API server uses a
+    /// [`Notifier`] with the history database instead, which also logs
+    /// every attempt.
+    pub async fn process_alerts(
+        rx: mpsc::Receiver<Alert>,
+        config: NotificationConfig,
+    ) {
+        Notifier::new(config, None).run(rx).await;
     }
-    
-    Ok(())
 }
 
 #[cfg(test)]
@@ -444,14 +1125,20 @@ mod tests {
     #[test]
     fn test_alert_creation() {
         let alert = Alert {
+            id: 1,
             alert_type: AlertType::HighCpu,
             severity: AlertSeverity::Warning,
+            rule: "cpu".to_string(),
             process_name: "test".to_string(),
             pid: 1234,
+            user: "test".to_string(),
             message: "High CPU usage".to_string(),
             timestamp: chrono::Utc::now(),
             value: Some(85.0),
             threshold: Some(80.0),
+            lifecycle: None,
+            acknowledged: None,
+            suppressed_by: None,
         };
         
         assert_eq!(alert.pid, 1234);
@@ -461,15 +1148,317 @@ mod tests {
     #[test]
     fn test_alert_rule() {
         let rule = AlertRule {
+            name: "nginx-cpu".to_string(),
             enabled: true,
             alert_type: AlertType::HighCpu,
             threshold: 80.0,
             duration_secs: 60,
             cooldown_secs: 300,
             process_filter: Some("nginx".to_string()),
+            command_pattern: None,
+            expr: None,
+            severity: None,
         };
         
         assert!(rule.enabled);
         assert_eq!(rule.threshold, 80.0);
     }
+
+    fn process(pid: u32, name: &str, cpu_usage: f32, memory_percent: f32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid: 1,
+            name: name.to_string(),
+            command: String::new(),
+            user: "test".to_string(),
+            cpu_usage,
+            memory_usage: 1024,
+            memory_percent,
+            status: "Running".to_string(),
+            start_time: 0,
+            running_time: Duration::from_secs(100),
+            cpu_time: Default::default(),
+            uid: 1000,
+            gid: 1000,
+            threads: 1,
+            priority: 20,
+            nice: 0,
+            network_connections: None,
+            is_container: false,
+            container_id: None,
+            cgroup_memory_limit: None,
+            gpu_memory: None,
+            disk_io: None,
+            network_io: None,
+            open_fds: None,
+            fd_limit: None,
+        }
+    }
+
+    fn config_with_bookmark() -> AlertConfig {
+        let mut config = AlertConfig::default();
+        config.bookmarked_processes.push(crate::config::BookmarkedProcess {
+            name: "nginx".to_string(),
+            command_pattern: None,
+            alert_on_exit: true,
+            alert_on_start: false,
+            alert_on_high_cpu: true,
+            alert_on_high_memory: false,
+        });
+        config
+    }
+
+    #[test]
+    fn test_rules_from_config() {
+        let rules = rules_from_config(&config_with_bookmark());
+        let names: Vec<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["cpu", "memory", "bookmark:nginx:cpu", "bookmark:nginx:exit"]);
+        assert_eq!(rules[0].threshold, 80.0);
+        assert_eq!(rules[0].duration_secs, 30);
+        assert_eq!(rules[1].alert_type, AlertType::HighMemory);
+        assert_eq!(rules[1].threshold, 85.0);
+        assert_eq!(rules[2].process_filter.as_deref(), Some("nginx"));
+        assert_eq!(rules[2].duration_secs, 0);
+    }
+
+    #[tokio::test]
+    async fn test_check_processes_tracks_active_and_recent() {
+        let rules = rules_from_config(&config_with_bookmark());
+        let (mut manager, mut rx) = AlertManager::new(rules, NotificationConfig::default());
+
+        // The global rule needs the condition to hold; the bookmark rule does not
+        let sample = vec![process(10, "nginx", 95.0, 1.0), process(20, "postgres", 95.0, 1.0)];
+        manager.check_processes(&sample).await.unwrap();
+        let alert = rx.try_recv().unwrap();
+        assert_eq!((alert.pid, alert.alert_type), (10, AlertType::HighCpu));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.active_alerts().len(), 1);
+
+        // Still above the threshold: within the cooldown, nothing new is sent
+        manager.check_processes(&sample).await.unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.recent_alerts(10).len(), 1);
+
+        // Cleared conditions and exited processes are no longer active
+        manager.check_processes(&[process(10, "nginx", 1.0, 1.0)]).await.unwrap();
+        assert!(manager.active_alerts().is_empty());
+        assert_eq!(manager.recent_alerts(10)[0].pid, 10);
+
+        manager.set_rules(Vec::new());
+        assert!(manager.rules().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expression_rules() {
+        let mut config = AlertConfig::default();
+        let rule = |name: &str, for_duration: Option<&str>| crate::config::AlertRuleConfig {
+            name: name.to_string(),
+            expr: "name ~ \"^postgres\" and memory_percent > 20 and user != \"root\"".to_string(),
+            for_duration: for_duration.map(str::to_string),
+            severity: AlertSeverity::Critical,
+            cooldown: Some("1h".to_string()),
+            enabled: true,
+        };
+        config.rules.push(rule("postgres-now", None));
+        config.rules.push(rule("postgres-sustained", Some("5m")));
+        let rules = rules_from_config(&config);
+        assert_eq!(rules[2].alert_type, AlertType::Custom("postgres-now".to_string()));
+        assert_eq!((rules[3].duration_secs, rules[3].cooldown_secs), (300, 3600));
+        let (mut manager, mut rx) = AlertManager::new(rules, NotificationConfig::default());
+
+        let mut root = process(20, "postgres", 1.0, 30.0);
+        root.user = "root".to_string();
+        let sample = vec![process(10, "postgres", 1.0, 30.0), root, process(30, "postgres", 1.0, 5.0)];
+        manager.check_processes(&sample).await.unwrap();
+
+        // Only the rule without `for` fires on the first match
+        let alert = rx.try_recv().unwrap();
+        assert_eq!((alert.pid, alert.alert_type), (10, AlertType::Custom("postgres-now".to_string())));
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!(alert.message.contains("matched rule 'postgres-now'"));
+        assert!(alert.value.is_none());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_full_notification_queue_does_not_block() {
+        let rule = AlertRule {
+            name: "cpu".to_string(),
+            enabled: true,
+            alert_type: AlertType::HighCpu,
+            threshold: 50.0,
+            duration_secs: 0,
+            cooldown_secs: 0,
+            process_filter: None,
+            command_pattern: None,
+            expr: None,
+            severity: None,
+        };
+        let (mut manager, mut rx) = AlertManager::new(vec![rule], NotificationConfig::default());
+        let busy: Vec<ProcessInfo> = (1..=NOTIFICATION_QUEUE_SIZE as u32 + 5)
+            .map(|pid| process(pid, "stress", 95.0, 1.0))
+            .collect();
+
+        // Nobody reads `rx`, yet the check completes
+        manager.check_processes(&busy).await.unwrap();
+        assert_eq!(manager.dropped_notifications(), 5);
+        assert_eq!(manager.active_alerts().len(), busy.len());
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_events_for_history() {
+        let mut config = config_with_bookmark();
+        config.cooldown_secs = 0;
+        let (mut manager, _rx) = AlertManager::new(rules_from_config(&config), NotificationConfig::default());
+        manager.resume_ids(41);
+        let busy = [process(10, "nginx", 95.0, 1.0)];
+
+        manager.check_processes(&busy).await.unwrap();
+        manager.check_processes(&busy).await.unwrap();
+        manager.acknowledge(42, "alice", String::new()).unwrap();
+        manager.check_processes(&[process(10, "nginx", 1.0, 1.0)]).await.unwrap();
+
+        let events: Vec<(AlertEventKind, u64)> = manager.take_events().iter()
+            .map(|event| (event.kind, event.alert.id))
+            .collect();
+        assert_eq!(events, vec![
+            (AlertEventKind::Fired, 42),
+            (AlertEventKind::Fired, 42),
+            (AlertEventKind::Acknowledged, 42),
+            (AlertEventKind::Cleared, 42),
+        ]);
+        assert!(manager.take_events().is_empty());
+
+        // Active alerts dropped with their rule are cleared too
+        manager.check_processes(&busy).await.unwrap();
+        manager.set_rules(Vec::new());
+        let kinds: Vec<AlertEventKind> = manager.take_events().iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec![AlertEventKind::Fired, AlertEventKind::Cleared]);
+        assert_eq!(AlertEventKind::from_name("cleared"), Some(AlertEventKind::Cleared));
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_and_suppress() {
+        let mut config = config_with_bookmark();
+        config.cooldown_secs = 0;
+        let (mut manager, mut rx) = AlertManager::new(rules_from_config(&config), NotificationConfig::default());
+        let busy = [process(10, "nginx", 95.0, 1.0)];
+        let idle = [process(10, "nginx", 1.0, 1.0)];
+
+        manager.check_processes(&busy).await.unwrap();
+        let alert = rx.try_recv().unwrap();
+        assert_eq!((alert.id, alert.rule.as_str(), alert.user.as_str()), (1, "bookmark:nginx:cpu", "test"));
+        assert!(manager.acknowledge(99, "alice", String::new()).is_none());
+
+        // Repeats keep the ID while the condition holds
+        manager.check_processes(&busy).await.unwrap();
+        assert_eq!(rx.try_recv().unwrap().id, 1);
+        let acked = manager.acknowledge(alert.id, "alice", "looking".to_string()).unwrap();
+        assert_eq!(acked.acknowledged.as_ref().unwrap().by, "alice");
+        assert!(manager.recent_alerts(2).iter().all(|alert| alert.acknowledged.is_some()));
+
+        // Repeats of an acknowledged condition are recorded, not notified
+        manager.check_processes(&busy).await.unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.recent_alerts(1)[0].acknowledged.as_ref().unwrap().comment, "looking");
+        assert_eq!(manager.recent_alerts(10).len(), 3);
+
+        // Once the condition clears, the next alert is new and notified again
+        manager.check_processes(&idle).await.unwrap();
+        assert!(manager.acknowledge(alert.id, "alice", String::new()).is_none());
+        manager.check_processes(&busy).await.unwrap();
+        let alert = rx.try_recv().unwrap();
+        assert_eq!(alert.id, 2);
+        assert!(alert.acknowledged.is_none());
+
+        let now = Utc::now();
+        let silence = manager.add_silence(Silence {
+            id: 0,
+            matcher: silences::AlertMatcher { process_name: Some("nginx".to_string()), ..Default::default() },
+            starts_at: now - chrono::Duration::minutes(1),
+            ends_at: now + chrono::Duration::hours(1),
+            created_by: "alice".to_string(),
+            comment: String::new(),
+        });
+        manager.check_processes(&idle).await.unwrap();
+        manager.check_processes(&busy).await.unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.recent_alerts(1)[0].suppressed_by.as_deref(), Some("silence 1"));
+        assert!(manager.remove_silence(silence.id));
+
+        let local = chrono::Local::now();
+        manager.add_maintenance_window(MaintenanceWindow {
+            id: None,
+            name: "deploy".to_string(),
+            start: (local - chrono::Duration::hours(1)).format("%H:%M").to_string(),
+            end: (local + chrono::Duration::hours(1)).format("%H:%M").to_string(),
+            days: Vec::new(),
+            matcher: silences::AlertMatcher { user: Some("test".to_string()), ..Default::default() },
+        });
+        manager.set_config_maintenance_windows(Vec::new());
+        assert_eq!(manager.maintenance_windows().len(), 1);
+        manager.check_processes(&idle).await.unwrap();
+        manager.check_processes(&busy).await.unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.recent_alerts(1)[0].suppressed_by.as_deref(), Some("maintenance window 'deploy'"));
+    }
+
+    fn nginx(pid: u32, role: &str, start_time: u64) -> ProcessInfo {
+        let mut process = process(pid, "nginx", 0.5, 1.0);
+        process.command = format!("nginx: {} process", role);
+        process.start_time = start_time;
+        process
+    }
+
+    #[tokio::test]
+    async fn test_bookmarked_process_exit_and_restart() {
+        let mut config = config_with_bookmark();
+        config.bookmarked_processes[0].command_pattern = Some("^nginx: master".to_string());
+        config.bookmarked_processes[0].alert_on_start = true;
+        let (mut manager, mut rx) = AlertManager::new(rules_from_config(&config), NotificationConfig::default());
+
+        // The first sample is the baseline
+        manager.check_processes(&[nginx(10, "master", 100), nginx(11, "worker", 101)]).await.unwrap();
+        assert!(rx.try_recv().is_err());
+
+        // Workers do not match the pattern, so the service is down
+        manager.check_processes(&[nginx(11, "worker", 101)]).await.unwrap();
+        let exit = rx.try_recv().unwrap();
+        assert_eq!((exit.alert_type.clone(), exit.pid), (AlertType::ProcessTerminated, 10));
+        assert_eq!(exit.severity, AlertSeverity::Critical);
+        let lifecycle = exit.lifecycle.unwrap();
+        assert_eq!(lifecycle.rule, "bookmark:nginx:exit");
+        assert_eq!(lifecycle.remaining, Some(0));
+        assert_eq!(lifecycle.last_memory_bytes, Some(1024));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.active_alerts().len(), 1);
+
+        // A new master is a restart, not a separate start alert
+        manager.check_processes(&[nginx(11, "worker", 101), nginx(12, "master", 250)]).await.unwrap();
+        let restart = rx.try_recv().unwrap();
+        assert_eq!((restart.alert_type.clone(), restart.pid), (AlertType::ProcessStarted, 12));
+        assert_eq!(restart.lifecycle.unwrap().previous_pid, Some(10));
+        assert!(rx.try_recv().is_err());
+        assert!(manager.active_alerts().is_empty());
+
+        // A reused PID is an exit plus a start; both are within the cooldown
+        manager.check_processes(&[nginx(11, "worker", 101), nginx(12, "master", 300)]).await.unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(manager.recent_alerts(10).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_started_process_alert() {
+        let mut config = config_with_bookmark();
+        config.bookmarked_processes[0].alert_on_exit = false;
+        config.bookmarked_processes[0].alert_on_start = true;
+        let (mut manager, mut rx) = AlertManager::new(rules_from_config(&config), NotificationConfig::default());
+
+        manager.check_processes(&[process(1, "init", 0.0, 0.1)]).await.unwrap();
+        manager.check_processes(&[process(1, "init", 0.0, 0.1), nginx(20, "master", 500)]).await.unwrap();
+        let alert = rx.try_recv().unwrap();
+        assert_eq!((alert.alert_type, alert.severity, alert.pid), (AlertType::ProcessStarted, AlertSeverity::Info, 20));
+        assert!(alert.lifecycle.unwrap().previous_pid.is_none());
+    }
 }
@@ -1,15 +1,33 @@
 // REST API module for programmatic access to process manager
 // Provides HTTP endpoints for querying processes, sending signals, and accessing historical data
 
-use crate::process::{ProcessManager, ProcessFilter, SortColumn, ProcessInfo};
-use crate::history::HistoryManager;
-use actix_web::{web, App, HttpServer, HttpResponse, Responder, middleware, http::header};
+use crate::process::{self, ProcessManager, ProcessFilter, SortColumn, ProcessInfo, sort_process_list};
+use crate::collector::{Collector, Snapshot};
+use crate::stream::{ProcessDelta, ProcessSnapshot, sse_event, sse_keepalive};
+use crate::history::{AlertHistoryFilter, AuditRecord, HistoryManager};
+use crate::anomaly::{AnomalyDetector, AnomalyDetectorConfig};
+use crate::alerts::{self, AlertEventKind, AlertManager};
+use crate::notifications::Notifier;
+use crate::rules::{parse_duration, Expr};
+use crate::silences::{AlertMatcher, MaintenanceWindow, Silence, MAX_SILENCE_DAYS};
+use crate::config::{Config, ConfigSource};
+use crate::logging::log_system_event;
+use crate::network::{self, PortOwner, SocketEntry};
+use crate::fds::{self, FdEntry};
+use crate::threads::{self, ThreadInfo};
+use crate::auth::{self, AuthError, Principal};
+use crate::affinity::{self, IoPriorityClass, SchedulingPolicy};
+use actix_web::{web, App, HttpMessage, HttpRequest, HttpServer, HttpResponse, Responder, middleware, http::header};
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
 use actix_cors::Cors;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use tokio::time::interval;
-use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::time::{interval, sleep};
+use chrono::{DateTime, Local, Utc};
 use tracing::{debug, info, warn, error};
 use rust_embed::RustEmbed;
 
@@ -18,7 +36,7 @@ use rust_embed::RustEmbed;
 #[folder = "web/dist/"]
 struct WebAssets;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiProcessInfo {
     pub pid: u32,
     pub ppid: u32,
@@ -30,10 +48,22 @@ pub struct ApiProcessInfo {
     pub state: String,
     pub command: String,
     pub start_time: u64,
+    /// CPU seconds consumed (user + system), what `top` shows as TIME+
+    pub cpu_time_secs: f64,
+    pub cpu_user_secs: f64,
+    pub cpu_system_secs: f64,
+    /// CPU seconds of exited children that were waited for
+    pub cpu_children_secs: f64,
     pub network_connections: Option<usize>,
     pub is_container: bool,
     pub container_id: Option<String>,
     pub gpu_memory: Option<u64>,
+    /// TCP bytes received/sent per second since the previous collection
+    pub net_rx_bytes_per_sec: Option<f64>,
+    pub net_tx_bytes_per_sec: Option<f64>,
+    pub open_fds: Option<usize>,
+    /// Soft `RLIMIT_NOFILE`
+    pub fd_limit: Option<u64>,
 }
 
 impl From<&ProcessInfo> for ApiProcessInfo {
@@ -49,14 +79,52 @@ impl From<&ProcessInfo> for ApiProcessInfo {
             state: p.status.clone(),
             command: p.command.clone(),
             start_time: p.start_time,
+            cpu_time_secs: p.cpu_time.total(),
+            cpu_user_secs: p.cpu_time.user,
+            cpu_system_secs: p.cpu_time.system,
+            cpu_children_secs: p.cpu_time.children,
             network_connections: p.network_connections,
             is_container: p.is_container,
             container_id: p.container_id.clone(),
             gpu_memory: p.gpu_memory,
+            net_rx_bytes_per_sec: p.network_io.map(|io| io.rx_bytes),
+            net_tx_bytes_per_sec: p.network_io.map(|io| io.tx_bytes),
+            open_fds: p.open_fds,
+            fd_limit: p.fd_limit,
         }
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct SocketListResponse {
+    pub pid: u32,
+    pub sockets: Vec<SocketEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FdListResponse {
+    pub pid: u32,
+    pub open: usize,
+    /// Soft `RLIMIT_NOFILE`, `None` if unlimited or unreadable
+    pub limit: Option<u64>,
+    pub fds: Vec<FdEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThreadListResponse {
+    pub pid: u32,
+    /// Sampling interval the CPU usage was measured over
+    pub interval_ms: u64,
+    /// Busiest thread first
+    pub threads: Vec<ThreadInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortOwnersResponse {
+    pub port: u16,
+    pub owners: Vec<PortOwner>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct SystemInfoResponse {
     pub cpu_count: usize,
@@ -82,7 +150,7 @@ pub struct ProcessListResponse {
     pub filtered: usize,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ProcessQuery {
     pub sort_by: Option<String>,
     pub ascending: Option<bool>,
@@ -103,6 +171,51 @@ pub struct KillResponse {
     pub message: String,
 }
 
+/// Body of `PUT /api/processes/{pid}/priority`; only the given settings change
+#[derive(Debug, Default, Deserialize)]
+pub struct PriorityRequest {
+    /// Nice value, -20 to 19
+    pub nice: Option<i32>,
+    /// Scheduling policy name, e.g. `SCHED_FIFO` or `rr`
+    pub policy: Option<String>,
+    /// Real-time priority for `SCHED_FIFO`/`SCHED_RR` (1-99)
+    pub rt_priority: Option<u32>,
+    /// I/O class: `none`, `realtime`, `best-effort` or `idle`
+    pub io_class: Option<String>,
+    /// I/O level within the class, 0-7 (default 4)
+    pub io_level: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AffinityRequest {
+    /// CPUs the process may run on
+    pub cpus: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KillTreeRequest {
+    pub signal: Option<i32>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignalResult {
+    pub pid: u32,
+    pub name: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KillTreeResponse {
+    pub success: bool,
+    pub dry_run: bool,
+    pub signal: i32,
+    pub root_pid: u32,
+    pub results: Vec<SignalResult>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct HistoryQuery {
     pub pid: Option<u32>,
@@ -111,49 +224,99 @@ pub struct HistoryQuery {
     pub limit: Option<usize>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub pid: Option<u32>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ThreadQuery {
+    /// CPU sampling interval in milliseconds (default 250, at most 5000)
+    pub interval_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnomalyQuery {
+    pub pid: Option<u32>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertHistoryQuery {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub alert_id: Option<u64>,
+    pub pid: Option<u32>,
+    pub rule: Option<String>,
+    /// `fired`, `cleared` or `acknowledged`
+    pub event: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RuleCheckQuery {
+    pub expr: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AckRequest {
+    #[serde(default)]
+    pub comment: String,
+}
+
+/// Body of `POST /api/alerts/silences`; give either `duration` or `ends_at`
+#[derive(Debug, Deserialize)]
+pub struct SilenceRequest {
+    #[serde(flatten)]
+    pub matcher: AlertMatcher,
+    /// Defaults to now
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Length from `starts_at`, e.g. `2h`
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub comment: String,
+}
+
+/// A silence or maintenance window and whether it applies right now
+#[derive(Debug, Serialize)]
+pub struct SuppressionStatus<T> {
+    #[serde(flatten)]
+    pub entry: T,
+    pub active: bool,
+}
+
 pub struct AppState {
-    pub process_manager: Arc<Mutex<ProcessManager>>,
+    /// Background collector; handlers read its latest snapshot
+    pub collector: Arc<Collector>,
     pub history_manager: Option<Arc<Mutex<HistoryManager>>>,
+    /// Kept across config reloads so learned baselines survive toggling
+    pub anomaly_detector: Arc<Mutex<AnomalyDetector>>,
+    /// Evaluates `[alerts]` rules against every snapshot; its notification
+    /// receiver is drained by a [`Notifier`]
+    pub alert_manager: Arc<tokio::sync::Mutex<AlertManager>>,
+    /// Current configuration, replaced on reload
+    pub config: Arc<RwLock<Config>>,
 }
 
 // API Endpoints
 
-/// GET /api/processes - List all processes
-async fn get_processes(
-    state: web::Data<AppState>,
-    query: web::Query<ProcessQuery>,
-) -> impl Responder {
-    debug!("API: GET /processes - query params: {:?}", query);
-    
-    let mut pm = state.process_manager.lock().unwrap();
-    
-    // Refresh process data
-    if let Err(e) = pm.refresh() {
-        error!("API: Failed to refresh processes: {}", e);
-        return HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": format!("Failed to refresh processes: {}", e)
-        }));
-    }
-    
-    info!("API: Returning {} processes", pm.get_processes().len());
-    
-    // Parse sort column
-    let sort_column = match query.sort_by.as_deref() {
-        Some("pid") => SortColumn::Pid,
-        Some("name") => SortColumn::Name,
-        Some("user") => SortColumn::User,
-        Some("cpu") => SortColumn::CpuUsage,
-        Some("memory") => SortColumn::MemoryUsage,
-        Some("start_time") => SortColumn::StartTime,
-        _ => SortColumn::CpuUsage,
-    };
-    
-    let ascending = query.ascending.unwrap_or(false);
+/// Sort, filter and limit a process list as described by `query`
+fn select_processes(snapshot: &Snapshot, query: &ProcessQuery) -> Vec<ApiProcessInfo> {
+    let mut processes = snapshot.processes.clone();
+    let sort_column = query.sort_by.as_deref()
+        .and_then(SortColumn::from_name)
+        .unwrap_or(SortColumn::CpuUsage);
+    sort_process_list(&mut processes, sort_column, query.ascending.unwrap_or(false));
     
-    // Sort processes
-    let processes = pm.sort_processes(sort_column, ascending);
-    
-    // Apply filters
     let mut filter = ProcessFilter::new();
     if let Some(ref user) = query.user {
         filter.username = Some(user.clone());
@@ -164,14 +327,27 @@ async fn get_processes(
         }
     }
     
-    let filtered_processes: Vec<ApiProcessInfo> = processes
+    processes
         .iter()
         .filter(|p| filter.matches(p))
         .take(query.limit.unwrap_or(1000))
-        .map(|p| ApiProcessInfo::from(p))
-        .collect();
+        .map(ApiProcessInfo::from)
+        .collect()
+}
+
+/// GET /api/processes - List all processes
+async fn get_processes(
+    state: web::Data<AppState>,
+    query: web::Query<ProcessQuery>,
+) -> impl Responder {
+    debug!("API: GET /processes - query params: {:?}", query);
     
-    let total = processes.len();
+    let snapshot = state.collector.latest();
+    
+    info!("API: Returning {} processes", snapshot.processes.len());
+    
+    let total = snapshot.processes.len();
+    let filtered_processes = select_processes(&snapshot, &query);
     let filtered = filtered_processes.len();
     
     HttpResponse::Ok().json(ProcessListResponse {
@@ -181,20 +357,61 @@ async fn get_processes(
     })
 }
 
+/// GET /api/processes/stream - Server-Sent Events stream of process list changes
+/// 
+/// Accepts the same query parameters as `GET /api/processes`. The first event
+/// is a `snapshot` of the filtered list; later `update` events carry only
+/// added PIDs, removed PIDs and changed fields, one per collector snapshot.
+async fn stream_processes(
+    state: web::Data<AppState>,
+    query: web::Query<ProcessQuery>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    let receiver = state.collector.subscribe();
+    debug!("API: GET /processes/stream - {} subscriber(s)", state.collector.subscriber_count());
+    
+    let events = futures_util::stream::unfold(
+        (receiver, None::<Vec<ApiProcessInfo>>, query),
+        |(mut receiver, previous, query)| async move {
+            // Start from the latest snapshot, then wait for each new one
+            if previous.is_some() || receiver.borrow().sequence == 0 {
+                receiver.changed().await.ok()?;
+            }
+            let snapshot = receiver.borrow_and_update().clone();
+            let total = snapshot.processes.len();
+            let current = select_processes(&snapshot, &query);
+            let timestamp = Utc::now().timestamp();
+            
+            let event = match previous {
+                None => sse_event("snapshot", &ProcessSnapshot { timestamp, total, processes: &current }),
+                Some(ref previous) => {
+                    let delta = ProcessDelta::between(previous, &current, timestamp, total);
+                    if delta.is_empty() {
+                        sse_keepalive()
+                    } else {
+                        sse_event("update", &delta)
+                    }
+                }
+            };
+            
+            Some((Ok::<_, actix_web::Error>(event), (receiver, Some(current), query)))
+        },
+    );
+    
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/event-stream"))
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(events)
+}
+
 /// GET /api/processes/:pid - Get specific process info
 async fn get_process(
     state: web::Data<AppState>,
     pid: web::Path<u32>,
 ) -> impl Responder {
-    let mut pm = state.process_manager.lock().unwrap();
-    
-    if let Err(e) = pm.refresh() {
-        return HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": format!("Failed to refresh processes: {}", e)
-        }));
-    }
+    let snapshot = state.collector.latest();
     
-    if let Some(process) = pm.get_process(*pid) {
+    if let Some(process) = snapshot.get(*pid) {
         HttpResponse::Ok().json(ApiProcessInfo::from(process))
     } else {
         HttpResponse::NotFound().json(serde_json::json!({
@@ -203,17 +420,150 @@ async fn get_process(
     }
 }
 
+/// GET /api/processes/:pid/sockets - List a process's TCP, UDP and Unix sockets
+async fn get_process_sockets(
+    state: web::Data<AppState>,
+    pid: web::Path<u32>,
+) -> impl Responder {
+    let pid = pid.into_inner();
+    if state.collector.latest().get(pid).is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Process {} not found", pid)
+        }));
+    }
+    
+    match web::block(move || network::get_process_sockets(pid)).await {
+        Ok(sockets) => HttpResponse::Ok().json(SocketListResponse { pid, sockets }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to read sockets: {}", e)
+        })),
+    }
+}
+
+/// GET /api/processes/:pid/fds - List a process's open file descriptors
+async fn get_process_fds(
+    state: web::Data<AppState>,
+    pid: web::Path<u32>,
+) -> impl Responder {
+    let pid = pid.into_inner();
+    if state.collector.latest().get(pid).is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Process {} not found", pid)
+        }));
+    }
+    
+    let listed = web::block(move || fds::list_fds(pid).map(|list| (list, fds::fd_limit(pid)))).await;
+    match listed {
+        Ok(Ok((fds, limit))) => HttpResponse::Ok().json(FdListResponse { pid, open: fds.len(), limit, fds }),
+        Ok(Err(e)) => {
+            let body = serde_json::json!({ "error": format!("{:#}", e) });
+            let kind = e.chain()
+                .find_map(|cause| cause.downcast_ref::<std::io::Error>().map(|io| io.kind()));
+            match kind {
+                // The process exited between the snapshot and the read
+                Some(std::io::ErrorKind::NotFound) => HttpResponse::NotFound().json(body),
+                Some(std::io::ErrorKind::PermissionDenied) => HttpResponse::Forbidden().json(body),
+                _ => HttpResponse::InternalServerError().json(body),
+            }
+        }
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to read fds: {}", e)
+        })),
+    }
+}
+
+/// GET /api/processes/:pid/threads - List a process's threads with per-thread CPU usage
+async fn get_process_threads(
+    state: web::Data<AppState>,
+    pid: web::Path<u32>,
+    query: web::Query<ThreadQuery>,
+) -> impl Responder {
+    let pid = pid.into_inner();
+    if state.collector.latest().get(pid).is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Process {} not found", pid)
+        }));
+    }
+    
+    let interval_ms = query.interval_ms.unwrap_or(250).min(5000);
+    let sampled = web::block(move || {
+        threads::sample_threads(pid, Duration::from_millis(interval_ms))
+    }).await;
+    match sampled {
+        Ok(Ok(threads)) => HttpResponse::Ok().json(ThreadListResponse { pid, interval_ms, threads }),
+        // The task directory is world-readable, so the process has exited
+        Ok(Err(e)) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("{:#}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to read threads: {}", e)
+        })),
+    }
+}
+
+/// GET /api/ports/:port - Find the processes with a TCP or UDP socket bound to a port
+async fn get_port_owners(port: web::Path<u16>) -> impl Responder {
+    let port = port.into_inner();
+    
+    match web::block(move || network::find_port_owners(port)).await {
+        Ok(owners) => HttpResponse::Ok().json(PortOwnersResponse { port, owners }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to look up port {}: {}", port, e)
+        })),
+    }
+}
+
+/// Initiator and client address of an API request, for the audit log
+fn audit_identity(http_req: &HttpRequest) -> (String, Option<String>) {
+    let initiator = http_req.extensions()
+        .get::<Principal>()
+        .map(|p| p.name.clone())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let remote_addr = http_req.peer_addr().map(|addr| addr.ip().to_string());
+    (initiator, remote_addr)
+}
+
+/// Persist audit records, if the history database is available
+fn record_audit(state: &AppState, records: &[AuditRecord]) {
+    match state.history_manager {
+        Some(ref history_manager) => {
+            let hm = history_manager.lock().unwrap();
+            for record in records {
+                if let Err(e) = hm.record_audit(record) {
+                    error!(pid = record.pid, action = %record.action, "Failed to write audit record: {}", e);
+                }
+            }
+        }
+        None => debug!("History disabled, {} audit record(s) not persisted", records.len()),
+    }
+}
+
 /// POST /api/processes/kill - Kill a process
 async fn kill_process(
     state: web::Data<AppState>,
+    http_req: HttpRequest,
     req: web::Json<KillRequest>,
 ) -> impl Responder {
     let signal = req.signal.unwrap_or(15); // Default to SIGTERM
     info!("API: POST /kill - PID: {}, Signal: {}", req.pid, signal);
     
-    let pm = state.process_manager.lock().unwrap();
+    let snapshot = state.collector.latest();
+    let result = process::send_signal(req.pid, signal);
+    
+    let (initiator, remote_addr) = audit_identity(&http_req);
+    let mut record = AuditRecord::new(
+        &initiator,
+        "api",
+        snapshot.get(req.pid),
+        req.pid,
+        "signal",
+        format!("signal={}", signal),
+        result.as_ref().map(|_| ()).map_err(|e| e.to_string()),
+    );
+    record.remote_addr = remote_addr;
+    record_audit(&state, &[record]);
     
-    match pm.kill_process(req.pid, signal) {
+    match result {
         Ok(()) => {
             info!("API: Successfully sent signal {} to PID {}", signal, req.pid);
             HttpResponse::Ok().json(KillResponse {
@@ -231,10 +581,249 @@ async fn kill_process(
     }
 }
 
+/// POST /api/processes/:pid/kill-tree - Signal a process and all its descendants
+async fn kill_process_tree(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    pid: web::Path<u32>,
+    req: web::Json<KillTreeRequest>,
+) -> impl Responder {
+    let pid = pid.into_inner();
+    let signal = req.signal.unwrap_or(15); // Default to SIGTERM
+    info!("API: POST /processes/{}/kill-tree - Signal: {}, dry_run: {}", pid, signal, req.dry_run);
+
+    // The snapshot may be up to a refresh interval old, so re-read the parent
+    // links from /proc to catch children forked since. Nothing is published.
+    let snapshot = state.collector.latest();
+    let sampled = snapshot.clone();
+    let processes = match web::block(move || process::with_current_parents(&sampled.processes)).await {
+        Ok(processes) => processes,
+        Err(e) => {
+            error!("API: Failed to read process tree: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to read process tree: {}", e)
+            }));
+        }
+    };
+
+    let outcomes = match process::signal_subtree(&processes, pid, signal, req.dry_run) {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    if !req.dry_run {
+        let (initiator, remote_addr) = audit_identity(&http_req);
+        let records: Vec<AuditRecord> = outcomes
+            .iter()
+            .map(|o| {
+                let mut record = AuditRecord::new(
+                    &initiator,
+                    "api",
+                    snapshot.get(o.pid),
+                    o.pid,
+                    "signal_tree",
+                    format!("signal={} root={}", signal, pid),
+                    o.error.clone().map_or(Ok(()), Err),
+                );
+                record.name = o.name.clone();
+                record.remote_addr = remote_addr.clone();
+                record
+            })
+            .collect();
+        record_audit(&state, &records);
+    }
+
+    let results: Vec<SignalResult> = outcomes
+        .into_iter()
+        .map(|o| SignalResult {
+            pid: o.pid,
+            name: o.name,
+            success: o.success,
+            error: o.error,
+        })
+        .collect();
+    let success = results.iter().all(|r| r.success);
+
+    if success {
+        info!("API: Sent signal {} to {} processes in subtree of PID {}", signal, results.len(), pid);
+    } else {
+        warn!("API: Failed to signal some processes in subtree of PID {}", pid);
+    }
+
+    let response = KillTreeResponse {
+        success,
+        dry_run: req.dry_run,
+        signal,
+        root_pid: pid,
+        results,
+    };
+
+    if success {
+        HttpResponse::Ok().json(response)
+    } else {
+        HttpResponse::InternalServerError().json(response)
+    }
+}
+
+/// PUT /api/processes/:pid/priority - Change nice value, scheduling policy and I/O priority
+async fn set_process_priority(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    pid: web::Path<u32>,
+    req: web::Json<PriorityRequest>,
+) -> impl Responder {
+    let pid = pid.into_inner();
+    let req = req.into_inner();
+    info!("API: PUT /processes/{}/priority - {:?}", pid, req);
+    
+    let snapshot = state.collector.latest();
+    if snapshot.get(pid).is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Process {} not found", pid)
+        }));
+    }
+    
+    // Validate everything before changing anything
+    let policy = match req.policy.as_deref().map(|name| (name, SchedulingPolicy::from_name(name))) {
+        Some((name, None)) => return bad_request(format!("Unknown scheduling policy '{}'", name)),
+        Some((_, policy)) => policy,
+        None if req.rt_priority.is_some() => return bad_request("rt_priority requires policy".to_string()),
+        None => None,
+    };
+    let io_class = match req.io_class.as_deref().map(|name| (name, IoPriorityClass::from_name(name))) {
+        Some((name, None)) => return bad_request(format!("Unknown I/O class '{}'", name)),
+        Some((_, class)) => class,
+        None if req.io_level.is_some() => return bad_request("io_level requires io_class".to_string()),
+        None => None,
+    };
+    if req.nice.is_none() && policy.is_none() && io_class.is_none() {
+        return bad_request("Nothing to change: give nice, policy or io_class".to_string());
+    }
+    
+    // Applied in order; a failure stops the request, leaving earlier changes in place
+    if let Some(nice) = req.nice {
+        let result = affinity::set_nice_value(pid, nice);
+        if let Err(response) = audit_control(&state, &http_req, pid, "renice", format!("nice={}", nice), result) {
+            return response;
+        }
+    }
+    if let Some(policy) = policy {
+        let rt_priority = req.rt_priority.unwrap_or(0);
+        let result = affinity::set_scheduling_policy(pid, policy, rt_priority);
+        let details = format!("policy={} rt_priority={}", policy.name(), rt_priority);
+        if let Err(response) = audit_control(&state, &http_req, pid, "set_scheduler", details, result) {
+            return response;
+        }
+    }
+    if let Some(class) = io_class {
+        let level = req.io_level.unwrap_or(4);
+        let result = affinity::set_io_priority(pid, class, level);
+        let details = format!("class={} level={}", class.name(), level);
+        if let Err(response) = audit_control(&state, &http_req, pid, "set_ionice", details, result) {
+            return response;
+        }
+    }
+    
+    match affinity::get_priority_info(pid) {
+        Ok(info) => HttpResponse::Ok().json(info),
+        Err(e) => control_error_response(&e),
+    }
+}
+
+/// PUT /api/processes/:pid/affinity - Pin a process to a set of CPUs
+async fn set_process_affinity(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    pid: web::Path<u32>,
+    req: web::Json<AffinityRequest>,
+) -> impl Responder {
+    let pid = pid.into_inner();
+    info!("API: PUT /processes/{}/affinity - CPUs: {:?}", pid, req.cpus);
+    
+    let snapshot = state.collector.latest();
+    if snapshot.get(pid).is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Process {} not found", pid)
+        }));
+    }
+    if req.cpus.is_empty() {
+        return bad_request("cpus must not be empty".to_string());
+    }
+    
+    let result = affinity::set_cpu_affinity(pid, &req.cpus);
+    let details = format!("cpus={}", affinity::format_affinity_list(&req.cpus));
+    if let Err(response) = audit_control(&state, &http_req, pid, "set_affinity", details, result) {
+        return response;
+    }
+    
+    match affinity::get_priority_info(pid) {
+        Ok(info) => HttpResponse::Ok().json(info),
+        Err(e) => control_error_response(&e),
+    }
+}
+
+/// Audit a scheduling change, turning a failure into its error response
+fn audit_control(
+    state: &AppState,
+    http_req: &HttpRequest,
+    pid: u32,
+    action: &str,
+    details: String,
+    result: anyhow::Result<()>,
+) -> Result<(), HttpResponse> {
+    let (initiator, remote_addr) = audit_identity(http_req);
+    let mut record = AuditRecord::new(
+        &initiator,
+        "api",
+        state.collector.latest().get(pid),
+        pid,
+        action,
+        details,
+        result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e)),
+    );
+    record.remote_addr = remote_addr;
+    record_audit(state, &[record]);
+    
+    result.map_err(|e| {
+        warn!("API: {} failed for PID {}: {:#}", action, pid, e);
+        control_error_response(&e)
+    })
+}
+
+fn bad_request(message: String) -> HttpResponse {
+    HttpResponse::BadRequest().json(serde_json::json!({ "error": message }))
+}
+
+/// Map a failed scheduling change to a status: permission problems are 403,
+/// a vanished process 404, invalid values (rejected before or by the kernel) 400
+fn control_error_response(e: &anyhow::Error) -> HttpResponse {
+    let body = serde_json::json!({ "error": format!("{:#}", e) });
+    let os_error = e.chain()
+        .find_map(|cause| cause.downcast_ref::<std::io::Error>().and_then(|io| io.raw_os_error())
+            .or_else(|| cause.downcast_ref::<nix::errno::Errno>().map(|errno| *errno as i32)));
+    match os_error {
+        Some(libc::EPERM) | Some(libc::EACCES) => HttpResponse::Forbidden().json(body),
+        Some(libc::ESRCH) => HttpResponse::NotFound().json(body),
+        Some(libc::EINVAL) | None => HttpResponse::BadRequest().json(body),
+        Some(_) => HttpResponse::InternalServerError().json(body),
+    }
+}
+
 /// GET /api/system - Get system information
 async fn get_system_info(state: web::Data<AppState>) -> impl Responder {
-    let pm = state.process_manager.lock().unwrap();
-    let sys_info = pm.get_system_info();
+    let snapshot = state.collector.latest();
+    let sys_info = match snapshot.system {
+        Some(ref sys_info) => sys_info,
+        None => {
+            return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+                "error": "System information has not been collected yet"
+            }));
+        }
+    };
     
     HttpResponse::Ok().json(SystemInfoResponse {
         cpu_count: sys_info.cpu_count,
@@ -316,16 +905,362 @@ async fn get_top_cpu_consumers(
     }
 }
 
-/// GET /api/health - Health check endpoint
-async fn health_check() -> impl Responder {
-    HttpResponse::Ok().json(serde_json::json!({
-        "status": "healthy",
-        "timestamp": Utc::now().to_rfc3339(),
-    }))
+/// GET /api/audit - Get process-control actions, newest first
+async fn get_audit_log(
+    state: web::Data<AppState>,
+    query: web::Query<AuditQuery>,
+) -> impl Responder {
+    if let Some(ref history_manager) = state.history_manager {
+        let hm = history_manager.lock().unwrap();
+        
+        let end = query.end.unwrap_or_else(Utc::now);
+        let start = query.start.unwrap_or_else(|| end - chrono::Duration::hours(24));
+        let limit = query.limit.unwrap_or(1000);
+        
+        match hm.get_audit_log(start, end, query.pid, limit) {
+            Ok(records) => HttpResponse::Ok().json(records),
+            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to fetch audit log: {}", e)
+            })),
+        }
+    } else {
+        HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "History manager not enabled"
+        }))
+    }
 }
 
-/// Serve embedded static files
-async fn serve_embedded_file(path: web::Path<String>) -> impl Responder {
+/// GET /api/anomalies - Get recently detected anomalies
+async fn get_anomalies(
+    state: web::Data<AppState>,
+    query: web::Query<AnomalyQuery>,
+) -> impl Responder {
+    if state.config.read().unwrap().features.anomaly_detection {
+        let detector = state.anomaly_detector.lock().unwrap();
+        let limit = query.limit.unwrap_or(100);
+        
+        let anomalies = if let Some(pid) = query.pid {
+            let mut anomalies = detector.get_process_anomalies(pid);
+            anomalies.reverse();
+            anomalies.truncate(limit);
+            anomalies
+        } else {
+            detector.get_recent_anomalies(limit)
+        };
+        
+        HttpResponse::Ok().json(serde_json::json!({
+            "anomalies": anomalies,
+            "stats": detector.get_stats(),
+        }))
+    } else {
+        HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "Anomaly detection not enabled"
+        }))
+    }
+}
+
+/// GET /api/alerts - Active alerts and recently sent alerts
+async fn get_alerts(
+    state: web::Data<AppState>,
+    query: web::Query<AlertQuery>,
+) -> impl Responder {
+    if !state.config.read().unwrap().alerts.enabled {
+        return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "Alerts not enabled"
+        }));
+    }
+    
+    let manager = state.alert_manager.lock().await;
+    HttpResponse::Ok().json(serde_json::json!({
+        "active": manager.active_alerts(),
+        "recent": manager.recent_alerts(query.limit.unwrap_or(100)),
+        "dropped_notifications": manager.dropped_notifications(),
+    }))
+}
+
+/// POST /api/alerts/:id/ack - Acknowledge an active alert
+async fn acknowledge_alert(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    id: web::Path<u64>,
+    req: Option<web::Json<AckRequest>>,
+) -> impl Responder {
+    let id = id.into_inner();
+    let (initiator, _) = audit_identity(&http_req);
+    let comment = req.map(|r| r.into_inner().comment).unwrap_or_default();
+    
+    let mut manager = state.alert_manager.lock().await;
+    let acknowledged = manager.acknowledge(id, &initiator, comment);
+    store_alert_events(&state, &mut manager);
+    match acknowledged {
+        Some(alert) => HttpResponse::Ok().json(alert),
+        None => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("No active alert with id {}", id)
+        })),
+    }
+}
+
+/// GET /api/alerts/history - Fired, cleared and acknowledged alerts with
+/// their notification attempts and pending retries
+async fn get_alert_history(
+    state: web::Data<AppState>,
+    query: web::Query<AlertHistoryQuery>,
+) -> impl Responder {
+    let Some(ref history_manager) = state.history_manager else {
+        return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "History manager not enabled"
+        }));
+    };
+    let event = match query.event.as_deref().map(|name| (name, AlertEventKind::from_name(name))) {
+        Some((name, None)) => {
+            return bad_request(format!("Unknown event {:?}, expected fired, cleared or acknowledged", name));
+        }
+        Some((_, kind)) => kind,
+        None => None,
+    };
+    let filter = AlertHistoryFilter {
+        alert_id: query.alert_id,
+        pid: query.pid,
+        rule: query.rule.clone(),
+        event,
+    };
+    
+    let end = query.end.unwrap_or_else(Utc::now);
+    let start = query.start.unwrap_or_else(|| end - chrono::Duration::hours(24));
+    let limit = query.limit.unwrap_or(1000);
+    
+    let hm = history_manager.lock().unwrap();
+    let result = hm.get_alert_history(start, end, &filter, limit).and_then(|events| {
+        let notifications = hm.get_notification_log(start, end, query.alert_id, limit)?;
+        let queued = hm.get_queued_notifications(None)?;
+        Ok((events, notifications, queued))
+    });
+    match result {
+        Ok((events, mut notifications, mut queued)) => {
+            // Keep the deliveries of the alerts listed when filtering by more than ID
+            if filter.pid.is_some() || filter.rule.is_some() || filter.event.is_some() {
+                let ids: std::collections::HashSet<u64> = events.iter().map(|e| e.alert.id).collect();
+                notifications.retain(|n| ids.contains(&n.alert_id));
+                queued.retain(|q| ids.contains(&q.alert.id));
+            } else if let Some(id) = query.alert_id {
+                queued.retain(|q| q.alert.id == id);
+            }
+            HttpResponse::Ok().json(serde_json::json!({
+                "events": events,
+                "notifications": notifications,
+                "queued": queued,
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to fetch alert history: {}", e)
+        })),
+    }
+}
+
+/// GET /api/alerts/silences - Silences that have not expired
+async fn get_silences(state: web::Data<AppState>) -> impl Responder {
+    let now = Utc::now();
+    let manager = state.alert_manager.lock().await;
+    let silences: Vec<_> = manager.silences().iter()
+        .filter(|s| !s.is_expired(now))
+        .map(|s| SuppressionStatus { entry: s.clone(), active: s.is_active(now) })
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({ "silences": silences }))
+}
+
+/// POST /api/alerts/silences - Silence matching alerts for a time range
+async fn create_silence(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    req: web::Json<SilenceRequest>,
+) -> impl Responder {
+    let req = req.into_inner();
+    if req.matcher.is_empty() {
+        return bad_request("Give at least one of rule, pid, process_name or user".to_string());
+    }
+    let starts_at = req.starts_at.unwrap_or_else(Utc::now);
+    let ends_at = match (req.ends_at, req.duration.as_deref()) {
+        (Some(ends_at), None) => ends_at,
+        (None, Some(duration)) => match parse_duration(duration) {
+            Ok(secs) => {
+                let ends_at = i64::try_from(secs)
+                    .ok()
+                    .and_then(chrono::TimeDelta::try_seconds)
+                    .and_then(|length| starts_at.checked_add_signed(length));
+                match ends_at {
+                    Some(ends_at) => ends_at,
+                    None => return bad_request(format!("duration: {:?} is too long", duration)),
+                }
+            }
+            Err(e) => return bad_request(format!("duration: {}", e)),
+        },
+        _ => return bad_request("Give either duration or ends_at".to_string()),
+    };
+    if ends_at <= starts_at {
+        return bad_request("ends_at must be after starts_at".to_string());
+    }
+    if ends_at - starts_at > chrono::TimeDelta::days(MAX_SILENCE_DAYS) {
+        return bad_request(format!(
+            "A silence may last at most {} days; use a maintenance window for recurring mutes",
+            MAX_SILENCE_DAYS
+        ));
+    }
+    
+    let (initiator, _) = audit_identity(&http_req);
+    let mut silence = Silence {
+        id: 0,
+        matcher: req.matcher,
+        starts_at,
+        ends_at,
+        created_by: initiator,
+        comment: req.comment,
+    };
+    if let Some(ref history_manager) = state.history_manager {
+        match history_manager.lock().unwrap().add_silence(&silence) {
+            Ok(id) => silence.id = id,
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": format!("Failed to store silence: {}", e)
+                }));
+            }
+        }
+    }
+    
+    let silence = state.alert_manager.lock().await.add_silence(silence);
+    info!("Silence {} created by {} until {}", silence.id, silence.created_by, silence.ends_at);
+    HttpResponse::Created().json(silence)
+}
+
+/// DELETE /api/alerts/silences/:id - Remove a silence
+async fn delete_silence(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+) -> impl Responder {
+    let id = id.into_inner();
+    if let Some(ref history_manager) = state.history_manager {
+        if let Err(e) = history_manager.lock().unwrap().delete_silence(id) {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to delete silence: {}", e)
+            }));
+        }
+    }
+    
+    if state.alert_manager.lock().await.remove_silence(id) {
+        info!("Silence {} removed", id);
+        HttpResponse::NoContent().finish()
+    } else {
+        HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("No silence with id {}", id)
+        }))
+    }
+}
+
+/// GET /api/alerts/maintenance-windows - Windows from the config and the API
+async fn get_maintenance_windows(state: web::Data<AppState>) -> impl Responder {
+    let now = Local::now().naive_local();
+    let manager = state.alert_manager.lock().await;
+    let windows: Vec<_> = manager.maintenance_windows().iter()
+        .map(|w| SuppressionStatus { entry: w.clone(), active: w.is_active_at(now) })
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({ "maintenance_windows": windows }))
+}
+
+/// POST /api/alerts/maintenance-windows - Add a recurring maintenance window
+async fn create_maintenance_window(
+    state: web::Data<AppState>,
+    req: web::Json<MaintenanceWindow>,
+) -> impl Responder {
+    let mut window = req.into_inner();
+    window.id = None;
+    if let Err(e) = window.validate() {
+        return bad_request(e);
+    }
+    if let Some(ref history_manager) = state.history_manager {
+        match history_manager.lock().unwrap().add_maintenance_window(&window) {
+            Ok(id) => window.id = Some(id),
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": format!("Failed to store maintenance window: {}", e)
+                }));
+            }
+        }
+    }
+    
+    let window = state.alert_manager.lock().await.add_maintenance_window(window);
+    info!("Maintenance window '{}' added ({} to {})", window.name, window.start, window.end);
+    HttpResponse::Created().json(window)
+}
+
+/// DELETE /api/alerts/maintenance-windows/:id - Remove a window added through the API
+async fn delete_maintenance_window(
+    state: web::Data<AppState>,
+    id: web::Path<i64>,
+) -> impl Responder {
+    let id = id.into_inner();
+    if let Some(ref history_manager) = state.history_manager {
+        if let Err(e) = history_manager.lock().unwrap().delete_maintenance_window(id) {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to delete maintenance window: {}", e)
+            }));
+        }
+    }
+    
+    if state.alert_manager.lock().await.remove_maintenance_window(id) {
+        info!("Maintenance window {} removed", id);
+        HttpResponse::NoContent().finish()
+    } else {
+        HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("No maintenance window with id {} (windows from the config file cannot be removed here)", id)
+        }))
+    }
+}
+
+/// GET /api/alerts/rules - Rules built from the `[alerts]` configuration
+async fn get_alert_rules(state: web::Data<AppState>) -> impl Responder {
+    let config = state.config.read().unwrap();
+    HttpResponse::Ok().json(serde_json::json!({
+        "enabled": config.alerts.enabled,
+        "rules": alerts::rules_from_config(&config.alerts),
+    }))
+}
+
+/// GET /api/alerts/rules/check - Validate a rule expression and list the
+/// processes it matches right now
+async fn check_alert_rule(
+    state: web::Data<AppState>,
+    query: web::Query<RuleCheckQuery>,
+) -> impl Responder {
+    let expr = match Expr::parse(&query.expr) {
+        Ok(expr) => expr,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string(),
+                "position": e.position,
+            }));
+        }
+    };
+    
+    let snapshot = state.collector.latest();
+    let matches: Vec<_> = snapshot.processes.iter()
+        .filter(|p| expr.matches(p))
+        .map(|p| serde_json::json!({ "pid": p.pid, "name": p.name }))
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({
+        "valid": true,
+        "matches": matches,
+    }))
+}
+
+/// GET /api/health - Health check endpoint
+async fn health_check() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "healthy",
+        "timestamp": Utc::now().to_rfc3339(),
+    }))
+}
+
+/// Serve embedded static files
+async fn serve_embedded_file(path: web::Path<String>) -> impl Responder {
     let path = path.into_inner();
     let file_path = if path.is_empty() { "index.html" } else { &path };
 
@@ -362,57 +1297,314 @@ async fn serve_index() -> impl Responder {
     }
 }
 
-// Background task to record historical data
+// Background task to record the latest snapshot as history and prune it past the
+// retention period. Interval, retention and the on/off switch are re-read from the
+// config every cycle.
 async fn record_history_task(state: Arc<AppState>) {
-    let mut interval = interval(Duration::from_secs(60)); // Record every minute
+    let mut last_cleanup: Option<std::time::Instant> = None;
     
     loop {
-        interval.tick().await;
+        let (enabled, recording_interval, retention_days) = {
+            let config = state.config.read().unwrap();
+            (config.api.auto_record_history, config.history.recording_interval, config.history.retention_days)
+        };
         
-        if let Some(ref history_manager) = state.history_manager {
-            let mut pm = state.process_manager.lock().unwrap();
-            
-            if let Err(e) = pm.refresh() {
-                eprintln!("Failed to refresh processes for history: {}", e);
-                continue;
+        if let (true, Some(history_manager)) = (enabled, &state.history_manager) {
+            // Prune old data once a day
+            if last_cleanup.is_none_or(|t| t.elapsed() >= Duration::from_secs(86400)) {
+                let hm = history_manager.lock().unwrap();
+                match hm.clean_old_data(retention_days) {
+                    Ok(deleted) => info!("Removed {} history rows older than {} days", deleted, retention_days),
+                    Err(e) => warn!("Failed to clean old history data: {}", e),
+                }
+                last_cleanup = Some(std::time::Instant::now());
             }
             
-            let processes_refs = pm.get_processes();
-            // Clone to owned values for history recording
-            let processes: Vec<ProcessInfo> = processes_refs.iter().map(|p| (*p).clone()).collect();
-            let system_info = pm.get_system_info();
-            
-            let hm = history_manager.lock().unwrap();
-            
-            if let Err(e) = hm.record_processes(&processes) {
-                eprintln!("Failed to record process history: {}", e);
+            record_history_sample(&state, history_manager);
+        }
+        
+        sleep(Duration::from_secs(recording_interval.max(1))).await;
+    }
+}
+
+fn record_history_sample(state: &AppState, history_manager: &Mutex<HistoryManager>) {
+    let snapshot = state.collector.latest();
+    let system_info = match snapshot.system {
+        Some(ref system_info) => system_info,
+        None => return,
+    };
+    
+    let hm = history_manager.lock().unwrap();
+    
+    if let Err(e) = hm.record_processes(&snapshot.processes) {
+        eprintln!("Failed to record process history: {}", e);
+    }
+    
+    if let Err(e) = hm.record_system_stats(
+        system_info.cpu_count,
+        (system_info.load_average.one, system_info.load_average.five, system_info.load_average.fifteen),
+        system_info.total_memory,
+        system_info.used_memory,
+        system_info.total_swap,
+        system_info.used_swap,
+        system_info.uptime,
+    ) {
+        eprintln!("Failed to record system history: {}", e);
+    }
+}
+
+// Background task to feed the anomaly detector with collector snapshots while the
+// feature is enabled. A snapshot is analysed at most once; new anomalies are
+// passed on to the alert manager.
+async fn anomaly_detection_task(state: Arc<AppState>) {
+    let mut last_sequence = 0;
+    
+    loop {
+        let (enabled, sample_interval) = {
+            let config = state.config.read().unwrap();
+            (config.features.anomaly_detection, config.history.recording_interval)
+        };
+        
+        let snapshot = state.collector.latest();
+        if enabled && snapshot.sequence > last_sequence {
+            last_sequence = snapshot.sequence;
+            let anomalies = state.anomaly_detector.lock().unwrap().update(&snapshot.processes);
+            if !anomalies.is_empty() {
+                let mut manager = state.alert_manager.lock().await;
+                if let Err(e) = manager.report_anomalies(&anomalies).await {
+                    warn!("Failed to raise anomaly alerts: {}", e);
+                }
+                store_alert_events(&state, &mut manager);
             }
-            
-            if let Err(e) = hm.record_system_stats(
-                system_info.cpu_count,
-                (system_info.load_average.one, system_info.load_average.five, system_info.load_average.fifteen),
-                system_info.total_memory,
-                system_info.used_memory,
-                system_info.total_swap,
-                system_info.used_swap,
-                system_info.uptime,
-            ) {
-                eprintln!("Failed to record system history: {}", e);
+        }
+        
+        sleep(Duration::from_secs(sample_interval.max(1))).await;
+    }
+}
+
+// Background task that checks every collector snapshot against the alert rules.
+// Rules are rebuilt from `[alerts]` when the config changes; while alerts are
+// disabled no rules are evaluated.
+async fn alert_task(state: Arc<AppState>) {
+    let mut receiver = state.collector.subscribe();
+    
+    while receiver.changed().await.is_ok() {
+        let snapshot = receiver.borrow_and_update().clone();
+        let (rules, windows) = {
+            let config = state.config.read().unwrap();
+            let rules = if config.alerts.enabled {
+                alerts::rules_from_config(&config.alerts)
+            } else {
+                Vec::new()
+            };
+            (rules, config.alerts.maintenance_windows.clone())
+        };
+        
+        let mut manager = state.alert_manager.lock().await;
+        if manager.rules() != rules.as_slice() {
+            info!("Alert rules updated: {} rule(s)", rules.len());
+            manager.set_rules(rules);
+        }
+        manager.set_config_maintenance_windows(windows);
+        if let Err(e) = manager.check_processes(&snapshot.processes).await {
+            warn!("Failed to evaluate alert rules: {}", e);
+        }
+        store_alert_events(&state, &mut manager);
+    }
+}
+
+/// Move the alert events since the last call into the alert history; they
+/// are dropped when history is disabled
+fn store_alert_events(state: &AppState, manager: &mut AlertManager) {
+    let events = manager.take_events();
+    let Some(ref history_manager) = state.history_manager else {
+        return;
+    };
+    let hm = history_manager.lock().unwrap();
+    let mut failed = 0;
+    let mut last_error = None;
+    for event in &events {
+        if let Err(e) = hm.record_alert_event(event) {
+            failed += 1;
+            last_error = Some(e);
+        }
+    }
+    if let Some(e) = last_error {
+        warn!("Failed to record {} of {} alert history event(s): {}", failed, events.len(), e);
+    }
+}
+
+/// Middleware enforcing `[api.auth]` on every request.
+/// 
+/// Refused requests get a 401 (with `WWW-Authenticate` challenges) or 403
+/// and a JSON error body. On success the [`auth::Principal`] is stored in
+/// the request extensions for handlers that need to know who is calling.
+async fn require_auth(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let auth_config = {
+        let state = req.app_data::<web::Data<AppState>>().expect("AppState is registered");
+        let config = state.config.read().unwrap();
+        config.api.auth.clone()
+    };
+    let authorization = req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    // Routes match the percent-decoded path, so authorize on that rather
+    // than the raw `req.path()`
+    let method = req.method().clone();
+    let path = req.match_info().as_str().to_string();
+    // Password hashes are deliberately slow to verify, so keep them off the worker
+    let result = web::block(move || {
+        auth::authorize(&auth_config, &method, &path, authorization.as_deref())
+            .map_err(|e| (e, !auth_config.users.is_empty()))
+    })
+    .await?;
+    
+    match result {
+        Ok(Some(principal)) => {
+            debug!(client = %principal.name, role = %principal.role, "API: authenticated {} {}", req.method(), req.path());
+            req.extensions_mut().insert(principal);
+        }
+        Ok(None) => {}
+        Err((e, offer_basic)) => {
+            warn!(path = req.path(), method = %req.method(), "API: refused request: {}", e);
+            let body = match e {
+                AuthError::Forbidden { required, actual } => serde_json::json!({
+                    "error": e.to_string(),
+                    "required_role": required,
+                    "role": actual,
+                }),
+                _ => serde_json::json!({ "error": e.to_string() }),
+            };
+            let mut response = HttpResponse::build(e.status_code());
+            if e.status_code() == actix_web::http::StatusCode::UNAUTHORIZED {
+                response.append_header((header::WWW_AUTHENTICATE, "Bearer realm=\"process-manager\""));
+                if offer_basic {
+                    response.append_header((header::WWW_AUTHENTICATE, "Basic realm=\"process-manager\""));
+                }
+            }
+            return Ok(req.into_response(response.json(body)));
+        }
+    }
+    
+    next.call(req).await.map(|res| res.map_into_boxed_body())
+}
+
+/// How often the config file's modification time is checked when `api.watch_config` is set
+const CONFIG_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+// Background task that reloads the configuration on SIGHUP, and on file
+// changes when `api.watch_config` is enabled
+async fn config_reload_task(state: Arc<AppState>, source: ConfigSource) {
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            error!("Failed to install SIGHUP handler, config reload disabled: {}", e);
+            return;
+        }
+    };
+    let mut watch = interval(CONFIG_WATCH_INTERVAL);
+    let mut last_modified: Option<SystemTime> = source.modified();
+    
+    info!(path = ?source.path, "Config reload enabled (send SIGHUP to reload)");
+    
+    loop {
+        tokio::select! {
+            _ = hangup.recv() => {
+                info!("Received SIGHUP, reloading configuration");
+                last_modified = source.modified();
+                reload_config(&state, &source);
             }
+            _ = watch.tick() => {
+                if !state.config.read().unwrap().api.watch_config {
+                    continue;
+                }
+                let modified = source.modified();
+                if modified.is_some() && modified != last_modified {
+                    info!(path = ?source.path, "Config file changed, reloading configuration");
+                    last_modified = modified;
+                    reload_config(&state, &source);
+                }
+            }
+        }
+    }
+}
+
+/// Load the configuration again from `source` and swap it in if it is valid.
+/// 
+/// Alert thresholds, history interval and retention, and feature toggles take
+/// effect immediately. Changes to the listening socket, CORS or the history
+/// database are logged and apply after a restart. Invalid files are rejected
+/// and the running configuration is kept.
+/// 
+/// # Returns
+/// 
+/// `true` if the new configuration was applied
+pub fn reload_config(state: &AppState, source: &ConfigSource) -> bool {
+    let candidate = match source.load() {
+        Ok(config) => config,
+        Err(e) => {
+            error!(path = ?source.path, "Rejected configuration reload: {:#}", e);
+            return false;
+        }
+    };
+    
+    let changes = state.config.read().unwrap().diff(&candidate);
+    
+    if let Err(errors) = candidate.validate() {
+        error!(path = ?source.path, "Rejected configuration reload: {} invalid setting(s)", errors.len());
+        for e in &errors {
+            error!("  invalid: {}", e);
         }
+        for change in &changes {
+            warn!("  not applied: {}", change);
+        }
+        return false;
+    }
+    
+    if changes.is_empty() {
+        info!("Configuration reloaded, no changes");
+        return true;
     }
+    
+    for change in &changes {
+        if change.requires_restart() {
+            warn!("  {} (takes effect after restart)", change);
+        } else {
+            info!("  {}", change);
+        }
+    }
+    
+    state.collector.set_features(candidate.features.clone());
+    *state.config.write().unwrap() = candidate;
+    
+    log_system_event(
+        "config_reload",
+        &format!("Configuration reloaded with {} change(s)", changes.len()),
+        tracing::Level::INFO,
+    );
+    true
 }
 
 /// Start the REST API server
+/// 
+/// Binds to `config.api.bind_address:config.api.port`. History storage,
+/// background recording, CORS, anomaly detection and alerting follow the
+/// `history`, `api`, `features` and `alerts` sections of `config`; `[api.tls]` switches the listener
+/// to HTTPS for both `/api/*` and the web UI. When `config_source` is given,
+/// the configuration is reloaded from it on SIGHUP (see [`reload_config`]).
 pub async fn start_api_server(
-    bind_address: &str,
+    config: Config,
     process_manager: ProcessManager,
-    history_db_path: Option<String>,
+    config_source: Option<ConfigSource>,
 ) -> std::io::Result<()> {
-    let pm = Arc::new(Mutex::new(process_manager));
+    let bind_address = format!("{}:{}", config.api.bind_address, config.api.port);
 
-    let history_manager = if let Some(db_path) = history_db_path {
-        match HistoryManager::new(&db_path) {
+    let history_manager = if config.history.enabled {
+        match HistoryManager::new(&config.history.database_path) {
             Ok(hm) => Some(Arc::new(Mutex::new(hm))),
             Err(e) => {
                 eprintln!("Failed to initialize history manager: {}", e);
@@ -423,9 +1615,58 @@ pub async fn start_api_server(
         None
     };
 
+    let anomaly_detector = Arc::new(Mutex::new(AnomalyDetector::new(AnomalyDetectorConfig::default())));
+    let notification_config = config.alerts.notifications.clone();
+    let (mut alert_manager, alert_rx) = AlertManager::new(Vec::new(), notification_config.clone());
+    alert_manager.set_config_maintenance_windows(config.alerts.maintenance_windows.clone());
+    if let Some(ref history_manager) = history_manager {
+        let hm = history_manager.lock().unwrap();
+        match hm.last_alert_id() {
+            Ok(last_id) => alert_manager.resume_ids(last_id),
+            Err(e) => warn!("Failed to read the alert history: {}", e),
+        }
+        match (hm.get_silences(Utc::now()), hm.get_maintenance_windows()) {
+            (Ok(silences), Ok(windows)) => {
+                for silence in silences {
+                    alert_manager.add_silence(silence);
+                }
+                for window in windows {
+                    alert_manager.add_maintenance_window(window);
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => warn!("Failed to load alert silences and maintenance windows: {}", e),
+        }
+    }
+    let enable_cors = config.api.enable_cors;
+    
+    // Load certificates before spawning anything so a bad TLS setup fails fast
+    let tls_config = if config.api.tls.enabled {
+        let tls = crate::tls::load_server_config(&config.api.tls, &config.api.bind_address)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{:#}", e)))?;
+        Some(tls)
+    } else {
+        None
+    };
+    let scheme = if tls_config.is_some() { "https" } else { "http" };
+
+    // Collect once up front so the first requests are answered from real data
+    let collector = Arc::new(Collector::new(process_manager));
+    if let Err(e) = collector.collect() {
+        warn!("Initial process collection failed: {}", e);
+    }
+
     let app_state = Arc::new(AppState {
-        process_manager: pm,
+        collector: collector.clone(),
         history_manager: history_manager.clone(),
+        anomaly_detector,
+        alert_manager: Arc::new(tokio::sync::Mutex::new(alert_manager)),
+        config: Arc::new(RwLock::new(config)),
+    });
+
+    // Start background collector
+    let config_clone = app_state.config.clone();
+    tokio::spawn(async move {
+        collector.run(config_clone).await;
     });
 
     // Start background history recording task
@@ -436,39 +1677,101 @@ pub async fn start_api_server(
         });
     }
 
-    println!("Starting REST API server on {}", bind_address);
-    println!("Web UI embedded in binary - no external files needed");
+    // Start background anomaly detection task
+    let state_clone = app_state.clone();
+    tokio::spawn(async move {
+        anomaly_detection_task(state_clone).await;
+    });
 
-    let app_state_data = web::Data::new(AppState {
-        process_manager: app_state.process_manager.clone(),
-        history_manager: app_state.history_manager.clone(),
+    // Start alert evaluation and notification delivery
+    let state_clone = app_state.clone();
+    tokio::spawn(async move {
+        alert_task(state_clone).await;
     });
+    tokio::spawn(Notifier::new(notification_config, history_manager.clone()).run(alert_rx));
 
-    HttpServer::new(move || {
-        let cors = Cors::default()
-            .allow_any_origin()
-            .allow_any_method()
-            .allow_any_header();
+    // Start config reload task
+    if let Some(source) = config_source {
+        let state_clone = app_state.clone();
+        tokio::spawn(async move {
+            config_reload_task(state_clone, source).await;
+        });
+    }
+
+    {
+        let config = app_state.config.read().unwrap();
+        if !config.api.auth.enabled {
+            let loopback = config.api.bind_address.parse::<std::net::IpAddr>()
+                .map(|ip| ip.is_loopback())
+                .unwrap_or(false);
+            if !loopback {
+                warn!(bind_address = %config.api.bind_address, "API authentication is disabled on a non-loopback address");
+                println!("⚠️  Warning: API authentication is disabled and the server is reachable from the network.");
+                println!("   Anyone who can connect can signal any process. See [api.auth] in the config.");
+            }
+        }
+    }
+
+    println!("Starting REST API server on {}://{}", scheme, bind_address);
+    println!("Web UI embedded in binary - no external files needed");
+
+    let app_state_data = web::Data::from(app_state.clone());
+
+    let server = HttpServer::new(move || {
+        let cors = if enable_cors {
+            Cors::default()
+                .allow_any_origin()
+                .allow_any_method()
+                .allow_any_header()
+        } else {
+            Cors::default()
+        };
 
         App::new()
             .app_data(app_state_data.clone())
+            .wrap(middleware::from_fn(require_auth))
             .wrap(middleware::Logger::default())
             .wrap(cors)
             // API routes
             .route("/api/health", web::get().to(health_check))
             .route("/api/processes", web::get().to(get_processes))
+            .route("/api/processes/stream", web::get().to(stream_processes))
             .route("/api/processes/{pid}", web::get().to(get_process))
             .route("/api/processes/kill", web::post().to(kill_process))
+            .route("/api/processes/{pid}/kill-tree", web::post().to(kill_process_tree))
+            .route("/api/processes/{pid}/sockets", web::get().to(get_process_sockets))
+            .route("/api/processes/{pid}/fds", web::get().to(get_process_fds))
+            .route("/api/processes/{pid}/threads", web::get().to(get_process_threads))
+            .route("/api/processes/{pid}/priority", web::put().to(set_process_priority))
+            .route("/api/processes/{pid}/affinity", web::put().to(set_process_affinity))
+            .route("/api/ports/{port}", web::get().to(get_port_owners))
             .route("/api/system", web::get().to(get_system_info))
             .route("/api/history/processes", web::get().to(get_process_history))
             .route("/api/history/top-cpu", web::get().to(get_top_cpu_consumers))
+            .route("/api/anomalies", web::get().to(get_anomalies))
+            .route("/api/alerts", web::get().to(get_alerts))
+            .route("/api/alerts/rules", web::get().to(get_alert_rules))
+            .route("/api/alerts/rules/check", web::get().to(check_alert_rule))
+            .route("/api/alerts/history", web::get().to(get_alert_history))
+            .route("/api/alerts/silences", web::get().to(get_silences))
+            .route("/api/alerts/silences", web::post().to(create_silence))
+            .route("/api/alerts/silences/{id}", web::delete().to(delete_silence))
+            .route("/api/alerts/maintenance-windows", web::get().to(get_maintenance_windows))
+            .route("/api/alerts/maintenance-windows", web::post().to(create_maintenance_window))
+            .route("/api/alerts/maintenance-windows/{id}", web::delete().to(delete_maintenance_window))
+            .route("/api/alerts/{id}/ack", web::post().to(acknowledge_alert))
+            .route("/api/audit", web::get().to(get_audit_log))
             // Serve embedded static files
             .route("/", web::get().to(serve_index))
             .route("/{path:.*}", web::get().to(serve_embedded_file))
-    })
-    .bind(bind_address)?
-    .run()
-    .await
+    });
+    
+    let server = match tls_config {
+        Some(tls) => server.bind_rustls_0_23(&bind_address, tls)?,
+        None => server.bind(&bind_address)?,
+    };
+    
+    server.run().await
 }
 
 
@@ -492,6 +1795,7 @@ mod tests {
             command: "test command".to_string(),
             start_time: 123456,
             running_time: Duration::from_secs(3600),
+            cpu_time: Default::default(),
             uid: 0,
             gid: 0,
             threads: 1,
@@ -502,10 +1806,114 @@ mod tests {
             container_id: None,
             cgroup_memory_limit: None,
             gpu_memory: None,
+            disk_io: None,
+            network_io: None,
+            open_fds: None,
+            fd_limit: None,
         };
         
         let api_info = ApiProcessInfo::from(&process);
         assert_eq!(api_info.pid, 1234);
         assert_eq!(api_info.name, "test");
     }
+
+    fn test_state(config: Config) -> AppState {
+        AppState {
+            collector: Arc::new(Collector::new(ProcessManager::new())),
+            history_manager: None,
+            anomaly_detector: Arc::new(Mutex::new(AnomalyDetector::new(AnomalyDetectorConfig::default()))),
+            alert_manager: Arc::new(tokio::sync::Mutex::new(AlertManager::new(Vec::new(), Default::default()).0)),
+            config: Arc::new(RwLock::new(config)),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_auth_applies_to_percent_encoded_paths() {
+        use actix_web::test;
+        
+        let mut config = Config::default();
+        config.api.auth.enabled = true;
+        config.api.auth.tokens.push(crate::config::ApiToken {
+            name: "ops".to_string(),
+            token: "operator-token-0123".to_string(),
+            role: crate::config::ApiRole::Operator,
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(test_state(config)))
+                .wrap(middleware::from_fn(require_auth))
+                .route("/api/health", web::get().to(HttpResponse::Ok))
+                .route("/api/system", web::get().to(HttpResponse::Ok))
+                .route("/api/processes/{pid}/kill-tree", web::post().to(HttpResponse::Ok)),
+        ).await;
+        
+        for (method, uri) in [
+            (actix_web::http::Method::GET, "/api/system"),
+            (actix_web::http::Method::GET, "/%61pi/system"),
+            (actix_web::http::Method::GET, "/%61%70%69/%73ystem"),
+            (actix_web::http::Method::POST, "/%61pi/processes/1/kill-tree"),
+        ] {
+            let req = test::TestRequest::default().method(method.clone()).uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED, "{} {}", method, uri);
+        }
+        
+        // The encoded path does reach the route once authenticated
+        let req = test::TestRequest::get()
+            .uri("/%61pi/system")
+            .insert_header((header::AUTHORIZATION, "Bearer operator-token-0123"))
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        let req = test::TestRequest::get().uri("/api/health").to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_create_silence_rejects_overlong_durations() {
+        use actix_web::test;
+        
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(test_state(Config::default())))
+                .route("/api/alerts/silences", web::post().to(create_silence)),
+        ).await;
+        
+        for (duration, status) in [
+            ("9999999999999s", 400),
+            ("99999999999999999999s", 400),
+            ("31d", 400),
+            ("2h", 201),
+        ] {
+            let req = test::TestRequest::post()
+                .uri("/api/alerts/silences")
+                .set_json(serde_json::json!({ "rule": "cpu", "duration": duration }))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status().as_u16(), status, "{}", duration);
+        }
+    }
+
+    #[test]
+    fn test_reload_config_applies_valid_and_rejects_invalid() {
+        use crate::config::ConfigOverrides;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let source = ConfigSource::new(path.clone(), ConfigOverrides::default());
+        
+        let state = test_state(Config::default());
+        
+        std::fs::write(&path, "[history]\nrecording_interval = 15\n[alerts]\ncpu_threshold = 95.0\n").unwrap();
+        assert!(reload_config(&state, &source));
+        assert_eq!(state.config.read().unwrap().history.recording_interval, 15);
+        assert_eq!(state.config.read().unwrap().alerts.cpu_threshold, 95.0);
+        
+        std::fs::write(&path, "[history]\nrecording_interval = 0\n").unwrap();
+        assert!(!reload_config(&state, &source));
+        assert_eq!(state.config.read().unwrap().history.recording_interval, 15);
+        
+        std::fs::write(&path, "[history\n").unwrap();
+        assert!(!reload_config(&state, &source));
+        assert_eq!(state.config.read().unwrap().alerts.cpu_threshold, 95.0);
+    }
 }
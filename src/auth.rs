@@ -0,0 +1,307 @@
+//! # REST API Authentication
+//!
+//! Bearer-token and HTTP basic authentication for the REST API, with
+//! role-based authorization of each route.
+//!
+//! ## Features
+//!
+//! - **Bearer tokens**: `Authorization: Bearer <token>` for scripts and dashboards
+//! - **Basic auth**: `Authorization: Basic ...` checked against salted Argon2
+//!   password hashes, so browsers can log in to the web UI with their native prompt
+//! - **Roles**: `read_only` may use any `GET` route; `operator` may also use
+//!   routes that change state (kill, renice, affinity)
+//! - `/api/health`, CORS preflight requests and the embedded web UI assets
+//!   are always public
+//!
+//! ## Example
+//!
+//! ```toml
+//! [api.auth]
+//! enabled = true
+//!
+//! [[api.auth.tokens]]
+//! name = "grafana"
+//! token = "a-long-random-string"
+//! role = "read_only"
+//!
+//! [[api.auth.users]]
+//! username = "admin"
+//! # echo 'secret' | process-manager --hash-password
+//! password_hash = "$argon2id$v=19$m=19456,t=2,p=1$UM5fxoCNznk+jz82Ba8ZGg$nSnTvcM2ykMVVmmCHzIORgW6Gcz+Uw3EPpTShs9y2s0"
+//! role = "operator"
+//! ```
+
+use crate::config::{ApiRole, AuthConfig};
+use actix_web::http::{Method, StatusCode};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use base64::Engine;
+use thiserror::Error;
+
+/// The client a request was authenticated as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Token name or basic-auth username
+    pub name: String,
+    pub role: ApiRole,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AuthError {
+    #[error("Authentication required")]
+    MissingCredentials,
+
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+
+    #[error("Role '{actual}' is not allowed to perform this action, '{required}' is required")]
+    Forbidden { required: ApiRole, actual: ApiRole },
+}
+
+impl AuthError {
+    /// HTTP status to answer with: 401 for missing or bad credentials, 403 for
+    /// an authenticated client without the required role
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AuthError::MissingCredentials | AuthError::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            AuthError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+/// Role needed to call `method path`, or `None` if the route is public.
+///
+/// `path` must be the percent-decoded path the router matches on. Read-only
+/// methods under `/api/` need `read_only`; every other method changes state
+/// and needs `operator`.
+pub fn required_role(method: &Method, path: &str) -> Option<ApiRole> {
+    if method == Method::OPTIONS || is_public_path(path) {
+        return None;
+    }
+
+    if method == Method::GET || method == Method::HEAD {
+        Some(ApiRole::ReadOnly)
+    } else {
+        Some(ApiRole::Operator)
+    }
+}
+
+/// Whether `path` is served without credentials: `/api/health` and the web
+/// UI. Paths that could still reach an API route once decoded further or
+/// normalized (leftover `%` escapes, `//`, `.` or `..` segments) are not.
+fn is_public_path(path: &str) -> bool {
+    if path == "/api/health" {
+        return true;
+    }
+    let ambiguous = path.contains('%')
+        || path.contains("//")
+        || path.contains('\\')
+        || path.split('/').any(|segment| segment == "." || segment == "..");
+    !ambiguous && path != "/api" && !path.starts_with("/api/")
+}
+
+/// Check a request against the auth configuration.
+///
+/// # Arguments
+///
+/// * `config` - Current `[api.auth]` settings
+/// * `method`, `path` - The request being made
+/// * `authorization` - Value of the `Authorization` header, if any
+///
+/// # Returns
+///
+/// * `Ok(Some(principal))` for an authenticated client with a sufficient role
+/// * `Ok(None)` when auth is disabled or the route is public
+/// * `Err` with the reason the request must be refused
+pub fn authorize(
+    config: &AuthConfig,
+    method: &Method,
+    path: &str,
+    authorization: Option<&str>,
+) -> Result<Option<Principal>, AuthError> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    let required = match required_role(method, path) {
+        Some(role) => role,
+        None => return Ok(None),
+    };
+
+    let principal = authenticate(config, authorization.ok_or(AuthError::MissingCredentials)?)?;
+
+    if principal.role < required {
+        return Err(AuthError::Forbidden {
+            required,
+            actual: principal.role,
+        });
+    }
+
+    Ok(Some(principal))
+}
+
+/// Resolve an `Authorization` header value to a configured token or user
+fn authenticate(config: &AuthConfig, authorization: &str) -> Result<Principal, AuthError> {
+    let (scheme, credentials) = authorization
+        .trim()
+        .split_once(' ')
+        .ok_or(AuthError::InvalidCredentials)?;
+    let credentials = credentials.trim();
+
+    if scheme.eq_ignore_ascii_case("Bearer") {
+        config
+            .tokens
+            .iter()
+            .find(|t| constant_time_eq(t.token.as_bytes(), credentials.as_bytes()))
+            .map(|t| Principal {
+                name: t.name.clone(),
+                role: t.role,
+            })
+            .ok_or(AuthError::InvalidCredentials)
+    } else if scheme.eq_ignore_ascii_case("Basic") {
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(credentials)
+            .map_err(|_| AuthError::InvalidCredentials)?;
+        let decoded = String::from_utf8(decoded).map_err(|_| AuthError::InvalidCredentials)?;
+        let (username, password) = decoded.split_once(':').ok_or(AuthError::InvalidCredentials)?;
+
+        config
+            .users
+            .iter()
+            .find(|u| u.username == username && verify_password(password, &u.password_hash))
+            .map(|u| Principal {
+                name: u.username.clone(),
+                role: u.role,
+            })
+            .ok_or(AuthError::InvalidCredentials)
+    } else {
+        Err(AuthError::InvalidCredentials)
+    }
+}
+
+/// Hash a password into the Argon2id PHC string stored in `password_hash`,
+/// with a fresh random salt
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+/// Whether `password` matches an Argon2 PHC string, using the parameters and
+/// salt stored in it; a malformed hash never matches
+fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .is_ok_and(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+}
+
+/// Compare secrets without leaking how many leading bytes matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ApiToken, ApiUser};
+
+    fn test_config() -> AuthConfig {
+        AuthConfig {
+            enabled: true,
+            tokens: vec![ApiToken {
+                name: "grafana".to_string(),
+                token: "read-only-token-0123".to_string(),
+                role: ApiRole::ReadOnly,
+            }],
+            users: vec![ApiUser {
+                username: "admin".to_string(),
+                password_hash: hash_password("secret").unwrap(),
+                role: ApiRole::Operator,
+            }],
+        }
+    }
+
+    fn basic(user: &str, password: &str) -> String {
+        format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password))
+        )
+    }
+
+    #[test]
+    fn test_required_role() {
+        assert_eq!(required_role(&Method::GET, "/api/health"), None);
+        assert_eq!(required_role(&Method::GET, "/index.html"), None);
+        assert_eq!(required_role(&Method::OPTIONS, "/api/processes/kill"), None);
+        assert_eq!(required_role(&Method::GET, "/api/processes"), Some(ApiRole::ReadOnly));
+        assert_eq!(required_role(&Method::POST, "/api/processes/kill"), Some(ApiRole::Operator));
+        assert_eq!(required_role(&Method::PUT, "/api/processes/1/priority"), Some(ApiRole::Operator));
+
+        // Paths that might still be normalized into an API route are not public
+        assert_eq!(required_role(&Method::GET, "/api"), Some(ApiRole::ReadOnly));
+        assert_eq!(required_role(&Method::GET, "/%61pi/system"), Some(ApiRole::ReadOnly));
+        assert_eq!(required_role(&Method::GET, "//api/system"), Some(ApiRole::ReadOnly));
+        assert_eq!(required_role(&Method::POST, "/static/../api/processes/1/kill-tree"), Some(ApiRole::Operator));
+        assert_eq!(required_role(&Method::GET, "/assets/app.js"), None);
+    }
+
+    #[test]
+    fn test_authorize_bearer_and_basic() {
+        let config = test_config();
+
+        let principal = authorize(&config, &Method::GET, "/api/processes", Some("Bearer read-only-token-0123"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(principal.name, "grafana");
+
+        let principal = authorize(&config, &Method::POST, "/api/processes/kill", Some(&basic("admin", "secret")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(principal.role, ApiRole::Operator);
+
+        assert_eq!(authorize(&config, &Method::GET, "/api/health", None), Ok(None));
+    }
+
+    #[test]
+    fn test_authorize_rejections() {
+        let config = test_config();
+
+        assert_eq!(
+            authorize(&config, &Method::GET, "/api/system", None),
+            Err(AuthError::MissingCredentials)
+        );
+        assert_eq!(
+            authorize(&config, &Method::GET, "/api/system", Some("Bearer wrong")),
+            Err(AuthError::InvalidCredentials)
+        );
+        assert_eq!(
+            authorize(&config, &Method::GET, "/api/system", Some(&basic("admin", "guess"))),
+            Err(AuthError::InvalidCredentials)
+        );
+
+        let err = authorize(&config, &Method::POST, "/api/processes/kill", Some("Bearer read-only-token-0123"))
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::FORBIDDEN);
+        assert_eq!(AuthError::MissingCredentials.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_password_hashes_are_salted() {
+        let first = hash_password("secret").unwrap();
+        let second = hash_password("secret").unwrap();
+        assert!(first.starts_with("$argon2id$"));
+        assert_ne!(first, second);
+        assert!(verify_password("secret", &first) && verify_password("secret", &second));
+        assert!(!verify_password("Secret", &first));
+        // Unsalted SHA-256 of "secret", as older configs stored it
+        assert!(!verify_password("secret", "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"));
+    }
+
+    #[test]
+    fn test_authorize_disabled() {
+        let mut config = test_config();
+        config.enabled = false;
+        assert_eq!(authorize(&config, &Method::POST, "/api/processes/kill", None), Ok(None));
+    }
+}
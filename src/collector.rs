@@ -0,0 +1,180 @@
+//! # Background Process Collector
+//!
+//! Owns the API server's [`ProcessManager`] and refreshes it on a timer,
+//! publishing each result as an immutable, shared [`Snapshot`].
+//!
+//! ## Features
+//!
+//! - **One scan per interval**: `/proc` is read once per
+//!   `general.refresh_interval` no matter how many clients are connected
+//! - **Non-blocking readers**: handlers and background tasks clone an `Arc`
+//!   to the latest snapshot instead of locking the process manager, so a slow
+//!   scan never holds up a request
+//! - **Stable CPU percentages**: CPU usage is always measured over the
+//!   collector interval, not over the time since the last HTTP request
+//! - **Change notification**: tasks that want every sample (the live stream)
+//!   subscribe and are woken when a new snapshot is published
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use process_manager::collector::Collector;
+//! use process_manager::process::ProcessManager;
+//!
+//! let collector = Collector::new(ProcessManager::new());
+//! collector.collect().unwrap();
+//!
+//! let snapshot = collector.latest();
+//! println!("{} processes at #{}", snapshot.processes.len(), snapshot.sequence);
+//! ```
+
+use crate::config::{Config, FeatureConfig};
+use crate::process::{ProcessInfo, ProcessManager, SystemInfo};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+/// Process list and system totals from one refresh
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Increases by one with every published snapshot; 0 means nothing has
+    /// been collected yet
+    pub sequence: u64,
+    pub taken_at: DateTime<Utc>,
+    /// All processes, ordered by PID
+    pub processes: Vec<ProcessInfo>,
+    /// System totals, `None` before the first collection
+    pub system: Option<SystemInfo>,
+}
+
+impl Snapshot {
+    /// Placeholder published until the first collection completes
+    pub fn empty() -> Self {
+        Self {
+            sequence: 0,
+            taken_at: Utc::now(),
+            processes: Vec::new(),
+            system: None,
+        }
+    }
+
+    /// Look up a process by PID
+    pub fn get(&self, pid: u32) -> Option<&ProcessInfo> {
+        self.processes
+            .binary_search_by_key(&pid, |p| p.pid)
+            .ok()
+            .map(|i| &self.processes[i])
+    }
+}
+
+/// Refreshes a [`ProcessManager`] and publishes the results as [`Snapshot`]s
+pub struct Collector {
+    manager: Mutex<ProcessManager>,
+    sender: watch::Sender<Arc<Snapshot>>,
+}
+
+impl Collector {
+    pub fn new(manager: ProcessManager) -> Self {
+        Self {
+            manager: Mutex::new(manager),
+            sender: watch::channel(Arc::new(Snapshot::empty())).0,
+        }
+    }
+
+    /// The most recently published snapshot
+    pub fn latest(&self) -> Arc<Snapshot> {
+        self.sender.borrow().clone()
+    }
+
+    /// Receiver that is notified whenever a new snapshot is published
+    pub fn subscribe(&self) -> watch::Receiver<Arc<Snapshot>> {
+        self.sender.subscribe()
+    }
+
+    /// Number of live [`subscribe`](Self::subscribe) receivers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Replace the feature toggles used by subsequent collections
+    pub fn set_features(&self, features: FeatureConfig) {
+        self.manager.lock().unwrap().set_features(features);
+    }
+
+    /// Refresh the process list now and publish the result.
+    ///
+    /// This blocks while `/proc` is scanned; from async code run it on the
+    /// blocking pool. Readers of [`latest`](Self::latest) are not held up.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot that was published
+    pub fn collect(&self) -> Result<Arc<Snapshot>> {
+        let (mut processes, system) = {
+            let mut pm = self.manager.lock().unwrap();
+            pm.refresh()?;
+            let processes: Vec<ProcessInfo> = pm.get_processes().into_iter().cloned().collect();
+            (processes, pm.get_system_info())
+        };
+        processes.sort_unstable_by_key(|p| p.pid);
+
+        let snapshot = Arc::new(Snapshot {
+            sequence: self.sender.borrow().sequence + 1,
+            taken_at: Utc::now(),
+            processes,
+            system: Some(system),
+        });
+        debug!(sequence = snapshot.sequence, processes = snapshot.processes.len(), "Published process snapshot");
+        self.sender.send_replace(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Collect once per `general.refresh_interval`, re-reading the interval
+    /// from `config` every cycle so reloads take effect. Runs forever.
+    pub async fn run(self: Arc<Self>, config: Arc<RwLock<Config>>) {
+        loop {
+            let refresh_interval = config.read().unwrap().general.refresh_interval;
+
+            let collector = self.clone();
+            match tokio::task::spawn_blocking(move || collector.collect()).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => warn!("Failed to collect process snapshot: {}", e),
+                Err(e) => warn!("Process collector panicked: {}", e),
+            }
+
+            sleep(Duration::from_secs(refresh_interval.max(1))).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_publishes_snapshots() {
+        let collector = Collector::new(ProcessManager::new());
+        assert_eq!(collector.latest().sequence, 0);
+        assert!(collector.latest().get(std::process::id()).is_none());
+
+        let mut receiver = collector.subscribe();
+        let first = collector.collect().unwrap();
+        assert_eq!(first.sequence, 1);
+        assert!(receiver.has_changed().unwrap());
+        assert!(first.system.is_some());
+        assert!(first.processes.windows(2).all(|w| w[0].pid < w[1].pid));
+        assert!(first.get(std::process::id()).is_some());
+
+        let second = collector.collect().unwrap();
+        assert_eq!(second.sequence, 2);
+        assert_eq!(collector.latest().sequence, 2);
+
+        // Earlier snapshots stay intact for readers still holding them
+        assert_eq!(first.sequence, 1);
+        assert!(receiver.borrow_and_update().sequence >= 2);
+    }
+}
@@ -0,0 +1,580 @@
+//! # Process Table Columns
+//!
+//! Registry of the columns the TUI process table can show. Each column has a
+//! key (as stored in [`ViewProfile::columns`](crate::profiles::ViewProfile)),
+//! a header, a width, an alignment, an optional sort key and formatters for
+//! process rows and per-thread rows.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use process_manager::columns;
+//!
+//! let layout = columns::resolve(&["pid".to_string(), "cpu_time".to_string(), "name".to_string()]);
+//! for column in &layout {
+//!     println!("{:<10} width {:>3} sortable: {}", column.header, column.width, column.sort.is_some());
+//! }
+//! ```
+
+use crate::process::{ProcessInfo, SortColumn};
+use crate::threads::ThreadInfo;
+use tracing::warn;
+
+/// Horizontal alignment of a column's cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// One column of the process table
+#[derive(Debug)]
+pub struct ColumnDef {
+    /// Name used in view profiles
+    pub key: &'static str,
+    pub header: &'static str,
+    /// Width in characters; the minimum width for a fill column
+    pub width: u16,
+    /// Whether the column takes up the remaining space
+    pub fill: bool,
+    pub align: Align,
+    /// Sort order used when sorting by this column
+    pub sort: Option<SortColumn>,
+    /// Shown in the column picker
+    pub description: &'static str,
+    pub format: fn(&ProcessInfo) -> String,
+    /// Cell of a per-thread row; blank when `None`
+    pub thread: Option<fn(&ThreadInfo) -> String>,
+}
+
+impl ColumnDef {
+    /// Pad `text` to the column width according to its alignment
+    pub fn align(&self, text: String) -> String {
+        match self.align {
+            Align::Left => text,
+            Align::Right => format!("{:>width$}", text, width = self.width as usize),
+        }
+    }
+}
+
+/// Columns of a layout without a usable `columns` list
+pub const DEFAULT_COLUMNS: &[&str] = &[
+    "pid", "user", "cpu", "memory_percent", "memory", "cpu_time", "disk_read", "disk_write",
+    "read_syscalls", "write_syscalls", "cancelled_write", "connections", "net_rx", "net_tx",
+    "status", "name", "command",
+];
+
+/// Every available column, in the order the column picker lists them
+pub static COLUMNS: &[ColumnDef] = &[
+    ColumnDef {
+        key: "pid",
+        header: "PID",
+        width: 8,
+        fill: false,
+        align: Align::Left,
+        sort: Some(SortColumn::Pid),
+        description: "Process ID",
+        format: |p| p.pid.to_string(),
+        thread: Some(|t| format!("└{}", t.tid)),
+    },
+    ColumnDef {
+        key: "ppid",
+        header: "PPID",
+        width: 8,
+        fill: false,
+        align: Align::Left,
+        sort: None,
+        description: "Parent process ID",
+        format: |p| p.ppid.to_string(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "user",
+        header: "User",
+        width: 10,
+        fill: false,
+        align: Align::Left,
+        sort: Some(SortColumn::User),
+        description: "Owner user name",
+        format: |p| p.user.clone(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "uid",
+        header: "UID",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Real user ID",
+        format: |p| p.uid.to_string(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "gid",
+        header: "GID",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Real group ID",
+        format: |p| p.gid.to_string(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "cpu",
+        header: "CPU%",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::CpuUsage),
+        description: "CPU usage, 100% = one core",
+        format: |p| format!("{:.1}", p.cpu_usage),
+        thread: Some(|t| format!("{:.1}", t.cpu_usage)),
+    },
+    ColumnDef {
+        key: "memory_percent",
+        header: "Mem%",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::MemoryPercent),
+        description: "Resident memory as a share of RAM",
+        format: |p| format!("{:.1}", p.memory_percent),
+        thread: None,
+    },
+    ColumnDef {
+        key: "memory",
+        header: "Memory",
+        width: 10,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::MemoryUsage),
+        description: "Resident memory",
+        format: |p| format!("{} KB", p.memory_usage),
+        thread: None,
+    },
+    ColumnDef {
+        key: "cpu_time",
+        header: "TIME+",
+        width: 10,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::CpuTime),
+        description: "CPU time, user + system",
+        format: |p| format_cpu_time(p.cpu_time.total()),
+        thread: None,
+    },
+    ColumnDef {
+        key: "cpu_user",
+        header: "User s",
+        width: 9,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "CPU seconds in user mode",
+        format: |p| format!("{:.2}", p.cpu_time.user),
+        thread: None,
+    },
+    ColumnDef {
+        key: "cpu_system",
+        header: "Sys s",
+        width: 9,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "CPU seconds in kernel mode",
+        format: |p| format!("{:.2}", p.cpu_time.system),
+        thread: None,
+    },
+    ColumnDef {
+        key: "cpu_children",
+        header: "Child s",
+        width: 9,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "CPU seconds of exited, waited-for children",
+        format: |p| format!("{:.2}", p.cpu_time.children),
+        thread: None,
+    },
+    ColumnDef {
+        key: "start_time",
+        header: "Start",
+        width: 12,
+        fill: false,
+        align: Align::Left,
+        sort: Some(SortColumn::StartTime),
+        description: "Local start time",
+        format: |p| {
+            chrono::DateTime::from_timestamp(p.start_time as i64, 0)
+                .map(|t| t.with_timezone(&chrono::Local).format("%b %d %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string())
+        },
+        thread: None,
+    },
+    ColumnDef {
+        key: "running_time",
+        header: "Elapsed",
+        width: 11,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Time since start, [[dd-]hh:]mm:ss",
+        format: |p| format_elapsed(p.running_time.as_secs()),
+        thread: None,
+    },
+    ColumnDef {
+        key: "threads",
+        header: "Thr",
+        width: 4,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Number of threads",
+        format: |p| p.threads.to_string(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "priority",
+        header: "Pri",
+        width: 4,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Kernel scheduling priority",
+        format: |p| p.priority.to_string(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "nice",
+        header: "NI",
+        width: 3,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Nice value",
+        format: |p| p.nice.to_string(),
+        thread: None,
+    },
+    ColumnDef {
+        key: "disk_read",
+        header: "Read/s",
+        width: 8,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::DiskRead),
+        description: "Disk bytes read per second",
+        format: |p| p.disk_io.map(|io| format_bytes(io.read_bytes)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "disk_write",
+        header: "Write/s",
+        width: 8,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::DiskWrite),
+        description: "Disk bytes written per second",
+        format: |p| p.disk_io.map(|io| format_bytes(io.write_bytes)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "read_syscalls",
+        header: "rOps/s",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::ReadSyscalls),
+        description: "read(2)-family syscalls per second",
+        format: |p| p.disk_io.map(|io| format!("{:.0}", io.read_syscalls)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "write_syscalls",
+        header: "wOps/s",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::WriteSyscalls),
+        description: "write(2)-family syscalls per second",
+        format: |p| p.disk_io.map(|io| format!("{:.0}", io.write_syscalls)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "cancelled_write",
+        header: "Cncl/s",
+        width: 8,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::CancelledWrite),
+        description: "Written bytes per second that never reached disk",
+        format: |p| p.disk_io.map(|io| format_bytes(io.cancelled_write_bytes)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "connections",
+        header: "Net",
+        width: 5,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Open sockets",
+        format: |p| p.network_connections.map(|n| n.to_string()).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "net_rx",
+        header: "Rx/s",
+        width: 8,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::NetRx),
+        description: "TCP bytes received per second",
+        format: |p| p.network_io.map(|io| format_bytes(io.rx_bytes)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "net_tx",
+        header: "Tx/s",
+        width: 8,
+        fill: false,
+        align: Align::Right,
+        sort: Some(SortColumn::NetTx),
+        description: "TCP bytes sent per second",
+        format: |p| p.network_io.map(|io| format_bytes(io.tx_bytes)).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "fds",
+        header: "FDs",
+        width: 6,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Open file descriptors",
+        format: |p| p.open_fds.map(|n| n.to_string()).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "fd_limit",
+        header: "FD lim",
+        width: 8,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "Soft RLIMIT_NOFILE",
+        format: |p| p.fd_limit.map(|n| n.to_string()).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "container",
+        header: "Container",
+        width: 12,
+        fill: false,
+        align: Align::Left,
+        sort: None,
+        description: "Short container ID",
+        format: |p| {
+            p.container_id
+                .as_ref()
+                .map(|id| id.chars().take(12).collect())
+                .unwrap_or_else(dash)
+        },
+        thread: None,
+    },
+    ColumnDef {
+        key: "cgroup_memory_limit",
+        header: "MemLimit",
+        width: 10,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "cgroup memory limit",
+        format: |p| {
+            p.cgroup_memory_limit
+                .map(crate::network::format_memory_limit)
+                .unwrap_or_else(dash)
+        },
+        thread: None,
+    },
+    ColumnDef {
+        key: "gpu_memory",
+        header: "GPU MB",
+        width: 7,
+        fill: false,
+        align: Align::Right,
+        sort: None,
+        description: "GPU memory (NVIDIA)",
+        format: |p| p.gpu_memory.map(|mb| mb.to_string()).unwrap_or_else(dash),
+        thread: None,
+    },
+    ColumnDef {
+        key: "status",
+        header: "Status",
+        width: 8,
+        fill: false,
+        align: Align::Left,
+        sort: None,
+        description: "State, with container and GPU markers",
+        format: |p| {
+            let mut status = p.status.clone();
+            if p.is_container {
+                status.push_str(" 🐳");
+            }
+            if p.gpu_memory.is_some() {
+                status.push_str(" 🎮");
+            }
+            status
+        },
+        thread: Some(|t| t.state.to_string()),
+    },
+    ColumnDef {
+        key: "name",
+        header: "Name",
+        width: 12,
+        fill: false,
+        align: Align::Left,
+        sort: Some(SortColumn::Name),
+        description: "Process name",
+        format: |p| p.name.clone(),
+        thread: Some(|t| t.name.clone()),
+    },
+    ColumnDef {
+        key: "command",
+        header: "Command",
+        width: 20,
+        fill: true,
+        align: Align::Left,
+        sort: None,
+        description: "Full command line",
+        format: |p| {
+            if p.command.chars().count() > 40 {
+                format!("{}...", p.command.chars().take(37).collect::<String>())
+            } else {
+                p.command.clone()
+            }
+        },
+        thread: Some(|t| {
+            format!(
+                "cpu {} ctxsw {}/{}",
+                t.last_cpu.map(|c| c.to_string()).unwrap_or_else(|| "?".to_string()),
+                t.voluntary_ctxt_switches,
+                t.nonvoluntary_ctxt_switches
+            )
+        }),
+    },
+];
+
+/// Look up a column by its profile key
+pub fn find(key: &str) -> Option<&'static ColumnDef> {
+    COLUMNS.iter().find(|column| column.key == key)
+}
+
+/// Columns for a profile's `columns` list, skipping unknown keys and
+/// duplicates; falls back to [`DEFAULT_COLUMNS`] if nothing is left
+pub fn resolve(keys: &[String]) -> Vec<&'static ColumnDef> {
+    let mut columns: Vec<&'static ColumnDef> = Vec::new();
+    for key in keys {
+        match find(key) {
+            Some(column) if !columns.iter().any(|c| c.key == column.key) => columns.push(column),
+            Some(_) => {}
+            None => warn!("Ignoring unknown column '{}' in view profile", key),
+        }
+    }
+    if columns.is_empty() {
+        columns = DEFAULT_COLUMNS.iter().filter_map(|key| find(key)).collect();
+    }
+    columns
+}
+
+fn dash() -> String {
+    "-".to_string()
+}
+
+/// Format a byte count (or a per-second rate) with a binary unit suffix
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "K", "M", "G"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Format CPU seconds like `top`'s TIME+: `minutes:seconds.hundredths`, or
+/// `hours,minutes` once that no longer fits
+pub fn format_cpu_time(secs: f64) -> String {
+    let hundredths = (secs.max(0.0) * 100.0).round() as u64;
+    let minutes = hundredths / 6000;
+    if minutes < 10000 {
+        format!("{}:{:02}.{:02}", minutes, (hundredths / 100) % 60, hundredths % 100)
+    } else {
+        format!("{},{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+/// Format elapsed seconds like `ps`'s ETIME: `[[dd-]hh:]mm:ss`
+pub fn format_elapsed(secs: u64) -> String {
+    let (days, hours, minutes, seconds) = (secs / 86400, (secs % 86400) / 3600, (secs % 3600) / 60, secs % 60);
+    if days > 0 {
+        format!("{}-{:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    } else if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_keys() {
+        for (i, column) in COLUMNS.iter().enumerate() {
+            assert!(
+                COLUMNS[i + 1..].iter().all(|other| other.key != column.key),
+                "duplicate column {}",
+                column.key
+            );
+            // Sortable columns use the same name as the sort key
+            if let Some(ref sort) = column.sort {
+                assert_eq!(SortColumn::from_name(column.key).as_ref(), Some(sort), "{}", column.key);
+            }
+        }
+        assert!(DEFAULT_COLUMNS.iter().all(|key| find(key).is_some()));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+        let layout = resolve(&keys(&["cpu_time", "bogus", "pid", "cpu_time"]));
+        assert_eq!(layout.iter().map(|c| c.key).collect::<Vec<_>>(), vec!["cpu_time", "pid"]);
+
+        assert_eq!(resolve(&[]).len(), DEFAULT_COLUMNS.len());
+        assert_eq!(resolve(&keys(&["bogus"])).len(), DEFAULT_COLUMNS.len());
+    }
+
+    #[test]
+    fn test_format_and_align() {
+        assert_eq!(format_cpu_time(0.0), "0:00.00");
+        assert_eq!(format_cpu_time(754.31), "12:34.31");
+        assert_eq!(format_cpu_time(700_000.0), "194,26");
+        assert_eq!(format_elapsed(59), "00:59");
+        assert_eq!(format_elapsed(3 * 3600 + 5), "03:00:05");
+        assert_eq!(format_elapsed(2 * 86400 + 61), "2-00:01:01");
+        assert_eq!(format_bytes(512.0), "512B");
+        assert_eq!(format_bytes(1536.0), "1.5K");
+
+        let cpu = find("cpu").unwrap();
+        assert_eq!(cpu.align("5.0".to_string()), "   5.0");
+        assert_eq!(find("name").unwrap().align("bash".to_string()), "bash");
+    }
+}
@@ -1,7 +1,13 @@
+use crate::alerts::{AlertSeverity, NotificationConfig};
+use crate::rules::{parse_duration, Expr};
+use crate::silences::MaintenanceWindow;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
+use std::net::IpAddr;
 use std::path::PathBuf;
+use std::time::SystemTime;
 use tracing::{debug, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +48,10 @@ pub struct GeneralConfig {
     /// Sort in ascending order
     #[serde(default)]
     pub sort_ascending: bool,
+    
+    /// Only show processes owned by this user
+    #[serde(default)]
+    pub user_filter: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +71,11 @@ pub struct UiConfig {
     /// Number of processes to display per page
     #[serde(default = "default_page_size")]
     pub page_size: usize,
+    
+    /// Key of the saved view profile to start with (e.g. `process_tree`);
+    /// its sort order, tree mode and thread display override the settings above
+    #[serde(default)]
+    pub view_profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +99,95 @@ pub struct ApiConfig {
     /// Auto-record history when API is enabled
     #[serde(default = "default_true")]
     pub auto_record_history: bool,
+    
+    /// Reload the configuration automatically when the file changes
+    /// (SIGHUP always triggers a reload)
+    #[serde(default)]
+    pub watch_config: bool,
+    
+    /// Authentication and authorization for `/api/*` routes
+    #[serde(default)]
+    pub auth: AuthConfig,
+    
+    /// HTTPS settings
+    #[serde(default)]
+    pub tls: TlsConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// Serve the API and web UI over HTTPS instead of HTTP
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// PEM certificate chain
+    #[serde(default)]
+    pub cert_path: Option<String>,
+    
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1)
+    #[serde(default)]
+    pub key_path: Option<String>,
+    
+    /// PEM CA bundle; when set, clients must present a certificate signed by it (mutual TLS)
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+    
+    /// Generate and reuse a self-signed certificate when no cert/key is configured
+    #[serde(default)]
+    pub self_signed: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Require credentials for every `/api/*` route except `/api/health`
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Bearer tokens (`Authorization: Bearer <token>`)
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+    
+    /// HTTP basic auth users (`Authorization: Basic ...`)
+    #[serde(default)]
+    pub users: Vec<ApiUser>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// Label used in logs
+    pub name: String,
+    pub token: String,
+    pub role: ApiRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiUser {
+    pub username: String,
+    /// Argon2 PHC string of the password (`$argon2id$...`), as printed by
+    /// `process-manager --hash-password`. Old `password_sha256` entries are
+    /// read into this field so that validation rejects them.
+    #[serde(alias = "password_sha256")]
+    pub password_hash: String,
+    pub role: ApiRole,
+}
+
+/// What an authenticated API client is allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiRole {
+    /// Process list, system info, history and anomalies
+    ReadOnly,
+    /// Everything read-only can do, plus signals, renice and affinity
+    Operator,
+}
+
+impl fmt::Display for ApiRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiRole::ReadOnly => write!(f, "read_only"),
+            ApiRole::Operator => write!(f, "operator"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,6 +223,19 @@ pub struct AlertConfig {
     #[serde(default = "default_memory_threshold")]
     pub memory_threshold: f32,
     
+    /// Seconds a threshold must stay exceeded before an alert is sent
+    #[serde(default = "default_alert_duration")]
+    pub duration_secs: u64,
+    
+    /// Minimum seconds between two alerts for the same process and condition
+    #[serde(default = "default_alert_cooldown")]
+    pub cooldown_secs: u64,
+    
+    /// Raise an alert for anomalies found by the anomaly detector
+    /// (`features.anomaly_detection`) with severity 0.5 or more
+    #[serde(default)]
+    pub alert_on_anomalies: bool,
+    
     /// Alert sound enabled
     #[serde(default)]
     pub sound_enabled: bool,
@@ -126,16 +243,56 @@ pub struct AlertConfig {
     /// Bookmarked processes to watch
     #[serde(default)]
     pub bookmarked_processes: Vec<BookmarkedProcess>,
+    
+    /// Expression rules (`[[alerts.rules]]`)
+    #[serde(default)]
+    pub rules: Vec<AlertRuleConfig>,
+    
+    /// Recurring times when matching alerts are recorded but not notified
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    
+    /// Where alerts are delivered (desktop, email, webhook)
+    #[serde(default)]
+    pub notifications: NotificationConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BookmarkedProcess {
+    /// Matches processes whose name contains this string
     pub name: String,
+    /// Regex the command line must also match, e.g. `^nginx: master` to
+    /// watch only the nginx master process
+    #[serde(default)]
+    pub command_pattern: Option<String>,
+    /// Alert when a matching process exits, and again when one is back
     pub alert_on_exit: bool,
+    /// Alert whenever a new matching process appears
+    #[serde(default)]
+    pub alert_on_start: bool,
     pub alert_on_high_cpu: bool,
     pub alert_on_high_memory: bool,
 }
 
+/// An alert rule written in the [`crate::rules`] expression language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRuleConfig {
+    /// Unique rule name, shown in alerts
+    pub name: String,
+    /// Condition, e.g. `name ~ "^postgres" and memory_percent > 20`
+    pub expr: String,
+    /// How long the condition must hold before alerting, e.g. `5m`
+    #[serde(default, rename = "for", skip_serializing_if = "Option::is_none")]
+    pub for_duration: Option<String>,
+    #[serde(default = "default_rule_severity")]
+    pub severity: AlertSeverity,
+    /// Overrides `alerts.cooldown_secs`, e.g. `1h`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureConfig {
     /// Enable GPU monitoring
@@ -173,6 +330,9 @@ fn default_retention_days() -> i64 { 30 }
 fn default_recording_interval() -> u64 { 60 }
 fn default_cpu_threshold() -> f32 { 80.0 }
 fn default_memory_threshold() -> f32 { 85.0 }
+fn default_alert_duration() -> u64 { 30 }
+fn default_alert_cooldown() -> u64 { 300 }
+fn default_rule_severity() -> AlertSeverity { AlertSeverity::Warning }
 fn default_true() -> bool { true }
 
 impl Default for Config {
@@ -195,6 +355,7 @@ impl Default for GeneralConfig {
             show_only_user_processes: false,
             default_sort_column: default_sort_column(),
             sort_ascending: false,
+            user_filter: None,
         }
     }
 }
@@ -206,6 +367,7 @@ impl Default for UiConfig {
             show_graphs: true,
             color_scheme: default_color_scheme(),
             page_size: default_page_size(),
+            view_profile: None,
         }
     }
 }
@@ -218,6 +380,9 @@ impl Default for ApiConfig {
             bind_address: default_bind_address(),
             enable_cors: true,
             auto_record_history: true,
+            watch_config: false,
+            auth: AuthConfig::default(),
+            tls: TlsConfig::default(),
         }
     }
 }
@@ -239,8 +404,14 @@ impl Default for AlertConfig {
             enabled: false,
             cpu_threshold: default_cpu_threshold(),
             memory_threshold: default_memory_threshold(),
+            duration_secs: default_alert_duration(),
+            cooldown_secs: default_alert_cooldown(),
+            alert_on_anomalies: false,
             sound_enabled: false,
             bookmarked_processes: Vec::new(),
+            rules: Vec::new(),
+            maintenance_windows: Vec::new(),
+            notifications: NotificationConfig::default(),
         }
     }
 }
@@ -315,6 +486,162 @@ impl Config {
         }
     }
     
+    /// Render the configuration as TOML, as it would be written to disk
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize config")
+    }
+    
+    /// Serialize like [`to_toml_string`](Self::to_toml_string), with tokens,
+    /// password hashes and other credentials replaced by `"<redacted>"`
+    pub fn to_redacted_toml_string(&self) -> Result<String> {
+        let mut value = toml::Value::try_from(self).context("Failed to serialize config")?;
+        redact_secrets("", &mut value);
+        toml::to_string_pretty(&value).context("Failed to serialize config")
+    }
+    
+    /// Check the configuration for values that cannot be used.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` if the configuration is usable
+    /// * `Err` with one message per invalid setting, prefixed with its key
+    pub fn validate(&self) -> std::result::Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        
+        if self.general.refresh_interval == 0 {
+            errors.push("general.refresh_interval: must be at least 1 second".to_string());
+        }
+        if crate::process::SortColumn::from_name(&self.general.default_sort_column).is_none() {
+            errors.push(format!(
+                "general.default_sort_column: unknown column '{}'",
+                self.general.default_sort_column
+            ));
+        }
+        if self.ui.page_size == 0 {
+            errors.push("ui.page_size: must be greater than 0".to_string());
+        }
+        if self.api.port == 0 {
+            errors.push("api.port: must be between 1 and 65535".to_string());
+        }
+        if self.api.bind_address.parse::<IpAddr>().is_err() {
+            errors.push(format!(
+                "api.bind_address: '{}' is not a valid IP address",
+                self.api.bind_address
+            ));
+        }
+        if self.api.auth.enabled && self.api.auth.tokens.is_empty() && self.api.auth.users.is_empty() {
+            errors.push("api.auth: enabled but no tokens or users are configured".to_string());
+        }
+        for (i, token) in self.api.auth.tokens.iter().enumerate() {
+            if token.token.len() < 16 {
+                errors.push(format!("api.auth.tokens[{}].token: must be at least 16 characters", i));
+            }
+        }
+        for (i, user) in self.api.auth.users.iter().enumerate() {
+            if user.username.is_empty() || user.username.contains(':') {
+                errors.push(format!("api.auth.users[{}].username: must be non-empty and must not contain ':'", i));
+            }
+            let is_argon2 = argon2::PasswordHash::new(&user.password_hash)
+                .is_ok_and(|hash| hash.algorithm.as_str().starts_with("argon2"));
+            if !is_argon2 {
+                errors.push(format!(
+                    "api.auth.users[{}].password_hash: must be an Argon2 PHC string ($argon2id$...), see --hash-password",
+                    i
+                ));
+            }
+        }
+        let tls = &self.api.tls;
+        if tls.enabled && !tls.self_signed && (tls.cert_path.is_none() || tls.key_path.is_none()) {
+            errors.push("api.tls: cert_path and key_path are required unless self_signed = true".to_string());
+        }
+        if tls.cert_path.is_some() != tls.key_path.is_some() {
+            errors.push("api.tls: cert_path and key_path must be set together".to_string());
+        }
+        if tls.client_ca_path.is_some() && !tls.enabled {
+            errors.push("api.tls.client_ca_path: requires api.tls.enabled = true".to_string());
+        }
+        if self.history.database_path.trim().is_empty() {
+            errors.push("history.database_path: must not be empty".to_string());
+        }
+        if self.history.retention_days < 1 {
+            errors.push("history.retention_days: must be at least 1 day".to_string());
+        }
+        if self.history.recording_interval == 0 {
+            errors.push("history.recording_interval: must be at least 1 second".to_string());
+        }
+        if self.alerts.cpu_threshold.is_nan() || self.alerts.cpu_threshold <= 0.0 {
+            errors.push(format!(
+                "alerts.cpu_threshold: {} must be greater than 0",
+                self.alerts.cpu_threshold
+            ));
+        }
+        let memory_threshold = self.alerts.memory_threshold;
+        if memory_threshold.is_nan() || memory_threshold <= 0.0 || memory_threshold > 100.0 {
+            errors.push(format!(
+                "alerts.memory_threshold: {} must be between 0 and 100",
+                self.alerts.memory_threshold
+            ));
+        }
+        for (i, bookmark) in self.alerts.bookmarked_processes.iter().enumerate() {
+            if bookmark.name.trim().is_empty() {
+                errors.push(format!("alerts.bookmarked_processes[{}].name: must not be empty", i));
+            }
+            if let Some(ref pattern) = bookmark.command_pattern {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    errors.push(format!("alerts.bookmarked_processes[{}].command_pattern: {}", i, e));
+                }
+            }
+        }
+        for (i, rule) in self.alerts.rules.iter().enumerate() {
+            if rule.name.trim().is_empty() {
+                errors.push(format!("alerts.rules[{}].name: must not be empty", i));
+            } else if self.alerts.rules[..i].iter().any(|other| other.name == rule.name) {
+                errors.push(format!("alerts.rules[{}].name: duplicate rule name {:?}", i, rule.name));
+            }
+            if let Err(e) = Expr::parse(&rule.expr) {
+                errors.push(format!("alerts.rules[{}].expr: {}", i, e));
+            }
+            for (key, value) in [("for", &rule.for_duration), ("cooldown", &rule.cooldown)] {
+                if let Some(Err(e)) = value.as_deref().map(parse_duration) {
+                    errors.push(format!("alerts.rules[{}].{}: {}", i, key, e));
+                }
+            }
+        }
+        for (i, window) in self.alerts.maintenance_windows.iter().enumerate() {
+            if let Err(e) = window.validate() {
+                errors.push(format!("alerts.maintenance_windows[{}]: {}", i, e));
+            }
+        }
+        if let Some(ref email) = self.alerts.notifications.email {
+            if email.enabled && email.to.is_empty() {
+                errors.push("alerts.notifications.email.to: must list at least one recipient".to_string());
+            }
+        }
+        if let Some(ref webhook) = self.alerts.notifications.webhook {
+            if webhook.enabled && !(webhook.url.starts_with("http://") || webhook.url.starts_with("https://")) {
+                errors.push(format!("alerts.notifications.webhook.url: {:?} must be an http(s) URL", webhook.url));
+            }
+        }
+        
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+    
+    /// List the settings that differ between `self` and `other`.
+    /// 
+    /// Keys are dotted paths such as `history.recording_interval`.
+    pub fn diff(&self, other: &Config) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+        match (toml::Value::try_from(self), toml::Value::try_from(other)) {
+            (Ok(old), Ok(new)) => diff_values("", Some(&old), Some(&new), &mut changes),
+            _ => warn!("Failed to serialize configuration for comparison"),
+        }
+        changes
+    }
+    
     /// Create example configuration file
     pub fn create_example_config(path: &PathBuf) -> Result<()> {
         let config = Config::default();
@@ -326,6 +653,176 @@ impl Config {
     }
 }
 
+/// A single setting that differs between two configurations
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// Dotted key path, e.g. `alerts.cpu_threshold`
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl ConfigChange {
+    /// Whether the change only takes effect after the API server restarts
+    /// (listening socket, TLS, CORS policy, history database and alert
+    /// notification channels).
+    pub fn requires_restart(&self) -> bool {
+        const RESTART_KEYS: &[&str] = &[
+            "api.port",
+            "api.bind_address",
+            "api.enable_cors",
+            "history.enabled",
+            "history.database_path",
+        ];
+        RESTART_KEYS.contains(&self.key.as_str())
+            || self.key.starts_with("api.tls.")
+            || self.key.starts_with("alerts.notifications.")
+    }
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.key,
+            self.old.as_deref().unwrap_or("<unset>"),
+            self.new.as_deref().unwrap_or("<unset>")
+        )
+    }
+}
+
+/// Settings that hold credentials, by dotted key. Entries of an array of
+/// tables share the array's key, so `api.auth.tokens.token` is every token.
+const SECRET_KEYS: &[&str] = &[
+    "api.auth.tokens.token",
+    "api.auth.users.password_hash",
+    "alerts.notifications.email.password",
+    "alerts.notifications.webhook.headers",
+];
+
+/// Whether the value at `key` is a credential or contains one
+fn is_secret_key(key: &str) -> bool {
+    SECRET_KEYS.iter().any(|secret| {
+        key == *secret
+            || key.starts_with(&format!("{}.", secret))
+            || secret.starts_with(&format!("{}.", key))
+    })
+}
+
+/// Replace every credential below `value`, which sits at `key`, with `"<redacted>"`
+fn redact_secrets(key: &str, value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            for (k, v) in table.iter_mut() {
+                let child_key = if key.is_empty() { k.clone() } else { format!("{}.{}", key, k) };
+                redact_secrets(&child_key, v);
+            }
+        }
+        toml::Value::Array(items) => {
+            for item in items {
+                redact_secrets(key, item);
+            }
+        }
+        leaf => {
+            if is_secret_key(key) {
+                *leaf = toml::Value::String("<redacted>".to_string());
+            }
+        }
+    }
+}
+
+fn diff_values(
+    key: &str,
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    if let (Some(toml::Value::Table(old_table)), Some(toml::Value::Table(new_table))) = (old, new) {
+        let mut keys: Vec<&String> = old_table.keys().chain(new_table.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for k in keys {
+            let child_key = if key.is_empty() { k.clone() } else { format!("{}.{}", key, k) };
+            diff_values(&child_key, old_table.get(k), new_table.get(k), changes);
+        }
+    } else if old != new {
+        // Never write credentials to the log
+        let secret = is_secret_key(key);
+        let render = |v: &toml::Value| if secret { "<redacted>".to_string() } else { v.to_string() };
+        changes.push(ConfigChange {
+            key: key.to_string(),
+            old: old.map(render),
+            new: new.map(render),
+        });
+    }
+}
+
+/// Settings given on the command line, which take precedence over the file
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub refresh_interval: Option<u64>,
+    pub user_filter: Option<String>,
+    pub start_in_tree_view: bool,
+    pub api_port: Option<u16>,
+    pub bind_address: Option<String>,
+    pub database_path: Option<String>,
+    pub view_profile: Option<String>,
+}
+
+impl ConfigOverrides {
+    /// Apply the overrides on top of `config`
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(refresh) = self.refresh_interval {
+            config.general.refresh_interval = refresh;
+        }
+        if let Some(ref user) = self.user_filter {
+            config.general.user_filter = Some(user.clone());
+        }
+        if self.start_in_tree_view {
+            config.ui.start_in_tree_view = true;
+        }
+        if let Some(port) = self.api_port {
+            config.api.port = port;
+        }
+        if let Some(ref bind_address) = self.bind_address {
+            config.api.bind_address = bind_address.clone();
+        }
+        if let Some(ref database_path) = self.database_path {
+            config.history.database_path = database_path.clone();
+        }
+        if let Some(ref view_profile) = self.view_profile {
+            config.ui.view_profile = Some(view_profile.clone());
+        }
+    }
+}
+
+/// Where a running process reads its configuration from, so it can be
+/// loaded again later with the same command-line overrides.
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub path: PathBuf,
+    pub overrides: ConfigOverrides,
+}
+
+impl ConfigSource {
+    pub fn new(path: PathBuf, overrides: ConfigOverrides) -> Self {
+        Self { path, overrides }
+    }
+    
+    /// Read the file and apply the command-line overrides (not validated)
+    pub fn load(&self) -> Result<Config> {
+        let mut config = Config::load_from_file(&self.path)?;
+        self.overrides.apply(&mut config);
+        Ok(config)
+    }
+    
+    /// Last modification time of the file, if it can be read
+    pub fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).and_then(|m| m.modified()).ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,11 +864,232 @@ mod tests {
         assert_eq!(config.api.port, 9090);
     }
     
+    #[test]
+    fn test_validate_reports_each_invalid_key() {
+        assert!(Config::default().validate().is_ok());
+        
+        let mut config = Config::default();
+        config.general.default_sort_column = "bogus".to_string();
+        config.history.recording_interval = 0;
+        config.alerts.memory_threshold = 150.0;
+        config.api.bind_address = "localhost:80".to_string();
+        
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("general.default_sort_column"));
+        assert!(errors.iter().any(|e| e.starts_with("history.recording_interval")));
+        assert!(errors.iter().any(|e| e.starts_with("alerts.memory_threshold")));
+        assert!(errors.iter().any(|e| e.starts_with("api.bind_address")));
+    }
+    
+    #[test]
+    fn test_diff_lists_changed_keys() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.alerts.cpu_threshold = 90.0;
+        new.history.recording_interval = 30;
+        new.api.port = 9090;
+        
+        let changes = old.diff(&new);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, vec!["alerts.cpu_threshold", "api.port", "history.recording_interval"]);
+        assert_eq!(changes[2].to_string(), "history.recording_interval: 60 -> 30");
+        assert!(!changes[0].requires_restart());
+        assert!(changes[1].requires_restart());
+        assert!(old.diff(&old).is_empty());
+        
+        let mut tls = old.clone();
+        tls.api.tls.self_signed = true;
+        assert!(old.diff(&tls)[0].requires_restart());
+    }
+    
+    #[test]
+    fn test_auth_config_validation_and_redaction() {
+        let toml_str = r#"
+            [api.auth]
+            enabled = true
+            
+            [[api.auth.tokens]]
+            name = "grafana"
+            token = "0123456789abcdef0123"
+            role = "read_only"
+            
+            [[api.auth.users]]
+            username = "admin"
+            password_hash = "$argon2id$v=19$m=19456,t=2,p=1$UM5fxoCNznk+jz82Ba8ZGg$nSnTvcM2ykMVVmmCHzIORgW6Gcz+Uw3EPpTShs9y2s0"
+            role = "operator"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.api.auth.tokens[0].role, ApiRole::ReadOnly);
+        assert_eq!(config.api.auth.users[0].role, ApiRole::Operator);
+        assert!(ApiRole::Operator > ApiRole::ReadOnly);
+        
+        let mut changed = config.clone();
+        changed.api.auth.tokens[0].token = "fedcba9876543210fedc".to_string();
+        let changes = config.diff(&changed);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_string(), "api.auth.tokens: <redacted> -> <redacted>");
+        
+        let printed = config.to_redacted_toml_string().unwrap();
+        assert!(!printed.contains("0123456789abcdef0123"));
+        assert!(!printed.contains("$argon2id$"));
+        assert!(printed.contains("name = \"grafana\""));
+        assert!(printed.contains("username = \"admin\""));
+        
+        let mut invalid = config.clone();
+        invalid.api.auth.tokens[0].token = "short".to_string();
+        // An unsalted SHA-256 hash from an old config is rejected
+        invalid.api.auth.users[0].password_hash = "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b".to_string();
+        assert_eq!(invalid.validate().unwrap_err().len(), 2);
+        
+        let mut empty = Config::default();
+        empty.api.auth.enabled = true;
+        assert!(empty.validate().is_err());
+    }
+    
+    #[test]
+    fn test_tls_config_validation() {
+        let mut config = Config::default();
+        config.api.tls.enabled = true;
+        assert!(config.validate().is_err());
+        
+        config.api.tls.self_signed = true;
+        assert!(config.validate().is_ok());
+        
+        config.api.tls.cert_path = Some("/etc/lpm/cert.pem".to_string());
+        assert!(config.validate().is_err());
+        
+        config.api.tls.key_path = Some("/etc/lpm/key.pem".to_string());
+        config.api.tls.self_signed = false;
+        assert!(config.validate().is_ok());
+        
+        config.api.tls.enabled = false;
+        config.api.tls.client_ca_path = Some("/etc/lpm/ca.pem".to_string());
+        assert!(config.validate().is_err());
+    }
+    
+    #[test]
+    fn test_alert_notifications_config() {
+        let toml_str = r#"
+            [alerts]
+            enabled = true
+    
+            [alerts.notifications.email]
+            enabled = true
+            smtp_server = "smtp.example.com"
+            smtp_port = 587
+            username = "alerts@example.com"
+            password = "hunter2"
+            from = "alerts@example.com"
+            to = ["admin@example.com"]
+    
+            [alerts.notifications.webhook]
+            enabled = true
+            url = "https://hooks.example.com/alerts"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.alerts.duration_secs, 30);
+        assert!(!config.alerts.notifications.desktop);
+        assert!(config.alerts.notifications.webhook.as_ref().unwrap().headers.is_empty());
+    
+        let mut changed = config.clone();
+        changed.alerts.notifications.email.as_mut().unwrap().password = "correct horse".to_string();
+        let changes = config.diff(&changed);
+        assert_eq!(changes[0].to_string(), "alerts.notifications.email.password: <redacted> -> <redacted>");
+        assert!(changes[0].requires_restart());
+    
+        let printed = config.to_redacted_toml_string().unwrap();
+        assert!(!printed.contains("hunter2"));
+        assert!(printed.contains("password = \"<redacted>\""));
+        assert!(printed.contains("username = \"alerts@example.com\""));
+    
+        changed.alerts.notifications.webhook.as_mut().unwrap().url = "hooks.example.com".to_string();
+        changed.alerts.notifications.email.as_mut().unwrap().to.clear();
+        assert_eq!(changed.validate().unwrap_err().len(), 2);
+    }
+    
+    #[test]
+    fn test_overrides_win_over_file() {
+        let mut config: Config = toml::from_str("[api]\nport = 9090\n[general]\nrefresh_interval = 5").unwrap();
+        let overrides = ConfigOverrides {
+            api_port: Some(7070),
+            start_in_tree_view: true,
+            view_profile: Some("process_tree".to_string()),
+            ..Default::default()
+        };
+        overrides.apply(&mut config);
+        
+        assert_eq!(config.api.port, 7070);
+        assert_eq!(config.general.refresh_interval, 5);
+        assert!(config.ui.start_in_tree_view);
+        assert_eq!(config.ui.view_profile.as_deref(), Some("process_tree"));
+    }
+    
+    #[test]
+    fn test_alert_rules_config() {
+        let mut config: Config = toml::from_str(r#"
+            [[alerts.rules]]
+            name = "postgres-memory"
+            expr = 'name ~ "^postgres" and memory_percent > 20 and user != "root"'
+            for = "5m"
+            severity = "critical"
+        "#).unwrap();
+        
+        let rule = &config.alerts.rules[0];
+        assert_eq!(rule.for_duration.as_deref(), Some("5m"));
+        assert_eq!(rule.severity, AlertSeverity::Critical);
+        assert!(rule.enabled);
+        assert!(config.validate().is_ok());
+        
+        let mut duplicate = rule.clone();
+        duplicate.expr = "cpu_usage > fast".to_string();
+        duplicate.cooldown = Some("10x".to_string());
+        config.alerts.rules.push(duplicate);
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("alerts.rules[1].name: duplicate"));
+        assert!(errors[1].starts_with("alerts.rules[1].expr: at column 13"));
+        assert!(errors[2].starts_with("alerts.rules[1].cooldown:"));
+    }
+    
+    #[test]
+    fn test_maintenance_windows_config() {
+        let mut config: Config = toml::from_str(r#"
+            [[alerts.maintenance_windows]]
+            name = "nightly-backup"
+            start = "02:00"
+            end = "03:00"
+            user = "backup"
+            
+            [[alerts.maintenance_windows]]
+            name = "weekend-reindex"
+            start = "22:00"
+            end = "04:00"
+            days = ["sat", "sun"]
+            process_name = "reindex"
+            pid = 4242
+        "#).unwrap();
+        
+        let windows = &config.alerts.maintenance_windows;
+        assert_eq!(windows[0].matcher.user.as_deref(), Some("backup"));
+        assert!(windows[0].days.is_empty());
+        assert_eq!(windows[1].matcher.pid, Some(4242));
+        assert!(config.validate().is_ok());
+        
+        config.alerts.maintenance_windows[1].end = "4pm".to_string();
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec!["alerts.maintenance_windows[1]: \"4pm\" is not a time of day (HH:MM)".to_string()]);
+    }
+    
     #[test]
     fn test_bookmarked_process() {
         let bookmark = BookmarkedProcess {
             name: "nginx".to_string(),
+            command_pattern: None,
             alert_on_exit: true,
+            alert_on_start: false,
             alert_on_high_cpu: false,
             alert_on_high_memory: false,
         };
@@ -0,0 +1,194 @@
+//! # Process Details
+//!
+//! Gathers everything shown on the TUI's process detail screen for one PID:
+//! working directory, executable, environment, resource limits, memory map,
+//! scheduling, process group and container information. Threads come from
+//! [`crate::threads`], which needs successive samples for CPU usage.
+//!
+//! Each section is read independently, so a section that cannot be read
+//! (another user's environment, a kernel thread's memory map) carries its
+//! error instead of failing the whole view.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use process_manager::details::ProcessDetails;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let details = ProcessDetails::load(1234)?;
+//! println!("cwd: {:?}, exe: {:?}", details.cwd, details.exe);
+//! if let Ok(ref limits) = details.limits {
+//!     for limit in limits {
+//!         println!("{:<26} {:>12} {:>12} {}", limit.name, limit.soft, limit.hard, limit.units);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::affinity::{self, AffinityInfo};
+use crate::containers::{ContainerAnalyzer, ContainerResources, NamespaceIds};
+use crate::groups::{self, ProcessGroupInfo};
+use crate::memmap::MemoryMapVisualizer;
+use anyhow::{bail, Result};
+use std::fs;
+use std::path::Path;
+use tracing::debug;
+
+/// One row of `/proc/<pid>/limits`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceLimit {
+    /// Limit name, e.g. `Max open files`
+    pub name: String,
+    /// Soft limit, a number or `unlimited`
+    pub soft: String,
+    /// Hard limit, a number or `unlimited`
+    pub hard: String,
+    /// Units, empty for limits without one (e.g. `Max nice priority`)
+    pub units: String,
+}
+
+/// Container and namespace information for a process
+#[derive(Debug, Clone)]
+pub struct ContainerDetails {
+    pub containerized: bool,
+    pub container_id: Option<String>,
+    pub namespaces: Option<NamespaceIds>,
+    /// Usage of the process's cgroup, whether or not it is a container
+    pub resources: Option<ContainerResources>,
+}
+
+/// Detail-screen data for one process.
+///
+/// Sections that could not be read hold the error message.
+#[derive(Debug)]
+pub struct ProcessDetails {
+    pub pid: u32,
+    /// Working directory; `None` when not readable
+    pub cwd: Option<String>,
+    /// Executable path; `None` for kernel threads or when not readable
+    pub exe: Option<String>,
+    /// `KEY=value` entries of the initial environment
+    pub environment: Result<Vec<String>, String>,
+    pub limits: Result<Vec<ResourceLimit>, String>,
+    pub memory_map: Result<MemoryMapVisualizer, String>,
+    pub scheduling: Result<AffinityInfo, String>,
+    pub group: Result<ProcessGroupInfo, String>,
+    pub container: ContainerDetails,
+}
+
+impl ProcessDetails {
+    /// Read all detail sections for `pid`.
+    ///
+    /// # Returns
+    ///
+    /// * `Err` only if the process does not exist
+    pub fn load(pid: u32) -> Result<Self> {
+        let proc_dir = format!("/proc/{}", pid);
+        if !Path::new(&proc_dir).exists() {
+            bail!("Process {} not found", pid);
+        }
+        debug!("Loading process details for pid {}", pid);
+
+        let read_link = |name: &str| {
+            fs::read_link(format!("{}/{}", proc_dir, name))
+                .ok()
+                .map(|p| p.to_string_lossy().to_string())
+        };
+        let read = |name: &str| {
+            fs::read(format!("{}/{}", proc_dir, name))
+                .map_err(|e| format!("Failed to read {}/{}: {}", proc_dir, name, e))
+        };
+
+        let analyzer = ContainerAnalyzer::new();
+        let container = ContainerDetails {
+            containerized: analyzer.is_containerized(pid).unwrap_or(false),
+            container_id: analyzer.get_container_id(pid).ok().flatten(),
+            namespaces: analyzer.get_namespace_ids(pid).ok(),
+            resources: analyzer.get_container_resources(pid).ok(),
+        };
+
+        Ok(Self {
+            pid,
+            cwd: read_link("cwd"),
+            exe: read_link("exe"),
+            environment: read("environ").map(|raw| parse_environ(&raw)),
+            limits: read("limits").map(|raw| parse_limits(&String::from_utf8_lossy(&raw))),
+            memory_map: MemoryMapVisualizer::new(pid).map_err(|e| format!("{:#}", e)),
+            scheduling: affinity::get_priority_info(pid).map_err(|e| format!("{:#}", e)),
+            group: groups::get_process_group_info(pid).map_err(|e| format!("{:#}", e)),
+            container,
+        })
+    }
+}
+
+/// Split the NUL-separated contents of `/proc/<pid>/environ`
+fn parse_environ(raw: &[u8]) -> Vec<String> {
+    raw.split(|&b| b == 0)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| String::from_utf8_lossy(entry).to_string())
+        .collect()
+}
+
+/// Parse the rows of `/proc/<pid>/limits`.
+///
+/// Columns are fixed-width: name (26), soft limit (21), hard limit (21), units.
+fn parse_limits(content: &str) -> Vec<ResourceLimit> {
+    content
+        .lines()
+        .skip(1)
+        .filter(|line| line.len() > 26)
+        .filter_map(|line| {
+            let name = line.get(..26)?.trim().to_string();
+            let mut values = line.get(26..)?.split_whitespace();
+            Some(ResourceLimit {
+                name,
+                soft: values.next()?.to_string(),
+                hard: values.next()?.to_string(),
+                units: values.next().unwrap_or("").to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_limits() {
+        let limits = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                      Max cpu time              unlimited            unlimited            seconds   \n\
+                      Max open files            1024                 524288               files     \n\
+                      Max nice priority         0                    0                    \n";
+        let parsed = parse_limits(limits);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].name, "Max cpu time");
+        assert_eq!(parsed[0].soft, "unlimited");
+        assert_eq!(parsed[1].soft, "1024");
+        assert_eq!(parsed[1].hard, "524288");
+        assert_eq!(parsed[1].units, "files");
+        assert_eq!(parsed[2].name, "Max nice priority");
+        assert_eq!(parsed[2].units, "");
+    }
+
+    #[test]
+    fn test_parse_environ() {
+        assert_eq!(parse_environ(b"HOME=/root\0PATH=/bin\0\0"), vec!["HOME=/root", "PATH=/bin"]);
+        assert!(parse_environ(b"").is_empty());
+    }
+
+    #[test]
+    fn test_load_own_process() {
+        let pid = std::process::id();
+        let details = ProcessDetails::load(pid).unwrap();
+        assert!(details.cwd.is_some());
+        assert!(details.exe.is_some());
+        assert!(details.environment.unwrap().iter().any(|e| e.contains('=')));
+        assert!(details.limits.unwrap().iter().any(|l| l.name == "Max open files"));
+        assert!(details.memory_map.is_ok());
+        assert!(details.group.is_ok());
+
+        assert!(ProcessDetails::load(u32::MAX).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! # Open File Descriptor Inspector
+//!
+//! Lists what a process has open, from `/proc/<pid>/fd` and `/proc/<pid>/fdinfo`,
+//! like `lsof -p` or `ls -l /proc/<pid>/fd`.
+//!
+//! ## Features
+//!
+//! - **Classification**: Regular files, directories, pipes, sockets, eventfds,
+//!   other anonymous inodes (epoll, timerfd, inotify, ...) and devices
+//! - **Open State**: Path, open flags and current file position per fd
+//! - **Limits**: Open fd count against the soft `RLIMIT_NOFILE`, to catch
+//!   descriptor leaks before `EMFILE`
+//!
+//! Reading another user's fd table needs root (or `CAP_SYS_PTRACE`).
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use process_manager::fds::{list_fds, fd_limit};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let fds = list_fds(1234)?;
+//! println!("{} of {:?} fds open", fds.len(), fd_limit(1234));
+//! for fd in &fds {
+//!     println!("{:>4} {:?} {} pos={:?}", fd.fd, fd.kind, fd.path, fd.position);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::os::unix::fs::FileTypeExt;
+use tracing::debug;
+
+/// What an open file descriptor refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FdKind {
+    File,
+    Directory,
+    Pipe,
+    Socket,
+    EventFd,
+    /// Other `anon_inode:` objects: epoll, timerfd, signalfd, inotify, ...
+    AnonInode,
+    /// Character or block device
+    Device,
+    Other,
+}
+
+impl FdKind {
+    /// Short label for table cells
+    pub fn label(&self) -> &'static str {
+        match self {
+            FdKind::File => "file",
+            FdKind::Directory => "dir",
+            FdKind::Pipe => "pipe",
+            FdKind::Socket => "socket",
+            FdKind::EventFd => "eventfd",
+            FdKind::AnonInode => "anon",
+            FdKind::Device => "device",
+            FdKind::Other => "other",
+        }
+    }
+}
+
+/// One open file descriptor of a process
+#[derive(Debug, Clone, Serialize)]
+pub struct FdEntry {
+    pub fd: u32,
+    pub kind: FdKind,
+    /// Link target of `/proc/<pid>/fd/<fd>`: a path, `pipe:[inode]`,
+    /// `socket:[inode]` or `anon_inode:[name]`
+    pub path: String,
+    /// Raw `open(2)` flags from fdinfo
+    pub flags: u32,
+    /// Names of the set flags, access mode first (e.g. `O_RDWR`, `O_CLOEXEC`)
+    pub flag_names: Vec<&'static str>,
+    /// File offset; `None` if fdinfo could not be read
+    pub position: Option<u64>,
+}
+
+/// List the open file descriptors of a process, ordered by fd number.
+///
+/// Descriptors closed while the table is being read are skipped.
+///
+/// # Returns
+///
+/// * `Err` if the fd table cannot be read (no such process, or permission denied)
+pub fn list_fds(pid: u32) -> Result<Vec<FdEntry>> {
+    debug!("Listing open fds for pid {}", pid);
+    let entries = fs::read_dir(format!("/proc/{}/fd", pid))
+        .with_context(|| format!("Failed to read /proc/{}/fd", pid))?;
+
+    let mut fds: Vec<FdEntry> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let fd = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let path = fs::read_link(entry.path()).ok()?.to_string_lossy().to_string();
+            let kind = classify(&path, || fs::metadata(entry.path()).ok().map(|m| m.file_type()));
+            let (flags, position) = read_fdinfo(pid, fd);
+            Some(FdEntry {
+                fd,
+                kind,
+                path,
+                flags: flags.unwrap_or(0),
+                flag_names: flags.map(describe_flags).unwrap_or_default(),
+                position,
+            })
+        })
+        .collect();
+    fds.sort_by_key(|f| f.fd);
+    Ok(fds)
+}
+
+/// Number of open file descriptors, if the fd table is readable
+pub fn count_fds(pid: u32) -> Option<usize> {
+    fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|entries| entries.count())
+}
+
+/// Soft `RLIMIT_NOFILE` of a process, from `/proc/<pid>/limits`.
+///
+/// `None` if the file cannot be read or the limit is `unlimited`.
+pub fn fd_limit(pid: u32) -> Option<u64> {
+    let content = fs::read_to_string(format!("/proc/{}/limits", pid)).ok()?;
+    parse_fd_limit(&content)
+}
+
+/// Find the soft limit in the `Max open files` row of a limits file
+fn parse_fd_limit(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|soft| soft.parse().ok())
+}
+
+/// Classify an fd from its link target, looking at the file type only for paths
+fn classify(target: &str, file_type: impl FnOnce() -> Option<fs::FileType>) -> FdKind {
+    if target.starts_with("socket:[") {
+        return FdKind::Socket;
+    }
+    if target.starts_with("pipe:[") {
+        return FdKind::Pipe;
+    }
+    if let Some(name) = target.strip_prefix("anon_inode:") {
+        return if name.trim_matches(|c| c == '[' || c == ']') == "eventfd" {
+            FdKind::EventFd
+        } else {
+            FdKind::AnonInode
+        };
+    }
+    if !target.starts_with('/') {
+        return FdKind::Other;
+    }
+
+    match file_type() {
+        Some(t) if t.is_dir() => FdKind::Directory,
+        Some(t) if t.is_char_device() || t.is_block_device() => FdKind::Device,
+        Some(t) if t.is_fifo() => FdKind::Pipe,
+        Some(t) if t.is_socket() => FdKind::Socket,
+        _ => FdKind::File,
+    }
+}
+
+/// Open flags and file position from `/proc/<pid>/fdinfo/<fd>`
+fn read_fdinfo(pid: u32, fd: u32) -> (Option<u32>, Option<u64>) {
+    match fs::read_to_string(format!("/proc/{}/fdinfo/{}", pid, fd)) {
+        Ok(content) => parse_fdinfo(&content),
+        Err(_) => (None, None),
+    }
+}
+
+/// Parse `pos:` (decimal) and `flags:` (octal) from fdinfo content
+fn parse_fdinfo(content: &str) -> (Option<u32>, Option<u64>) {
+    let mut flags = None;
+    let mut position = None;
+    for line in content.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            match key {
+                "pos" => position = value.trim().parse().ok(),
+                "flags" => flags = u32::from_str_radix(value.trim(), 8).ok(),
+                _ => {}
+            }
+        }
+    }
+    (flags, position)
+}
+
+/// Names of the `open(2)` flags set in `flags`, access mode first
+pub fn describe_flags(flags: u32) -> Vec<&'static str> {
+    const NAMED: [(libc::c_int, &str); 10] = [
+        (libc::O_CREAT, "O_CREAT"),
+        (libc::O_EXCL, "O_EXCL"),
+        (libc::O_NOCTTY, "O_NOCTTY"),
+        (libc::O_TRUNC, "O_TRUNC"),
+        (libc::O_APPEND, "O_APPEND"),
+        (libc::O_NONBLOCK, "O_NONBLOCK"),
+        (libc::O_SYNC, "O_SYNC"),
+        (libc::O_DIRECT, "O_DIRECT"),
+        (libc::O_NOATIME, "O_NOATIME"),
+        (libc::O_CLOEXEC, "O_CLOEXEC"),
+    ];
+
+    let flags = flags as libc::c_int;
+    let mut names = vec![match flags & libc::O_ACCMODE {
+        libc::O_WRONLY => "O_WRONLY",
+        libc::O_RDWR => "O_RDWR",
+        _ => "O_RDONLY",
+    }];
+    // O_SYNC includes the O_DSYNC bit, so test for all of a flag's bits
+    names.extend(NAMED.iter().filter(|(bit, _)| flags & bit == *bit).map(|(_, name)| *name));
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_special_targets() {
+        let no_stat = || None;
+        assert_eq!(classify("socket:[1234]", no_stat), FdKind::Socket);
+        assert_eq!(classify("pipe:[99]", no_stat), FdKind::Pipe);
+        assert_eq!(classify("anon_inode:[eventfd]", no_stat), FdKind::EventFd);
+        assert_eq!(classify("anon_inode:[eventpoll]", no_stat), FdKind::AnonInode);
+        assert_eq!(classify("anon_inode:inotify", no_stat), FdKind::AnonInode);
+        assert_eq!(classify("/var/log/app.log (deleted)", no_stat), FdKind::File);
+        assert_eq!(classify("net:[4026531840]", no_stat), FdKind::Other);
+    }
+
+    #[test]
+    fn test_parse_fdinfo_and_flags() {
+        let (flags, position) = parse_fdinfo("pos:\t4096\nflags:\t02102002\nmnt_id:\t25\nino:\t1234\n");
+        assert_eq!(position, Some(4096));
+        let names = describe_flags(flags.unwrap());
+        assert_eq!(names[0], "O_RDWR");
+        assert!(names.contains(&"O_APPEND"));
+        assert!(names.contains(&"O_CLOEXEC"));
+        assert!(!names.contains(&"O_SYNC"));
+    }
+
+    #[test]
+    fn test_parse_fd_limit() {
+        let limits = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                      Max processes             63328                63328                processes \n\
+                      Max open files            1024                 524288               files     \n";
+        assert_eq!(parse_fd_limit(limits), Some(1024));
+        assert_eq!(parse_fd_limit("Max open files            unlimited            unlimited            files"), None);
+    }
+
+    #[test]
+    fn test_list_own_fds() {
+        use std::io::{Seek, SeekFrom, Write};
+        use std::os::fd::AsRawFd;
+
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"hello").unwrap();
+        file.seek(SeekFrom::Start(3)).unwrap();
+        let dir = fs::File::open("/").unwrap();
+
+        let pid = std::process::id();
+        let fds = list_fds(pid).unwrap();
+        let entry = fds.iter().find(|f| f.fd == file.as_raw_fd() as u32).unwrap();
+        assert_eq!(entry.kind, FdKind::File);
+        assert_eq!(entry.position, Some(3));
+        assert_eq!(entry.flag_names[0], "O_RDWR");
+        let dir_entry = fds.iter().find(|f| f.fd == dir.as_raw_fd() as u32).unwrap();
+        assert_eq!(dir_entry.kind, FdKind::Directory);
+        assert_eq!(dir_entry.path, "/");
+
+        assert!(count_fds(pid).unwrap() >= 2);
+        assert!(fd_limit(pid).is_some());
+    }
+}
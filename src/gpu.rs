@@ -102,6 +102,15 @@ fn get_nvidia_gpu_info() -> Result<SystemGpuInfo> {
 
 /// Get per-process GPU stats for NVIDIA
 pub fn get_nvidia_process_stats(pid: u32) -> Result<GpuStats> {
+    get_nvidia_process_list()?
+        .into_iter()
+        .find(|stats| stats.pid == pid)
+        .ok_or_else(|| anyhow::anyhow!("Process not using GPU"))
+}
+
+/// Get GPU stats for every process using an NVIDIA GPU with a single
+/// `nvidia-smi` call
+pub fn get_nvidia_process_list() -> Result<Vec<GpuStats>> {
     let output = Command::new("nvidia-smi")
         .args(&[
             "--query-compute-apps=pid,used_memory,gpu_name,gpu_bus_id",
@@ -114,26 +123,27 @@ pub fn get_nvidia_process_stats(pid: u32) -> Result<GpuStats> {
         return Err(anyhow::anyhow!("nvidia-smi failed"));
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    
-    for line in output_str.lines() {
-        let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-        if parts.len() >= 3 {
-            if let Ok(process_pid) = parts[0].parse::<u32>() {
-                if process_pid == pid {
-                    return Ok(GpuStats {
-                        pid,
-                        gpu_memory_used: parts[1].parse().unwrap_or(0),
-                        gpu_utilization: 0.0, // Not available per-process
-                        gpu_device: parts[2].to_string(),
-                        gpu_index: 0,
-                    });
-                }
-            }
-        }
-    }
+    Ok(parse_compute_apps(&String::from_utf8_lossy(&output.stdout)))
+}
 
-    Err(anyhow::anyhow!("Process not using GPU"))
+/// Parse `nvidia-smi --query-compute-apps` CSV output
+fn parse_compute_apps(output: &str) -> Vec<GpuStats> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+            if parts.len() < 3 {
+                return None;
+            }
+            Some(GpuStats {
+                pid: parts[0].parse().ok()?,
+                gpu_memory_used: parts[1].parse().unwrap_or(0),
+                gpu_utilization: 0.0, // Not available per-process
+                gpu_device: parts[2].to_string(),
+                gpu_index: 0,
+            })
+        })
+        .collect()
 }
 
 /// Get AMD GPU information
@@ -253,6 +263,16 @@ mod tests {
         assert_eq!(format_gpu_memory(8192), "8.0 GB");
     }
 
+    #[test]
+    fn test_parse_compute_apps() {
+        let output = "1234, 512, NVIDIA A100, 00000000:07:00.0\n5678, 2048, NVIDIA A100, 00000000:07:00.0\n";
+        let stats = parse_compute_apps(output);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[1].pid, 5678);
+        assert_eq!(stats[1].gpu_memory_used, 2048);
+        assert!(parse_compute_apps("No running processes found").is_empty());
+    }
+
     #[test]
     fn test_gpu_detection() {
         // This test will vary by system
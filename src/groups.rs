@@ -35,9 +35,9 @@
 //! # }
 //! ```
 
-use anyhow::{Context, Result};
-use std::fs;
+use anyhow::Result;
 use std::collections::HashMap;
+use crate::procfs::ProcStat;
 use tracing::{debug, info, error};
 
 /// Complete process group and session information.
@@ -58,58 +58,21 @@ pub struct ProcessGroupInfo {
 /// Get process group and session information
 pub fn get_process_group_info(pid: u32) -> Result<ProcessGroupInfo> {
     debug!("Getting process group info for pid {}", pid);
-    let stat_path = format!("/proc/{}/stat", pid);
-    let stat_content = fs::read_to_string(&stat_path)
-        .context("Failed to read /proc/{pid}/stat")?;
-    
-    // Parse stat file
-    // Format: pid (comm) state ppid pgrp session tty_nr tpgid ...
-    let parts = parse_stat_line(&stat_content)?;
-    
-    let pid = parts[0].parse::<u32>()?;
-    let ppid = parts[3].parse::<u32>()?;
-    let pgid = parts[4].parse::<u32>()?;
-    let sid = parts[5].parse::<u32>()?;
-    let tty_nr = parts[6].parse::<i32>()?;
-    let tpgid = parts[7].parse::<i32>()?;
-    
-    Ok(ProcessGroupInfo {
-        pid,
-        ppid,
-        pgid,
-        sid,
-        tty_nr,
-        tpgid,
-        is_session_leader: pid == sid,
-        is_group_leader: pid == pgid,
-    })
+    Ok(group_info(&ProcStat::read(pid)?))
 }
 
-/// Parse /proc/[pid]/stat line
-fn parse_stat_line(line: &str) -> Result<Vec<String>> {
-    // Handle the (comm) field which may contain spaces and parentheses
-    let start_paren = line.find('(')
-        .context("Invalid stat format")?;
-    let end_paren = line.rfind(')')
-        .context("Invalid stat format")?;
-    
-    let mut parts = Vec::new();
-    
-    // Add PID (before first parenthesis)
-    parts.push(line[..start_paren].trim().to_string());
-    
-    // Add comm (between parentheses)
-    parts.push(line[start_paren+1..end_paren].to_string());
-    
-    // Add remaining fields
-    let remaining = &line[end_paren+1..];
-    parts.extend(
-        remaining
-            .split_whitespace()
-            .map(|s| s.to_string())
-    );
-    
-    Ok(parts)
+/// Group and session membership from a parsed stat line
+fn group_info(stat: &ProcStat) -> ProcessGroupInfo {
+    ProcessGroupInfo {
+        pid: stat.pid,
+        ppid: stat.ppid,
+        pgid: stat.pgrp,
+        sid: stat.session,
+        tty_nr: stat.tty_nr,
+        tpgid: stat.tpgid,
+        is_session_leader: stat.pid == stat.session,
+        is_group_leader: stat.pid == stat.pgrp,
+    }
 }
 
 /// Get all processes in a process group
@@ -222,13 +185,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_stat_line() {
-        let line = "1234 (bash) S 1000 1234 1234 34816 5678 4194560";
-        let parts = parse_stat_line(line).unwrap();
+    fn test_group_info_from_stat() {
+        let stat = ProcStat::parse(include_str!("../tests/fixtures/proc/stat_parenthesized")).unwrap();
+        let info = group_info(&stat);
         
-        assert_eq!(parts[0], "1234");
-        assert_eq!(parts[1], "bash");
-        assert_eq!(parts[2], "S");
+        assert_eq!(info.pid, 9921);
+        assert_eq!(info.ppid, 1);
+        assert_eq!(info.pgid, 9921);
+        assert_eq!(info.tty_nr, 34816);
+        assert!(info.is_session_leader);
+        assert!(info.is_group_leader);
     }
 
     #[test]
@@ -3,7 +3,18 @@ use chrono::{DateTime, Utc};
 use rusqlite::{Connection, params};
 use tracing::{debug, info};
 
+use crate::alerts::{Alert, AlertEvent, AlertEventKind};
 use crate::process::ProcessInfo;
+use crate::silences::{AlertMatcher, MaintenanceWindow, Silence};
+
+/// Disk I/O rate columns of `process_history`, added after the original schema
+const DISK_IO_COLUMNS: [&str; 5] = [
+    "disk_read_rate",
+    "disk_write_rate",
+    "read_syscalls_rate",
+    "write_syscalls_rate",
+    "cancelled_write_rate",
+];
 
 /// Historical data manager for storing process statistics
 pub struct HistoryManager {
@@ -13,6 +24,13 @@ pub struct HistoryManager {
 impl HistoryManager {
     /// Create a new history manager with SQLite backend
     pub fn new(db_path: &str) -> Result<Self> {
+        if let Some(parent) = std::path::Path::new(db_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .context("Failed to create history database directory")?;
+            }
+        }
+        
         let conn = Connection::open(db_path)
             .context("Failed to open history database")?;
         
@@ -34,11 +52,30 @@ impl HistoryManager {
                 cpu_usage REAL NOT NULL,
                 memory_usage INTEGER NOT NULL,
                 memory_percent REAL NOT NULL,
-                command TEXT
+                command TEXT,
+                disk_read_rate REAL,
+                disk_write_rate REAL,
+                read_syscalls_rate REAL,
+                write_syscalls_rate REAL,
+                cancelled_write_rate REAL
             )",
             [],
         )?;
 
+        // Databases created before disk I/O was recorded lack these columns
+        let existing: Vec<String> = self.conn
+            .prepare("SELECT name FROM pragma_table_info('process_history')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for column in DISK_IO_COLUMNS {
+            if !existing.iter().any(|c| c == column) {
+                self.conn.execute(
+                    &format!("ALTER TABLE process_history ADD COLUMN {} REAL", column),
+                    [],
+                )?;
+            }
+        }
+
         self.conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_timestamp ON process_history(timestamp)",
             [],
@@ -71,6 +108,122 @@ impl HistoryManager {
             [],
         )?;
 
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                initiator TEXT NOT NULL,
+                origin TEXT NOT NULL,
+                remote_addr TEXT,
+                pid INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                command TEXT NOT NULL,
+                action TEXT NOT NULL,
+                details TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                error TEXT
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_timestamp ON audit_log(timestamp)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_pid ON audit_log(pid)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_silences (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                rule TEXT,
+                pid INTEGER,
+                process_name TEXT,
+                user TEXT,
+                starts_at INTEGER NOT NULL,
+                ends_at INTEGER NOT NULL,
+                created_by TEXT NOT NULL,
+                comment TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS maintenance_windows (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                start TEXT NOT NULL,
+                end TEXT NOT NULL,
+                days TEXT NOT NULL,
+                rule TEXT,
+                pid INTEGER,
+                process_name TEXT,
+                user TEXT
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                event TEXT NOT NULL,
+                alert_id INTEGER NOT NULL,
+                rule TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                pid INTEGER NOT NULL,
+                process_name TEXT NOT NULL,
+                message TEXT NOT NULL,
+                alert TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_alert_history_timestamp ON alert_history(timestamp)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_alert_history_alert_id ON alert_history(alert_id)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS notification_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                alert_id INTEGER NOT NULL,
+                channel TEXT NOT NULL,
+                attempt INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                error TEXT,
+                latency_ms INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_notification_log_alert_id ON notification_log(alert_id)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS notification_queue (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel TEXT NOT NULL,
+                alert TEXT NOT NULL,
+                attempts INTEGER NOT NULL,
+                next_attempt INTEGER NOT NULL,
+                last_error TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -82,8 +235,9 @@ impl HistoryManager {
         
         let mut stmt = self.conn.prepare(
             "INSERT INTO process_history 
-             (timestamp, pid, name, user_name, cpu_usage, memory_usage, memory_percent, command)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+             (timestamp, pid, name, user_name, cpu_usage, memory_usage, memory_percent, command,
+              disk_read_rate, disk_write_rate, read_syscalls_rate, write_syscalls_rate, cancelled_write_rate)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )?;
 
         let mut inserted = 0;
@@ -99,6 +253,11 @@ impl HistoryManager {
                 process.memory_usage,
                 process.memory_percent,
                 process.command,
+                process.disk_io.map(|io| io.read_bytes),
+                process.disk_io.map(|io| io.write_bytes),
+                process.disk_io.map(|io| io.read_syscalls),
+                process.disk_io.map(|io| io.write_syscalls),
+                process.disk_io.map(|io| io.cancelled_write_bytes),
             ]) {
                 Ok(_) => inserted += 1,
                 Err(e) => {
@@ -162,7 +321,8 @@ impl HistoryManager {
         end_time: DateTime<Utc>,
     ) -> Result<Vec<HistoricalProcessData>> {
         let mut stmt = self.conn.prepare(
-            "SELECT timestamp, pid, name, user_name, cpu_usage, memory_usage, memory_percent, command
+            "SELECT timestamp, pid, name, user_name, cpu_usage, memory_usage, memory_percent, command,
+                    disk_read_rate, disk_write_rate, read_syscalls_rate, write_syscalls_rate, cancelled_write_rate
              FROM process_history
              WHERE pid = ? AND timestamp BETWEEN ? AND ?
              ORDER BY timestamp ASC"
@@ -180,6 +340,11 @@ impl HistoryManager {
                     memory_usage: row.get(5)?,
                     memory_percent: row.get(6)?,
                     command: row.get(7)?,
+                    disk_read_rate: row.get(8)?,
+                    disk_write_rate: row.get(9)?,
+                    read_syscalls_rate: row.get(10)?,
+                    write_syscalls_rate: row.get(11)?,
+                    cancelled_write_rate: row.get(12)?,
                 })
             },
         )?;
@@ -261,7 +426,408 @@ impl HistoryManager {
         Ok(results)
     }
 
-    /// Clean old data (older than specified days)
+    /// Record a process-control action in the audit log
+    pub fn record_audit(&self, record: &AuditRecord) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO audit_log
+             (timestamp, initiator, origin, remote_addr, pid, name, command,
+              action, details, success, error)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                record.timestamp,
+                record.initiator,
+                record.origin,
+                record.remote_addr,
+                record.pid,
+                record.name,
+                record.command,
+                record.action,
+                record.details,
+                record.success,
+                record.error,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get audit log entries in a time range, newest first
+    pub fn get_audit_log(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        pid: Option<u32>,
+        limit: usize,
+    ) -> Result<Vec<AuditRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, initiator, origin, remote_addr, pid, name, command,
+                    action, details, success, error
+             FROM audit_log
+             WHERE timestamp BETWEEN ?1 AND ?2 AND (?3 IS NULL OR pid = ?3)
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?4"
+        )?;
+
+        let rows = stmt.query_map(
+            params![start_time.timestamp(), end_time.timestamp(), pid, limit],
+            |row| {
+                Ok(AuditRecord {
+                    timestamp: row.get(0)?,
+                    initiator: row.get(1)?,
+                    origin: row.get(2)?,
+                    remote_addr: row.get(3)?,
+                    pid: row.get(4)?,
+                    name: row.get(5)?,
+                    command: row.get(6)?,
+                    action: row.get(7)?,
+                    details: row.get(8)?,
+                    success: row.get(9)?,
+                    error: row.get(10)?,
+                })
+            },
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Store an alert silence and return its ID. The `id` of `silence` is ignored.
+    pub fn add_silence(&self, silence: &Silence) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO alert_silences
+             (rule, pid, process_name, user, starts_at, ends_at, created_by, comment)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                silence.matcher.rule,
+                silence.matcher.pid,
+                silence.matcher.process_name,
+                silence.matcher.user,
+                silence.starts_at.timestamp(),
+                silence.ends_at.timestamp(),
+                silence.created_by,
+                silence.comment,
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Get the silences that end after `now`, oldest first
+    pub fn get_silences(&self, now: DateTime<Utc>) -> Result<Vec<Silence>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, rule, pid, process_name, user, starts_at, ends_at, created_by, comment
+             FROM alert_silences
+             WHERE ends_at > ?1
+             ORDER BY id"
+        )?;
+
+        let rows = stmt.query_map(params![now.timestamp()], |row| {
+            Ok(Silence {
+                id: row.get(0)?,
+                matcher: AlertMatcher {
+                    rule: row.get(1)?,
+                    pid: row.get(2)?,
+                    process_name: row.get(3)?,
+                    user: row.get(4)?,
+                },
+                starts_at: DateTime::from_timestamp(row.get(5)?, 0).unwrap_or_default(),
+                ends_at: DateTime::from_timestamp(row.get(6)?, 0).unwrap_or_default(),
+                created_by: row.get(7)?,
+                comment: row.get(8)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Delete a silence; returns whether it existed
+    pub fn delete_silence(&self, id: i64) -> Result<bool> {
+        let deleted = self.conn.execute("DELETE FROM alert_silences WHERE id = ?", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Store a maintenance window and return its ID. The `id` of `window` is ignored.
+    pub fn add_maintenance_window(&self, window: &MaintenanceWindow) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO maintenance_windows
+             (name, start, end, days, rule, pid, process_name, user)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                window.name,
+                window.start,
+                window.end,
+                window.days.join(","),
+                window.matcher.rule,
+                window.matcher.pid,
+                window.matcher.process_name,
+                window.matcher.user,
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Get the stored maintenance windows, oldest first
+    pub fn get_maintenance_windows(&self) -> Result<Vec<MaintenanceWindow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, start, end, days, rule, pid, process_name, user
+             FROM maintenance_windows
+             ORDER BY id"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let days: String = row.get(4)?;
+            Ok(MaintenanceWindow {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+                start: row.get(2)?,
+                end: row.get(3)?,
+                days: days.split(',').filter(|d| !d.is_empty()).map(str::to_string).collect(),
+                matcher: AlertMatcher {
+                    rule: row.get(5)?,
+                    pid: row.get(6)?,
+                    process_name: row.get(7)?,
+                    user: row.get(8)?,
+                },
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Delete a stored maintenance window; returns whether it existed
+    pub fn delete_maintenance_window(&self, id: i64) -> Result<bool> {
+        let deleted = self.conn.execute("DELETE FROM maintenance_windows WHERE id = ?", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Record that an alert fired, cleared or was acknowledged
+    pub fn record_alert_event(&self, event: &AlertEvent) -> Result<()> {
+        let alert = &event.alert;
+        self.conn.execute(
+            "INSERT INTO alert_history
+             (timestamp, event, alert_id, rule, severity, pid, process_name, message, alert)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                event.timestamp.timestamp(),
+                event.kind.as_str(),
+                alert.id,
+                alert.rule,
+                format!("{:?}", alert.severity),
+                alert.pid,
+                alert.process_name,
+                alert.message,
+                serde_json::to_string(alert)?,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get alert events in a time range, newest first
+    pub fn get_alert_history(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        filter: &AlertHistoryFilter,
+        limit: usize,
+    ) -> Result<Vec<AlertEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, event, alert
+             FROM alert_history
+             WHERE timestamp BETWEEN ?1 AND ?2
+               AND (?3 IS NULL OR alert_id = ?3)
+               AND (?4 IS NULL OR pid = ?4)
+               AND (?5 IS NULL OR rule = ?5)
+               AND (?6 IS NULL OR event = ?6)
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?7"
+        )?;
+
+        let rows = stmt.query_map(
+            params![
+                start_time.timestamp(),
+                end_time.timestamp(),
+                filter.alert_id,
+                filter.pid,
+                filter.rule,
+                filter.event.map(|kind| kind.as_str()),
+                limit,
+            ],
+            |row| {
+                let event: String = row.get(1)?;
+                Ok(AlertEvent {
+                    timestamp: DateTime::from_timestamp(row.get(0)?, 0).unwrap_or_default(),
+                    kind: AlertEventKind::from_name(&event).ok_or_else(|| {
+                        rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, event.into())
+                    })?,
+                    alert: alert_from_json(row, 2)?,
+                })
+            },
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Highest alert ID in the alert history, or 0 if it is empty
+    pub fn last_alert_id(&self) -> Result<u64> {
+        let id: i64 = self.conn.query_row(
+            "SELECT COALESCE(MAX(alert_id), 0) FROM alert_history",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(id as u64)
+    }
+
+    /// Record an attempt to send a notification
+    pub fn record_notification(&self, record: &NotificationRecord) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO notification_log
+             (timestamp, alert_id, channel, attempt, status, error, latency_ms)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                record.timestamp,
+                record.alert_id,
+                record.channel,
+                record.attempt,
+                record.status,
+                record.error,
+                record.latency_ms,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get notification attempts in a time range, newest first
+    pub fn get_notification_log(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        alert_id: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<NotificationRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, alert_id, channel, attempt, status, error, latency_ms
+             FROM notification_log
+             WHERE timestamp BETWEEN ?1 AND ?2 AND (?3 IS NULL OR alert_id = ?3)
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?4"
+        )?;
+
+        let rows = stmt.query_map(
+            params![start_time.timestamp(), end_time.timestamp(), alert_id, limit],
+            |row| {
+                Ok(NotificationRecord {
+                    timestamp: row.get(0)?,
+                    alert_id: row.get(1)?,
+                    channel: row.get(2)?,
+                    attempt: row.get(3)?,
+                    status: row.get(4)?,
+                    error: row.get(5)?,
+                    latency_ms: row.get(6)?,
+                })
+            },
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Add a notification to the retry queue and return its ID. The `id` of
+    /// `queued` is ignored.
+    pub fn enqueue_notification(&self, queued: &QueuedNotification) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO notification_queue
+             (channel, alert, attempts, next_attempt, last_error, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                queued.channel,
+                serde_json::to_string(&queued.alert)?,
+                queued.attempts,
+                queued.next_attempt,
+                queued.last_error,
+                queued.created_at,
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Get the queued notifications due by `due_by` (all of them if `None`),
+    /// oldest first
+    pub fn get_queued_notifications(&self, due_by: Option<DateTime<Utc>>) -> Result<Vec<QueuedNotification>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, channel, alert, attempts, next_attempt, last_error, created_at
+             FROM notification_queue
+             WHERE ?1 IS NULL OR next_attempt <= ?1
+             ORDER BY id"
+        )?;
+
+        let rows = stmt.query_map(params![due_by.map(|t| t.timestamp())], |row| {
+            Ok(QueuedNotification {
+                id: row.get(0)?,
+                channel: row.get(1)?,
+                alert: alert_from_json(row, 2)?,
+                attempts: row.get(3)?,
+                next_attempt: row.get(4)?,
+                last_error: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Store the attempt count, due time and last error of a queued notification
+    pub fn update_queued_notification(&self, queued: &QueuedNotification) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notification_queue SET attempts = ?, next_attempt = ?, last_error = ? WHERE id = ?",
+            params![queued.attempts, queued.next_attempt, queued.last_error, queued.id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Remove a notification from the retry queue; returns whether it existed
+    pub fn delete_queued_notification(&self, id: i64) -> Result<bool> {
+        let deleted = self.conn.execute("DELETE FROM notification_queue WHERE id = ?", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Clean old data (older than specified days), including the alert
+    /// history and notification log
+    /// 
+    /// The audit log is not affected by retention; it is kept until removed by hand.
     pub fn clean_old_data(&self, days: i64) -> Result<usize> {
         let cutoff = Utc::now().timestamp() - (days * 86400);
         
@@ -275,6 +841,16 @@ impl HistoryManager {
             params![cutoff],
         )?;
 
+        self.conn.execute(
+            "DELETE FROM alert_history WHERE timestamp < ?",
+            params![cutoff],
+        )?;
+
+        self.conn.execute(
+            "DELETE FROM notification_log WHERE timestamp < ?",
+            params![cutoff],
+        )?;
+
         // Vacuum to reclaim space
         self.conn.execute("VACUUM", [])?;
 
@@ -299,6 +875,102 @@ impl HistoryManager {
     }
 }
 
+/// A process-control action (signal, renice, affinity change) and its result
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AuditRecord {
+    pub timestamp: i64,
+    /// Who asked: local user for the TUI, token or user name for the API
+    pub initiator: String,
+    /// Where the action came from ("tui" or "api")
+    pub origin: String,
+    /// Client address for API requests
+    pub remote_addr: Option<String>,
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+    /// Kind of action, e.g. "signal" or "signal_tree"
+    pub action: String,
+    /// Action parameters, e.g. "signal=15"
+    pub details: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    /// Build a record for `process`, timestamped now
+    pub fn new(
+        initiator: &str,
+        origin: &str,
+        process: Option<&ProcessInfo>,
+        pid: u32,
+        action: &str,
+        details: String,
+        result: std::result::Result<(), String>,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now().timestamp(),
+            initiator: initiator.to_string(),
+            origin: origin.to_string(),
+            remote_addr: None,
+            pid,
+            name: process.map(|p| p.name.clone()).unwrap_or_default(),
+            command: process.map(|p| p.command.clone()).unwrap_or_default(),
+            action: action.to_string(),
+            details,
+            success: result.is_ok(),
+            error: result.err(),
+        }
+    }
+}
+
+/// Parse the alert stored as JSON in column `index`
+fn alert_from_json(row: &rusqlite::Row, index: usize) -> rusqlite::Result<Alert> {
+    let json: String = row.get(index)?;
+    serde_json::from_str(&json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Narrows [`HistoryManager::get_alert_history`]; unset fields match any event
+#[derive(Debug, Clone, Default)]
+pub struct AlertHistoryFilter {
+    pub alert_id: Option<u64>,
+    pub pid: Option<u32>,
+    /// Exact rule name, e.g. `cpu`
+    pub rule: Option<String>,
+    pub event: Option<AlertEventKind>,
+}
+
+/// One attempt to send an alert notification
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NotificationRecord {
+    pub timestamp: i64,
+    pub alert_id: u64,
+    /// "desktop", "email" or "webhook"
+    pub channel: String,
+    /// 1 for the first attempt
+    pub attempt: u32,
+    /// "sent", "retrying" (failed, queued for another attempt) or "failed"
+    /// (given up)
+    pub status: String,
+    pub error: Option<String>,
+    pub latency_ms: u64,
+}
+
+/// A notification waiting in the retry queue
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct QueuedNotification {
+    pub id: i64,
+    pub channel: String,
+    pub alert: Alert,
+    /// Failed attempts so far
+    pub attempts: u32,
+    /// When the next attempt is due, in seconds since the Unix epoch
+    pub next_attempt: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+}
+
 /// Historical process data point
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct HistoricalProcessData {
@@ -310,6 +982,12 @@ pub struct HistoricalProcessData {
     pub memory_usage: u64,
     pub memory_percent: f32,
     pub command: String,
+    /// Disk I/O rates per second; `None` when they were not available
+    pub disk_read_rate: Option<f64>,
+    pub disk_write_rate: Option<f64>,
+    pub read_syscalls_rate: Option<f64>,
+    pub write_syscalls_rate: Option<f64>,
+    pub cancelled_write_rate: Option<f64>,
 }
 
 /// Historical system data point
@@ -357,4 +1035,219 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_disk_io_columns_added_to_old_database() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db_path = dir.path().join("old.db");
+        Connection::open(&db_path)?.execute(
+            "CREATE TABLE process_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                pid INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                cpu_usage REAL NOT NULL,
+                memory_usage INTEGER NOT NULL,
+                memory_percent REAL NOT NULL,
+                command TEXT
+            )",
+            [],
+        )?;
+
+        let manager = HistoryManager::new(db_path.to_str().unwrap())?;
+        let mut process = crate::process::ProcessManager::new();
+        process.refresh()?;
+        let mut snapshot: Vec<ProcessInfo> = process.get_processes().into_iter().take(1).cloned().collect();
+        snapshot[0].disk_io = Some(crate::process::DiskIo { write_bytes: 512.0, ..Default::default() });
+        manager.record_processes(&snapshot)?;
+
+        let history = manager.get_process_history(
+            snapshot[0].pid,
+            Utc::now() - chrono::Duration::hours(1),
+            Utc::now() + chrono::Duration::hours(1),
+        )?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].disk_write_rate, Some(512.0));
+        assert_eq!(history[0].disk_read_rate, Some(0.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_log_filters() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let manager = HistoryManager::new(dir.path().join("audit.db").to_str().unwrap())?;
+
+        let mut kill = AuditRecord::new("alice", "tui", None, 100, "signal", "signal=15".to_string(), Ok(()));
+        kill.name = "nginx".to_string();
+        manager.record_audit(&kill)?;
+
+        let mut failed = AuditRecord::new(
+            "ops-token", "api", None, 200, "signal", "signal=9".to_string(),
+            Err("Operation not permitted".to_string()),
+        );
+        failed.remote_addr = Some("10.0.0.5".to_string());
+        manager.record_audit(&failed)?;
+
+        let mut ancient = AuditRecord::new("root", "tui", None, 300, "signal", "signal=15".to_string(), Ok(()));
+        ancient.timestamp -= 90 * 86400;
+        manager.record_audit(&ancient)?;
+
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let end = Utc::now() + chrono::Duration::hours(1);
+
+        let all = manager.get_audit_log(start, end, None, 100)?;
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].pid, 200); // newest first
+        assert!(!all[0].success);
+        assert_eq!(all[0].remote_addr.as_deref(), Some("10.0.0.5"));
+
+        let by_pid = manager.get_audit_log(start, end, Some(100), 100)?;
+        assert_eq!(by_pid.len(), 1);
+        assert_eq!(by_pid[0].initiator, "alice");
+        assert_eq!(by_pid[0].name, "nginx");
+
+        let old = manager.get_audit_log(start - chrono::Duration::hours(2), start, None, 100)?;
+        assert!(old.is_empty());
+
+        // Retention cleanup leaves the audit log alone
+        manager.clean_old_data(30)?;
+        let everything = manager.get_audit_log(start - chrono::Duration::days(100), end, None, 100)?;
+        assert_eq!(everything.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alert_history_and_notifications() -> Result<()> {
+        use crate::alerts::{AlertSeverity, AlertType};
+
+        let dir = tempfile::tempdir()?;
+        let manager = HistoryManager::new(dir.path().join("alerts.db").to_str().unwrap())?;
+        assert_eq!(manager.last_alert_id()?, 0);
+
+        let now = Utc::now();
+        let alert = |id: u64, pid: u32, rule: &str| Alert {
+            id,
+            alert_type: AlertType::HighCpu,
+            severity: AlertSeverity::Warning,
+            rule: rule.to_string(),
+            process_name: "nginx".to_string(),
+            pid,
+            user: "www".to_string(),
+            message: "High CPU usage".to_string(),
+            timestamp: now,
+            value: Some(95.0),
+            threshold: Some(80.0),
+            lifecycle: None,
+            acknowledged: None,
+            suppressed_by: None,
+        };
+        for (kind, alert) in [
+            (AlertEventKind::Fired, alert(1, 10, "cpu")),
+            (AlertEventKind::Fired, alert(2, 20, "memory")),
+            (AlertEventKind::Cleared, alert(1, 10, "cpu")),
+        ] {
+            manager.record_alert_event(&AlertEvent { kind, timestamp: now, alert })?;
+        }
+        assert_eq!(manager.last_alert_id()?, 2);
+
+        let start = now - chrono::Duration::hours(1);
+        let end = now + chrono::Duration::hours(1);
+        let all = manager.get_alert_history(start, end, &AlertHistoryFilter::default(), 100)?;
+        assert_eq!(all.len(), 3);
+        assert_eq!((all[0].kind, all[0].alert.id), (AlertEventKind::Cleared, 1)); // newest first
+        assert_eq!(all[0].alert.threshold, Some(80.0));
+
+        let by_pid = AlertHistoryFilter { pid: Some(10), ..Default::default() };
+        assert_eq!(manager.get_alert_history(start, end, &by_pid, 100)?.len(), 2);
+        let fired_cpu = AlertHistoryFilter {
+            rule: Some("cpu".to_string()),
+            event: Some(AlertEventKind::Fired),
+            ..Default::default()
+        };
+        assert_eq!(manager.get_alert_history(start, end, &fired_cpu, 100)?.len(), 1);
+        let later = manager.get_alert_history(end, end + chrono::Duration::hours(1), &AlertHistoryFilter::default(), 100)?;
+        assert!(later.is_empty());
+
+        manager.record_notification(&NotificationRecord {
+            timestamp: now.timestamp(),
+            alert_id: 2,
+            channel: "webhook".to_string(),
+            attempt: 1,
+            status: "retrying".to_string(),
+            error: Some("connection refused".to_string()),
+            latency_ms: 12,
+        })?;
+        let log = manager.get_notification_log(start, end, Some(2), 100)?;
+        assert_eq!((log[0].status.as_str(), log[0].latency_ms), ("retrying", 12));
+        assert!(manager.get_notification_log(start, end, Some(1), 100)?.is_empty());
+
+        let mut queued = QueuedNotification {
+            id: 0,
+            channel: "webhook".to_string(),
+            alert: alert(2, 20, "memory"),
+            attempts: 1,
+            next_attempt: now.timestamp() + 30,
+            last_error: Some("connection refused".to_string()),
+            created_at: now.timestamp(),
+        };
+        queued.id = manager.enqueue_notification(&queued)?;
+        assert!(manager.get_queued_notifications(Some(now))?.is_empty());
+        queued.attempts = 2;
+        queued.next_attempt = now.timestamp();
+        manager.update_queued_notification(&queued)?;
+        let due = manager.get_queued_notifications(Some(now))?;
+        assert_eq!((due[0].attempts, due[0].alert.pid), (2, 20));
+        assert!(manager.delete_queued_notification(queued.id)?);
+        assert!(manager.get_queued_notifications(None)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_silences_and_maintenance_windows() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let manager = HistoryManager::new(dir.path().join("silences.db").to_str().unwrap())?;
+
+        let now = Utc::now();
+        let mut silence = Silence {
+            id: 0,
+            matcher: AlertMatcher { rule: Some("cpu".to_string()), pid: Some(42), ..Default::default() },
+            starts_at: now,
+            ends_at: now + chrono::Duration::hours(2),
+            created_by: "alice".to_string(),
+            comment: "deploying".to_string(),
+        };
+        let id = manager.add_silence(&silence)?;
+        silence.ends_at = now - chrono::Duration::hours(1);
+        manager.add_silence(&silence)?;
+
+        // Expired silences are not returned
+        let silences = manager.get_silences(now)?;
+        assert_eq!(silences.len(), 1);
+        assert_eq!(silences[0].id, id);
+        assert_eq!(silences[0].matcher.pid, Some(42));
+        assert_eq!(silences[0].comment, "deploying");
+        assert!(manager.delete_silence(id)?);
+        assert!(!manager.delete_silence(id)?);
+        assert!(manager.get_silences(now)?.is_empty());
+
+        let window = MaintenanceWindow {
+            id: None,
+            name: "nightly-backup".to_string(),
+            start: "02:00".to_string(),
+            end: "03:00".to_string(),
+            days: vec!["mon".to_string(), "tue".to_string()],
+            matcher: AlertMatcher { user: Some("backup".to_string()), ..Default::default() },
+        };
+        let id = manager.add_maintenance_window(&window)?;
+        let windows = manager.get_maintenance_windows()?;
+        assert_eq!(windows, vec![MaintenanceWindow { id: Some(id), ..window }]);
+        assert!(manager.delete_maintenance_window(id)?);
+        assert!(manager.get_maintenance_windows()?.is_empty());
+
+        Ok(())
+    }
+}
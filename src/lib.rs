@@ -13,12 +13,21 @@
 //! - [`ui`] - Terminal user interface
 //! - [`tree`] - Process tree hierarchy
 //! - [`network`] - Network connections per process
+//! - [`fds`] - Open file descriptors per process
+//! - [`threads`] - Per-thread CPU, state and context switches
+//! - [`details`] - Per-process detail screen data
+//! - [`procfs`] - Typed `/proc/<pid>/stat` and `status` parsing
+//! - [`columns`] - Process table column registry
 //! - [`config`] - Configuration management
 //! 
 //! ### Advanced Modules
 //! - [`gpu`] - GPU monitoring (NVIDIA, AMD, Intel)
 //! - [`history`] - Historical data storage (SQLite)
 //! - [`api`] - REST API server
+//! - [`auth`] - REST API authentication and roles
+//! - [`tls`] - HTTPS and mutual TLS for the REST API
+//! - [`stream`] - Live process updates over Server-Sent Events
+//! - [`collector`] - Background process collection and shared snapshots
 //! - [`metrics`] - Prometheus/InfluxDB export
 //! - [`anomaly`] - Anomaly detection
 //! 
@@ -26,6 +35,9 @@
 //! - [`logging`] - Structured logging with rotation
 //! - [`affinity`] - CPU affinity and priority management
 //! - [`alerts`] - Smart alerting system
+//! - [`notifications`] - Alert notification delivery and retries
+//! - [`rules`] - Alert rule expressions
+//! - [`silences`] - Alert silences and maintenance windows
 //! - [`snapshots`] - Process state capture and replay
 //! - [`groups`] - Process group management
 //! - [`memmap`] - Memory map visualization
@@ -57,12 +69,21 @@ pub mod process;
 pub mod ui;
 pub mod tree;
 pub mod network;
+pub mod fds;
+pub mod threads;
+pub mod details;
+pub mod procfs;
+pub mod columns;
 pub mod config;
 
 // Advanced modules
 pub mod gpu;
 pub mod history;
 pub mod api;
+pub mod auth;
+pub mod tls;
+pub mod stream;
+pub mod collector;
 pub mod metrics;
 pub mod anomaly;
 
@@ -74,5 +95,8 @@ pub mod diffing;
 pub mod groups;
 pub mod logging;
 pub mod memmap;
+pub mod notifications;
 pub mod profiles;
+pub mod rules;
+pub mod silences;
 pub mod snapshots;
@@ -60,9 +60,18 @@ pub mod process;   // Process management and monitoring
 pub mod ui;        // Terminal user interface
 pub mod tree;      // Process tree hierarchy
 pub mod network;   // Network connections and containers
+pub mod fds;       // Open file descriptors
+pub mod threads;   // Per-thread monitoring
+pub mod details;   // Process detail screen data
+pub mod procfs;    // /proc stat and status parsing
+pub mod columns;   // Process table columns
 pub mod gpu;       // GPU monitoring
 pub mod history;   // Historical data storage
 pub mod api;       // REST API server
+pub mod auth;      // REST API authentication
+pub mod tls;       // REST API HTTPS
+pub mod stream;    // Live process stream
+pub mod collector; // Background process snapshots
 pub mod metrics;   // Metrics export
 pub mod anomaly;   // Anomaly detection
 pub mod config;    // Configuration management
@@ -71,6 +80,9 @@ pub mod config;    // Configuration management
 pub mod logging;    // Structured logging system
 pub mod affinity;   // CPU affinity and priority
 pub mod alerts;     // Smart alerting system
+pub mod notifications; // Alert notification delivery
+pub mod rules;      // Alert rule expressions
+pub mod silences;   // Alert silences and maintenance windows
 pub mod snapshots;  // Process snapshots
 pub mod groups;     // Process group management
 pub mod memmap;     // Memory map visualization
@@ -78,7 +90,8 @@ pub mod profiles;   // Saved view profiles
 pub mod diffing;    // Process state comparison
 pub mod containers; // Container deep dive
 
-use clap::{Arg, Command};
+use clap::{Arg, ArgMatches, Command};
+use config::{Config, ConfigOverrides, ConfigSource};
 use ui::run_app;
 use tracing::{Level, debug, info, error};
 use logging::log_system_event;
@@ -95,13 +108,21 @@ use logging::log_system_event;
 /// - `-r, --refresh <SECONDS>`: Set refresh interval in seconds
 /// - `-u, --user <USERNAME>`: Filter processes by user
 /// - `-t, --tree`: Start in tree view mode
+/// - `--profile <NAME>`: Start with a saved view profile (overrides `ui.view_profile`)
 /// - `--api`: Start REST API server
-/// - `--api-port <PORT>`: API server port (default: 8080)
+/// - `--api-port <PORT>`: API server port (overrides `api.port`)
+/// - `--bind-address <ADDR>`: API bind address (overrides `api.bind_address`)
 /// - `--export <FORMAT>`: Export metrics (prometheus|influxdb)
 /// - `--export-file <FILE>`: Export metrics to file
-/// - `--history-db <PATH>`: Path to history database
+/// - `--history-db <PATH>`: Path to history database (overrides `history.database_path`)
 /// - `-c, --config <FILE>`: Path to configuration file
 /// - `--generate-config <FILE>`: Generate example configuration file
+/// - `--print-effective-config`: Print the merged configuration, credentials redacted, and exit
+/// - `--port-owner <PORT>`: Show which processes have a socket bound to PORT and exit
+/// - `--hash-password`: Read a password from stdin and print its hash for `api.auth.users`
+/// 
+/// Command-line flags take precedence over the configuration file, which in
+/// turn takes precedence over built-in defaults.
 #[tokio::main]
 async fn main() {
     let matches = Command::new("Linux Process Manager")
@@ -130,6 +151,12 @@ async fn main() {
                 .help("Start in tree view mode")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("NAME")
+                .help("Start with a saved view profile (overrides ui.view_profile)"),
+        )
         .arg(
             Arg::new("api")
                 .long("api")
@@ -140,10 +167,15 @@ async fn main() {
             Arg::new("api-port")
                 .long("api-port")
                 .value_name("PORT")
-                .help("API server port (default: 8080)")
-                .default_value("8080")
+                .help("API server port (overrides api.port, default: 8080)")
                 .value_parser(clap::value_parser!(u16)),
         )
+        .arg(
+            Arg::new("bind-address")
+                .long("bind-address")
+                .value_name("ADDR")
+                .help("API bind address (overrides api.bind_address, default: 127.0.0.1)"),
+        )
         .arg(
             Arg::new("export")
                 .long("export")
@@ -161,8 +193,7 @@ async fn main() {
             Arg::new("history-db")
                 .long("history-db")
                 .value_name("PATH")
-                .help("Path to history database")
-                .default_value("process_history.db"),
+                .help("Path to history database (overrides history.database_path)"),
         )
         .arg(
             Arg::new("config")
@@ -177,6 +208,25 @@ async fn main() {
                 .value_name("FILE")
                 .help("Generate example configuration file"),
         )
+        .arg(
+            Arg::new("port-owner")
+                .long("port-owner")
+                .value_name("PORT")
+                .help("Show which processes have a TCP or UDP socket bound to PORT, then exit")
+                .value_parser(clap::value_parser!(u16)),
+        )
+        .arg(
+            Arg::new("hash-password")
+                .long("hash-password")
+                .help("Read a password from stdin and print its Argon2 hash for api.auth.users, then exit")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("print-effective-config")
+                .long("print-effective-config")
+                .help("Print the configuration after applying file and CLI overrides, then exit")
+                .action(clap::ArgAction::SetTrue),
+        )
         .get_matches();
 
     // Initialize logging system early for audit trail and debugging
@@ -192,11 +242,17 @@ async fn main() {
     
     log_system_event("startup", "Linux Process Manager starting", Level::INFO);
 
-    // Print application banner with version and team information
-    println!("Linux Process Manager (LPM) v1.0");
-    println!("CSCE 3401 - Operating Systems Fall 2025");
-    println!("Team: Adam Aberbach, Mohammad Yahya Hammoudeh, Mohamed Khalil Brik, Ahmed Elaswar");
-    println!();
+    // Print application banner with version and team information,
+    // unless stdout carries machine-readable output
+    let machine_output = matches.get_flag("print-effective-config")
+        || matches.get_flag("hash-password")
+        || (matches.contains_id("export") && !matches.contains_id("export-file"));
+    if !machine_output {
+        println!("Linux Process Manager (LPM) v1.0");
+        println!("CSCE 3401 - Operating Systems Fall 2025");
+        println!("Team: Adam Aberbach, Mohammad Yahya Hammoudeh, Mohamed Khalil Brik, Ahmed Elaswar");
+        println!();
+    }
 
     // Handle configuration file generation if requested
     // This creates an example config file with all available options
@@ -215,56 +271,59 @@ async fn main() {
         }
     }
 
-    // Load configuration from file or use defaults
-    // Priority: --config flag > default location (~/.config/lpm/config.toml) > built-in defaults
-    let _config = if let Some(config_file) = matches.get_one::<String>("config") {
-        let path = std::path::PathBuf::from(config_file);
-        match config::Config::load_from_file(&path) {
-            Ok(cfg) => {
-                println!("✓ Loaded configuration from: {}", config_file);
-                cfg
-            }
-            Err(e) => {
-                eprintln!("✗ Failed to load config file: {}", e);
-                eprintln!("  Using default configuration instead.");
-                config::Config::default()
-            }
+    if matches.get_flag("hash-password") {
+        handle_hash_password();
+        return;
+    }
+
+    // Reverse port lookup needs no configuration
+    if let Some(&port) = matches.get_one::<u16>("port-owner") {
+        handle_port_owner(port);
+        return;
+    }
+
+    // Resolve configuration: CLI flags > config file > built-in defaults
+    let (config, config_source) = resolve_config(&matches);
+
+    if let Err(errors) = config.validate() {
+        eprintln!("✗ Invalid configuration:");
+        for e in &errors {
+            eprintln!("  - {}", e);
         }
-    } else {
-        // Try to load from default location
-        match config::Config::load() {
-            Ok(cfg) => {
-                println!("✓ Loaded configuration from default location");
-                cfg
+        std::process::exit(1);
+    }
+
+    if matches.get_flag("print-effective-config") {
+        match config.to_redacted_toml_string() {
+            Ok(toml) => {
+                println!("{}", toml);
+                return;
             }
-            Err(_) => {
-                // Use defaults silently
-                config::Config::default()
+            Err(e) => {
+                eprintln!("✗ Failed to serialize configuration: {}", e);
+                std::process::exit(1);
             }
         }
-    };
+    }
 
     // Handle metrics export mode (non-interactive)
     // Exports current system metrics in Prometheus or InfluxDB format
     if let Some(format) = matches.get_one::<String>("export") {
-        handle_export_mode(format, matches.get_one::<String>("export-file"));
+        handle_export_mode(format, matches.get_one::<String>("export-file"), &config);
         return;
     }
 
     // Handle REST API server mode (long-running service)
     // Provides JSON API endpoints and serves the web UI
     if matches.get_flag("api") {
-        let port = *matches.get_one::<u16>("api-port").unwrap();
-        let bind_address = format!("0.0.0.0:{}", port);
-        let history_db = matches.get_one::<String>("history-db").map(|s| s.to_string());
-        
         println!("Starting REST API server mode...");
-        println!("API will be available at http://localhost:{}/api", port);
-        println!("Web UI available at: file://web/index.html");
+        let scheme = if config.api.tls.enabled { "https" } else { "http" };
+        println!("API will be available at {}://{}:{}/api", scheme, config.api.bind_address, config.api.port);
+        println!("Web UI available at: {}://{}:{}/", scheme, config.api.bind_address, config.api.port);
         println!();
         
-        let process_manager = process::ProcessManager::new();
-        if let Err(e) = api::start_api_server(&bind_address, process_manager, history_db).await {
+        let process_manager = process::ProcessManager::with_features(config.features.clone());
+        if let Err(e) = api::start_api_server(config, process_manager, Some(config_source)).await {
             eprintln!("API server error: {}", e);
             std::process::exit(1);
         }
@@ -277,18 +336,83 @@ async fn main() {
     println!("Press 'h' for help once the application starts.");
     println!();
     
-    if let Err(err) = run_app() {
+    if let Err(err) = run_app(&config) {
         eprintln!("Application error: {}", err);
         std::process::exit(1);
     }
 }
 
+/// Builds the effective configuration for this run.
+/// 
+/// Loads the file given with `--config` (or the default location), then
+/// applies any command-line overrides on top of it.
+/// 
+/// # Arguments
+/// 
+/// * `matches` - Parsed command-line arguments
+/// 
+/// # Returns
+/// 
+/// The merged configuration, and the source it came from so the API server
+/// can reload it later with the same overrides
+fn resolve_config(matches: &ArgMatches) -> (Config, ConfigSource) {
+    let overrides = ConfigOverrides {
+        refresh_interval: matches.get_one::<u64>("refresh").copied(),
+        user_filter: matches.get_one::<String>("user").cloned(),
+        start_in_tree_view: matches.get_flag("tree"),
+        api_port: matches.get_one::<u16>("api-port").copied(),
+        bind_address: matches.get_one::<String>("bind-address").cloned(),
+        database_path: matches.get_one::<String>("history-db").cloned(),
+        view_profile: matches.get_one::<String>("profile").cloned(),
+    };
+
+    // Priority: --config flag > default location (~/.config/process-manager/config.toml) > built-in defaults
+    let (mut config, path) = if let Some(config_file) = matches.get_one::<String>("config") {
+        let path = std::path::PathBuf::from(config_file);
+        let config = match Config::load_from_file(&path) {
+            Ok(cfg) => {
+                eprintln!("✓ Loaded configuration from: {}", config_file);
+                cfg
+            }
+            Err(e) => {
+                eprintln!("✗ Failed to load config file: {}", e);
+                eprintln!("  Using default configuration instead.");
+                Config::default()
+            }
+        };
+        (config, path)
+    } else {
+        // Try to load from default location
+        let config = match Config::load() {
+            Ok(cfg) => {
+                debug!("Loaded configuration from default location");
+                cfg
+            }
+            Err(_) => {
+                // Use defaults silently
+                Config::default()
+            }
+        };
+        let path = Config::get_config_path()
+            .unwrap_or_else(|_| std::path::PathBuf::from("process-manager.toml"));
+        (config, path)
+    };
+
+    overrides.apply(&mut config);
+
+    (config, ConfigSource::new(path, overrides))
+}
+
+/// Time between the two samples taken by `--export`, so rates can be computed
+const EXPORT_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 /// Handles metrics export to Prometheus or InfluxDB format.
 /// 
 /// # Arguments
 /// 
 /// * `format` - Export format ("prometheus" or "influxdb")
 /// * `output_file` - Optional file path to write metrics to (stdout if None)
+/// * `config` - Effective configuration (feature flags control which metrics are collected)
 /// 
 /// # Behavior
 /// 
@@ -296,11 +420,11 @@ async fn main() {
 /// 2. Exports metrics in requested format
 /// 3. Writes to file or stdout
 /// 4. Exits the application
-fn handle_export_mode(format: &str, output_file: Option<&String>) {
+fn handle_export_mode(format: &str, output_file: Option<&String>, config: &Config) {
     use metrics::{ExportFormat, MetricsExporter};
     
     log_system_event("export", &format!("Exporting metrics in {} format", format), Level::INFO);
-    println!("Exporting metrics in {} format...", format);
+    eprintln!("Exporting metrics in {} format...", format);
     
     let export_format = match format {
         "prometheus" => ExportFormat::Prometheus,
@@ -312,11 +436,17 @@ fn handle_export_mode(format: &str, output_file: Option<&String>) {
         }
     };
     
-    let mut process_manager = process::ProcessManager::new();
-    if let Err(e) = process_manager.refresh() {
-        error!("Failed to refresh processes during export: {}", e);
-        eprintln!("Failed to refresh processes: {}", e);
-        std::process::exit(1);
+    // Disk and network I/O rates (and CPU usage) need two samples
+    let mut process_manager = process::ProcessManager::with_features(config.features.clone());
+    for sample in 0..2 {
+        if sample > 0 {
+            std::thread::sleep(EXPORT_SAMPLE_INTERVAL);
+        }
+        if let Err(e) = process_manager.refresh() {
+            error!("Failed to refresh processes during export: {}", e);
+            eprintln!("Failed to refresh processes: {}", e);
+            std::process::exit(1);
+        }
     }
     
     let exporter = MetricsExporter::new("lpm");
@@ -337,3 +467,50 @@ fn handle_export_mode(format: &str, output_file: Option<&String>) {
         info!("Metrics exported successfully to stdout");
     }
 }
+
+/// Reads a password from the first line of stdin and prints its Argon2 PHC
+/// string, to paste into `password_hash` of an `[[api.auth.users]]` entry.
+fn handle_hash_password() {
+    let mut password = String::new();
+    if let Err(e) = std::io::stdin().read_line(&mut password) {
+        eprintln!("✗ Failed to read password: {}", e);
+        std::process::exit(1);
+    }
+    let password = password.trim_end_matches(['\r', '\n']);
+    if password.is_empty() {
+        eprintln!("✗ Empty password");
+        std::process::exit(1);
+    }
+    match auth::hash_password(password) {
+        Ok(hash) => println!("{}", hash),
+        Err(e) => {
+            eprintln!("✗ Failed to hash password: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints the processes with a TCP or UDP socket bound to `port`.
+/// 
+/// Exits with status 1 if no process could be found, as `lsof -i` does.
+fn handle_port_owner(port: u16) {
+    let owners = network::find_port_owners(port);
+    if owners.is_empty() {
+        eprintln!("No process found with a socket bound to port {}", port);
+        eprintln!("(processes of other users are only visible when run as root)");
+        std::process::exit(1);
+    }
+
+    println!("PID      NAME             PROTO  STATE        LOCAL                        REMOTE");
+    for owner in owners {
+        println!(
+            "{:<8} {:<16} {:<6} {:<12} {:<28} {}",
+            owner.pid,
+            owner.name,
+            owner.socket.protocol,
+            owner.socket.state,
+            owner.socket.local_address,
+            owner.socket.remote_address.as_deref().unwrap_or("*"),
+        );
+    }
+}
@@ -70,50 +70,90 @@ impl PrometheusExporter {
         }
         
         // Network connections (if available)
-        writeln!(output, "# HELP {}_process_network_connections Number of network connections per process", self.namespace).ok();
-        writeln!(output, "# TYPE {}_process_network_connections gauge", self.namespace).ok();
+        let mut samples = String::new();
         for process in &processes {
             if let Some(connections) = process.network_connections {
                 writeln!(
-                    output,
+                    samples,
                     "{}_process_network_connections{{pid=\"{}\",name=\"{}\"}} {}",
                     self.namespace, process.pid, Self::escape_label(&process.name), connections
                 ).ok();
             }
         }
+        self.push_family(&mut output, "process_network_connections", "Number of network connections per process", &samples);
         
         // Container processes
-        writeln!(output, "# HELP {}_process_in_container Whether process is running in a container (1=yes, 0=no)", self.namespace).ok();
-        writeln!(output, "# TYPE {}_process_in_container gauge", self.namespace).ok();
+        let mut samples = String::new();
         for process in &processes {
             if process.is_container {
                 let container_id = process.container_id.as_deref().unwrap_or("unknown");
                 writeln!(
-                    output,
+                    samples,
                     "{}_process_in_container{{pid=\"{}\",name=\"{}\",container_id=\"{}\"}} 1",
                     self.namespace, process.pid, Self::escape_label(&process.name),
                     Self::escape_label(container_id)
                 ).ok();
             }
         }
+        self.push_family(&mut output, "process_in_container", "Whether process is running in a container (1=yes, 0=no)", &samples);
         
         // GPU memory (if available)
-        writeln!(output, "# HELP {}_process_gpu_memory_bytes GPU memory usage per process in bytes", self.namespace).ok();
-        writeln!(output, "# TYPE {}_process_gpu_memory_bytes gauge", self.namespace).ok();
+        let mut samples = String::new();
         for process in &processes {
             if let Some(gpu_memory) = process.gpu_memory {
                 writeln!(
-                    output,
+                    samples,
                     "{}_process_gpu_memory_bytes{{pid=\"{}\",name=\"{}\"}} {}",
                     self.namespace, process.pid, Self::escape_label(&process.name),
                     gpu_memory * 1024 * 1024 // Convert MB to bytes
                 ).ok();
             }
         }
+        self.push_family(&mut output, "process_gpu_memory_bytes", "GPU memory usage per process in bytes", &samples);
+        
+        // Disk I/O rates (if available)
+        let mut samples = String::new();
+        for process in &processes {
+            if let Some(io) = process.disk_io {
+                for (op, rate) in [("read", io.read_bytes), ("write", io.write_bytes), ("cancelled_write", io.cancelled_write_bytes)] {
+                    writeln!(
+                        samples,
+                        "{}_process_disk_bytes_per_second{{pid=\"{}\",name=\"{}\",op=\"{}\"}} {}",
+                        self.namespace, process.pid, Self::escape_label(&process.name), op, rate
+                    ).ok();
+                }
+            }
+        }
+        self.push_family(&mut output, "process_disk_bytes_per_second", "Disk I/O per process in bytes per second", &samples);
+        
+        let mut samples = String::new();
+        for process in &processes {
+            if let Some(io) = process.disk_io {
+                for (op, rate) in [("read", io.read_syscalls), ("write", io.write_syscalls)] {
+                    writeln!(
+                        samples,
+                        "{}_process_io_syscalls_per_second{{pid=\"{}\",name=\"{}\",op=\"{}\"}} {}",
+                        self.namespace, process.pid, Self::escape_label(&process.name), op, rate
+                    ).ok();
+                }
+            }
+        }
+        self.push_family(&mut output, "process_io_syscalls_per_second", "Read and write syscalls per process per second", &samples);
         
         output
     }
     
+    /// Append a gauge family with its `# HELP` and `# TYPE` lines, or nothing
+    /// if it has no samples
+    fn push_family(&self, output: &mut String, name: &str, help: &str, samples: &str) {
+        if samples.is_empty() {
+            return;
+        }
+        writeln!(output, "# HELP {}_{} {}", self.namespace, name, help).ok();
+        writeln!(output, "# TYPE {}_{} gauge", self.namespace, name).ok();
+        output.push_str(samples);
+    }
+    
     /// Export system metrics in Prometheus format
     pub fn export_system_metrics(&self, process_manager: &ProcessManager) -> String {
         let mut output = String::new();
@@ -241,6 +281,23 @@ impl InfluxDBExporter {
                     timestamp
                 ).ok();
             }
+            
+            // Disk I/O rates
+            if let Some(io) = process.disk_io {
+                writeln!(
+                    output,
+                    "{}_process_disk,pid={},name={} read_bytes_per_sec={},write_bytes_per_sec={},read_syscalls_per_sec={},write_syscalls_per_sec={},cancelled_write_bytes_per_sec={} {}",
+                    self.measurement_prefix,
+                    process.pid,
+                    Self::escape_tag(&process.name),
+                    io.read_bytes,
+                    io.write_bytes,
+                    io.read_syscalls,
+                    io.write_syscalls,
+                    io.cancelled_write_bytes,
+                    timestamp
+                ).ok();
+            }
         }
         
         output
@@ -335,6 +392,17 @@ mod tests {
         assert_eq!(PrometheusExporter::escape_label("test\"quote"), "test\\\"quote");
     }
     
+    #[test]
+    fn test_prometheus_skips_empty_families() {
+        let exporter = PrometheusExporter::new("lpm");
+        let mut output = String::new();
+        exporter.push_family(&mut output, "process_gpu_memory_bytes", "GPU memory", "");
+        assert!(output.is_empty());
+        exporter.push_family(&mut output, "process_gpu_memory_bytes", "GPU memory", "lpm_process_gpu_memory_bytes{pid=\"1\"} 5\n");
+        assert_eq!(output.lines().count(), 3);
+        assert!(output.starts_with("# HELP lpm_process_gpu_memory_bytes GPU memory\n"));
+    }
+    
     #[test]
     fn test_influxdb_escape_tag() {
         assert_eq!(InfluxDBExporter::escape_tag("test"), "test");
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
 use std::path::Path;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Serialize;
 use tracing::debug;
 
 /// Network statistics for a process
@@ -31,47 +35,455 @@ pub struct CgroupInfo {
 }
 
 /// Get network statistics for a process
-pub fn get_network_stats(pid: u32) -> Result<NetworkStats> {
+///
+/// `rx_bytes`/`tx_bytes` are the cumulative byte counters of the process's
+/// open TCP sockets, looked up in `sockets`, one [`tcp_socket_bytes`] dump
+/// that callers share across all processes of a refresh; UDP and Unix
+/// sockets have no byte counters and are only counted in `connections`.
+pub fn get_network_stats(pid: u32, sockets: &HashMap<u64, SocketBytes>) -> NetworkStats {
     debug!("Getting network stats for pid {}", pid);
     let mut stats = NetworkStats {
         pid,
         ..Default::default()
     };
 
-    // Read network connections from /proc/net
-    let connections = count_network_connections(pid)?;
-    stats.connections = connections;
-    debug!("Process {} has {} network connections", pid, connections);
+    // Read network connections from /proc/<pid>/fd
+    let inodes = socket_inodes(pid);
+    stats.connections = inodes.len();
+    debug!("Process {} has {} network connections", pid, stats.connections);
 
-    // Note: Per-process network bandwidth requires eBPF or netfilter
-    // For now, we'll track connection count which is available from /proc
+    for inode in &inodes {
+        if let Some(bytes) = sockets.get(inode) {
+            stats.rx_bytes += bytes.rx_bytes;
+            stats.tx_bytes += bytes.tx_bytes;
+        }
+    }
     
-    Ok(stats)
+    stats
 }
 
-/// Count network connections for a process
-fn count_network_connections(pid: u32) -> Result<usize> {
+/// Inodes of the sockets a process holds open, from its fd table
+pub fn socket_inodes(pid: u32) -> Vec<u64> {
     let fd_path = format!("/proc/{}/fd", pid);
-    if !Path::new(&fd_path).exists() {
-        return Ok(0);
+    let entries = match fs::read_dir(&fd_path) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| fs::read_link(entry.path()).ok())
+        .filter_map(|link| {
+            // Socket links look like "socket:[12345]"
+            link.to_string_lossy()
+                .strip_prefix("socket:[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
+
+/// Cumulative byte counters of one TCP socket
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SocketBytes {
+    /// `tcpi_bytes_received`
+    pub rx_bytes: u64,
+    /// `tcpi_bytes_acked`
+    pub tx_bytes: u64,
+}
+
+// sock_diag definitions not exported by libc (linux/sock_diag.h, linux/inet_diag.h)
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_INFO: u16 = 2;
+/// Size of `struct inet_diag_req_v2`
+const INET_DIAG_REQ_LEN: usize = 56;
+/// Size of `struct inet_diag_msg`; the socket inode is its last field
+const INET_DIAG_MSG_LEN: usize = 72;
+/// Offsets of `tcpi_bytes_acked` and `tcpi_bytes_received` in `struct tcp_info`
+const TCPI_BYTES_ACKED: usize = 120;
+const TCPI_BYTES_RECEIVED: usize = 128;
+const NLMSG_HDR_LEN: usize = 16;
+
+/// Byte counters of every IPv4 and IPv6 TCP socket on the host, by inode.
+///
+/// One `sock_diag` netlink dump per address family; this needs no privileges
+/// and no eBPF. Kernels older than 4.1 do not report the counters, and their
+/// sockets are left out.
+pub fn tcp_socket_bytes() -> Result<HashMap<u64, SocketBytes>> {
+    let mut sockets = HashMap::new();
+    for family in [libc::AF_INET, libc::AF_INET6] {
+        dump_tcp_sockets(family as u8, &mut sockets)?;
     }
+    Ok(sockets)
+}
 
-    let mut count = 0;
-    
-    // Read file descriptors
-    if let Ok(entries) = fs::read_dir(&fd_path) {
-        for entry in entries.flatten() {
-            if let Ok(link) = fs::read_link(entry.path()) {
-                let link_str = link.to_string_lossy();
-                // Check if it's a socket
-                if link_str.starts_with("socket:") {
-                    count += 1;
+fn dump_tcp_sockets(family: u8, sockets: &mut HashMap<u64, SocketBytes>) -> Result<()> {
+    // SAFETY: plain socket(2) call; ownership of the fd moves into `OwnedFd`
+    let fd = unsafe {
+        libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, libc::NETLINK_SOCK_DIAG)
+    };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error()).context("Failed to open sock_diag socket");
+    }
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // nlmsghdr followed by inet_diag_req_v2 for all TCP states
+    let mut request = [0u8; NLMSG_HDR_LEN + INET_DIAG_REQ_LEN];
+    request[0..4].copy_from_slice(&((NLMSG_HDR_LEN + INET_DIAG_REQ_LEN) as u32).to_ne_bytes());
+    request[4..6].copy_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    request[6..8].copy_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+    request[16] = family;
+    request[17] = libc::IPPROTO_TCP as u8;
+    request[18] = 1 << (INET_DIAG_INFO - 1);
+    request[20..24].copy_from_slice(&u32::MAX.to_ne_bytes());
+
+    // SAFETY: a zeroed sockaddr_nl addresses the kernel
+    let sent = unsafe {
+        let mut addr: libc::sockaddr_nl = std::mem::zeroed();
+        addr.nl_family = libc::AF_NETLINK as u16;
+        libc::sendto(
+            fd.as_raw_fd(),
+            request.as_ptr() as *const libc::c_void,
+            request.len(),
+            0,
+            &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as u32,
+        )
+    };
+    if sent < 0 {
+        return Err(std::io::Error::last_os_error()).context("Failed to send sock_diag request");
+    }
+
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        // SAFETY: `buf` is valid for `buf.len()` bytes
+        let received = unsafe {
+            libc::recv(fd.as_raw_fd(), buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0)
+        };
+        if received < 0 {
+            return Err(std::io::Error::last_os_error()).context("Failed to read sock_diag reply");
+        }
+        if received == 0 || parse_diag_messages(&buf[..received as usize], sockets)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Parse one netlink datagram of `inet_diag_msg` replies into `sockets`.
+///
+/// Returns `true` once the end of the dump (`NLMSG_DONE`) is reached.
+fn parse_diag_messages(mut buf: &[u8], sockets: &mut HashMap<u64, SocketBytes>) -> Result<bool> {
+    let u16_at = |b: &[u8], at: usize| u16::from_ne_bytes([b[at], b[at + 1]]);
+    let u32_at = |b: &[u8], at: usize| u32::from_ne_bytes(b[at..at + 4].try_into().unwrap());
+    let u64_at = |b: &[u8], at: usize| u64::from_ne_bytes(b[at..at + 8].try_into().unwrap());
+
+    while buf.len() >= NLMSG_HDR_LEN {
+        let len = u32_at(buf, 0) as usize;
+        if len < NLMSG_HDR_LEN || len > buf.len() {
+            anyhow::bail!("Truncated sock_diag message");
+        }
+        let payload = &buf[NLMSG_HDR_LEN..len];
+
+        match u16_at(buf, 4) as i32 {
+            libc::NLMSG_DONE => return Ok(true),
+            libc::NLMSG_ERROR => {
+                let errno = if payload.len() >= 4 { -(u32_at(payload, 0) as i32) } else { 0 };
+                return Err(std::io::Error::from_raw_os_error(errno)).context("sock_diag request failed");
+            }
+            _ if payload.len() >= INET_DIAG_MSG_LEN => {
+                let inode = u32_at(payload, INET_DIAG_MSG_LEN - 4) as u64;
+                let mut attrs = &payload[INET_DIAG_MSG_LEN..];
+                // Route attributes: rta_len, rta_type, data, padded to 4 bytes
+                while attrs.len() >= 4 {
+                    let attr_len = u16_at(attrs, 0) as usize;
+                    if attr_len < 4 || attr_len > attrs.len() {
+                        break;
+                    }
+                    let data = &attrs[4..attr_len];
+                    if u16_at(attrs, 2) == INET_DIAG_INFO && data.len() >= TCPI_BYTES_RECEIVED + 8 {
+                        sockets.insert(inode, SocketBytes {
+                            rx_bytes: u64_at(data, TCPI_BYTES_RECEIVED),
+                            tx_bytes: u64_at(data, TCPI_BYTES_ACKED),
+                        });
+                    }
+                    attrs = &attrs[((attr_len + 3) & !3).min(attrs.len())..];
                 }
             }
+            _ => {}
+        }
+
+        buf = &buf[((len + 3) & !3).min(buf.len())..];
+    }
+    Ok(false)
+}
+
+/// Transport of an open socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SocketProtocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl SocketProtocol {
+    /// File under `/proc/net` (or `/proc/<pid>/net`) listing sockets of this kind
+    fn table(self) -> &'static str {
+        match self {
+            SocketProtocol::Tcp => "tcp",
+            SocketProtocol::Tcp6 => "tcp6",
+            SocketProtocol::Udp => "udp",
+            SocketProtocol::Udp6 => "udp6",
+            SocketProtocol::Unix => "unix",
+        }
+    }
+
+    const ALL: [SocketProtocol; 5] = [
+        SocketProtocol::Tcp,
+        SocketProtocol::Tcp6,
+        SocketProtocol::Udp,
+        SocketProtocol::Udp6,
+        SocketProtocol::Unix,
+    ];
+}
+
+impl std::fmt::Display for SocketProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.table())
+    }
+}
+
+/// One socket, as listed in the kernel's `/proc/net` tables
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SocketEntry {
+    pub protocol: SocketProtocol,
+    /// `address:port`, with IPv6 addresses in brackets; the bound path for
+    /// Unix sockets (empty if unnamed)
+    pub local_address: String,
+    /// `None` for unconnected and Unix sockets
+    pub remote_address: Option<String>,
+    /// `None` for Unix sockets
+    pub local_port: Option<u16>,
+    /// TCP state (`LISTEN`, `ESTABLISHED`, ...), `UNCONN`/`ESTAB` for UDP,
+    /// and `LISTEN`/`CONNECTED`/`UNCONNECTED` for Unix sockets
+    pub state: String,
+    /// Bytes queued for sending (TCP/UDP only)
+    pub tx_queue: u64,
+    /// Bytes queued for reading (TCP/UDP only)
+    pub rx_queue: u64,
+    pub inode: u64,
+}
+
+/// A process holding a socket bound to a given port
+#[derive(Debug, Clone, Serialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    pub name: String,
+    pub socket: SocketEntry,
+}
+
+/// Sockets a process holds open, resolved against its network namespace's
+/// `/proc/<pid>/net/{tcp,tcp6,udp,udp6,unix}` tables.
+///
+/// Sockets of other families (netlink, packet, ...) are left out.
+pub fn get_process_sockets(pid: u32) -> Vec<SocketEntry> {
+    let inodes = socket_inodes(pid);
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut table = read_socket_tables(&format!("/proc/{}/net", pid));
+    let mut sockets: Vec<SocketEntry> = inodes
+        .iter()
+        .filter_map(|inode| table.remove(inode))
+        .collect();
+    sockets.sort_by(|a, b| {
+        (a.protocol.table(), a.local_port, &a.local_address)
+            .cmp(&(b.protocol.table(), b.local_port, &b.local_address))
+    });
+    sockets
+}
+
+/// Processes with a TCP or UDP socket bound to local `port`, like `ss -tulpn`
+/// filtered on one port.
+///
+/// Only the host network namespace is searched, and processes whose fd table
+/// is not readable (other users', without root) cannot be matched.
+pub fn find_port_owners(port: u16) -> Vec<PortOwner> {
+    let bound: HashMap<u64, SocketEntry> = read_socket_tables("/proc/net")
+        .into_iter()
+        .filter(|(_, socket)| socket.local_port == Some(port))
+        .collect();
+    if bound.is_empty() {
+        return Vec::new();
+    }
+
+    let mut owners = Vec::new();
+    let entries = match fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return owners,
+    };
+    let mut pids: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    pids.sort_unstable();
+
+    for pid in pids {
+        for inode in socket_inodes(pid) {
+            // A socket shared across fork is reported for every holder
+            if let Some(socket) = bound.get(&inode) {
+                let name = fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .map(|comm| comm.trim_end().to_string())
+                    .unwrap_or_default();
+                owners.push(PortOwner { pid, name, socket: socket.clone() });
+            }
         }
     }
+    owners
+}
+
+/// Every socket in the tables under `dir` (`/proc/net` or `/proc/<pid>/net`), by inode
+fn read_socket_tables(dir: &str) -> HashMap<u64, SocketEntry> {
+    let mut sockets = HashMap::new();
+    for protocol in SocketProtocol::ALL {
+        let content = match fs::read_to_string(format!("{}/{}", dir, protocol.table())) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        // The first line is a column header
+        for line in content.lines().skip(1) {
+            let entry = match protocol {
+                SocketProtocol::Unix => parse_unix_line(line),
+                _ => parse_inet_line(protocol, line),
+            };
+            if let Some(entry) = entry {
+                sockets.insert(entry.inode, entry);
+            }
+        }
+    }
+    sockets
+}
+
+/// Parse one row of `/proc/net/{tcp,tcp6,udp,udp6}`:
+/// `sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode ...`
+fn parse_inet_line(protocol: SocketProtocol, line: &str) -> Option<SocketEntry> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 10 {
+        return None;
+    }
+
+    let (local_address, local_port) = parse_inet_address(fields[1])?;
+    let (remote_address, remote_port) = parse_inet_address(fields[2])?;
+    let state = u8::from_str_radix(fields[3], 16).ok()?;
+    let (tx_queue, rx_queue) = fields[4].split_once(':')?;
+
+    let is_tcp = matches!(protocol, SocketProtocol::Tcp | SocketProtocol::Tcp6);
+    let connected = remote_port != 0 && (!is_tcp || state != TCP_LISTEN);
+    let state = if is_tcp {
+        tcp_state_name(state).to_string()
+    } else if state == TCP_ESTABLISHED {
+        "ESTAB".to_string()
+    } else {
+        "UNCONN".to_string()
+    };
+
+    Some(SocketEntry {
+        protocol,
+        local_address: format_socket_address(local_address, local_port),
+        remote_address: connected.then(|| format_socket_address(remote_address, remote_port)),
+        local_port: Some(local_port),
+        state,
+        tx_queue: u64::from_str_radix(tx_queue, 16).ok()?,
+        rx_queue: u64::from_str_radix(rx_queue, 16).ok()?,
+        inode: fields[9].parse().ok()?,
+    })
+}
+
+/// Parse `0100007F:1F90` (IPv4) or a 32-digit IPv6 address and port. Each
+/// 32-bit word of the address is printed in host byte order.
+fn parse_inet_address(field: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for word in 0..address.len() / 8 {
+        let word = u32::from_str_radix(address.get(word * 8..word * 8 + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    let address = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(bytes).ok()?)),
+        16 => {
+            let v6 = Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?);
+            // Show IPv4-mapped peers of dual-stack sockets as plain IPv4
+            v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(IpAddr::V6(v6))
+        }
+        _ => return None,
+    };
+    Some((address, port))
+}
+
+fn format_socket_address(address: IpAddr, port: u16) -> String {
+    std::net::SocketAddr::new(address, port).to_string()
+}
+
+const TCP_ESTABLISHED: u8 = 0x01;
+const TCP_LISTEN: u8 = 0x0A;
+
+/// Name of a kernel TCP state (`include/net/tcp_states.h`)
+fn tcp_state_name(state: u8) -> &'static str {
+    match state {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        0x0C => "NEW_SYN_RECV",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Parse one row of `/proc/net/unix`:
+/// `Num RefCount Protocol Flags Type St Inode [Path]`
+fn parse_unix_line(line: &str) -> Option<SocketEntry> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 7 {
+        return None;
+    }
 
-    Ok(count)
+    // __SO_ACCEPTCON in the flags marks a listening socket
+    let flags = u32::from_str_radix(fields[3], 16).ok()?;
+    let state = if flags & 0x10000 != 0 {
+        "LISTEN"
+    } else {
+        match fields[5] {
+            "01" => "UNCONNECTED",
+            "02" => "CONNECTING",
+            "03" => "CONNECTED",
+            "04" => "DISCONNECTING",
+            _ => "UNKNOWN",
+        }
+    };
+
+    Some(SocketEntry {
+        protocol: SocketProtocol::Unix,
+        local_address: fields[7..].join(" "),
+        remote_address: None,
+        local_port: None,
+        state: state.to_string(),
+        tx_queue: 0,
+        rx_queue: 0,
+        inode: fields[6].parse().ok()?,
+    })
 }
 
 /// Get cgroup information for a process
@@ -305,6 +717,121 @@ mod tests {
         assert_eq!(format_cpu_limit(200000, 100000), "200.0%");
     }
 
+    /// One netlink message carrying an `inet_diag_msg` and a `tcp_info` attribute
+    fn diag_message(inode: u32, rx: u64, tx: u64) -> Vec<u8> {
+        let mut info = vec![0u8; TCPI_BYTES_RECEIVED + 8];
+        info[TCPI_BYTES_ACKED..TCPI_BYTES_ACKED + 8].copy_from_slice(&tx.to_ne_bytes());
+        info[TCPI_BYTES_RECEIVED..].copy_from_slice(&rx.to_ne_bytes());
+
+        let mut payload = vec![0u8; INET_DIAG_MSG_LEN];
+        payload[INET_DIAG_MSG_LEN - 4..].copy_from_slice(&inode.to_ne_bytes());
+        payload.extend_from_slice(&((4 + info.len()) as u16).to_ne_bytes());
+        payload.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        payload.extend_from_slice(&info);
+
+        let mut message = vec![0u8; NLMSG_HDR_LEN];
+        message[0..4].copy_from_slice(&((NLMSG_HDR_LEN + payload.len()) as u32).to_ne_bytes());
+        message[4..6].copy_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        message.extend_from_slice(&payload);
+        message
+    }
+
+    #[test]
+    fn test_parse_diag_messages() {
+        let mut buf = diag_message(42, 1000, 2000);
+        buf.extend(diag_message(43, 5, 6));
+        let mut sockets = HashMap::new();
+        assert!(!parse_diag_messages(&buf, &mut sockets).unwrap());
+        assert_eq!(sockets[&42], SocketBytes { rx_bytes: 1000, tx_bytes: 2000 });
+        assert_eq!(sockets[&43], SocketBytes { rx_bytes: 5, tx_bytes: 6 });
+
+        let mut done = vec![0u8; NLMSG_HDR_LEN + 4];
+        done[0..4].copy_from_slice(&((NLMSG_HDR_LEN + 4) as u32).to_ne_bytes());
+        done[4..6].copy_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        assert!(parse_diag_messages(&done, &mut sockets).unwrap());
+    }
+
+    #[test]
+    fn test_tcp_socket_bytes_sees_own_connection() {
+        use std::io::{Read, Write};
+        use std::os::unix::fs::MetadataExt;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+        client.write_all(&[7u8; 1000]).unwrap();
+        server.read_exact(&mut [0u8; 1000]).unwrap();
+
+        // A socket's inode is what fstat reports for its fd
+        let pid = std::process::id();
+        let inode = fs::metadata(format!("/proc/{}/fd/{}", pid, server.as_raw_fd())).unwrap().ino();
+        assert!(socket_inodes(pid).contains(&inode));
+
+        let sockets = tcp_socket_bytes().unwrap();
+        assert_eq!(sockets[&inode].rx_bytes, 1000);
+    }
+
+    #[test]
+    fn test_parse_inet_line() {
+        let listen = parse_inet_line(
+            SocketProtocol::Tcp,
+            "   0: 0100007F:20FB 00000000:0000 0A 00000000:00000003 00:00000000 00000000  1000        0 51234 1 0000000000000000 100 0 0 10 0",
+        ).unwrap();
+        assert_eq!(listen.local_address, "127.0.0.1:8443");
+        assert_eq!(listen.local_port, Some(8443));
+        assert_eq!(listen.remote_address, None);
+        assert_eq!(listen.state, "LISTEN");
+        assert_eq!(listen.rx_queue, 3);
+        assert_eq!(listen.inode, 51234);
+
+        let established = parse_inet_line(
+            SocketProtocol::Tcp6,
+            "   1: 00000000000000000000000001000000:C350 0000000000000000FFFF00000200000A:01BB 01 00000010:00000000 00:00000000 00000000  1000        0 777 1 0000000000000000 20 4 30 10 -1",
+        ).unwrap();
+        assert_eq!(established.local_address, "[::1]:50000");
+        assert_eq!(established.remote_address.as_deref(), Some("10.0.0.2:443"));
+        assert_eq!(established.state, "ESTABLISHED");
+        assert_eq!(established.tx_queue, 16);
+
+        let udp = parse_inet_line(
+            SocketProtocol::Udp,
+            "  12: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 999 2 0000000000000000 0",
+        ).unwrap();
+        assert_eq!(udp.local_address, "0.0.0.0:68");
+        assert_eq!(udp.state, "UNCONN");
+
+        assert!(parse_inet_line(SocketProtocol::Tcp, "  sl  local_address rem_address").is_none());
+    }
+
+    #[test]
+    fn test_parse_unix_line() {
+        let listening = parse_unix_line(
+            "0000000000000000: 00000002 00000000 00010000 0001 01 23456 /run/systemd/private",
+        ).unwrap();
+        assert_eq!(listening.protocol, SocketProtocol::Unix);
+        assert_eq!(listening.state, "LISTEN");
+        assert_eq!(listening.local_address, "/run/systemd/private");
+        assert_eq!(listening.inode, 23456);
+
+        let unnamed = parse_unix_line("0000000000000000: 00000003 00000000 00000000 0001 03  8765").unwrap();
+        assert_eq!(unnamed.state, "CONNECTED");
+        assert_eq!(unnamed.local_address, "");
+        assert_eq!(unnamed.inode, 8765);
+    }
+
+    #[test]
+    fn test_find_own_listening_socket() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let pid = std::process::id();
+
+        let sockets = get_process_sockets(pid);
+        assert!(sockets.iter().any(|s| s.local_port == Some(port) && s.state == "LISTEN"));
+
+        let owners = find_port_owners(port);
+        assert!(owners.iter().any(|o| o.pid == pid && o.socket.protocol == SocketProtocol::Tcp));
+    }
+
     #[test]
     fn test_extract_container_id() {
         let docker_path = "/docker/abc123def456/system.slice";
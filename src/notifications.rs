@@ -0,0 +1,562 @@
+//! # Alert Notification Delivery
+//!
+//! [`Notifier`] sends the alerts raised by the [`crate::alerts::AlertManager`]
+//! to the desktop, email and webhook channels of a [`NotificationConfig`].
+//!
+//! - Every attempt is recorded in the history database with its channel,
+//!   status, error and latency (see [`HistoryManager::get_notification_log`])
+//! - Failed email and webhook sends are queued and retried with exponential
+//!   backoff: 30 seconds after the first failure, doubling up to an hour,
+//!   until [`MAX_ATTEMPTS`] have failed. The queue is kept in the history
+//!   database, so pending retries survive a restart; without one it lives in
+//!   memory.
+//! - Desktop notifications are not retried; one shown late is of little use.
+
+use crate::alerts::{Alert, AlertSeverity, EmailConfig, NotificationConfig, WebhookConfig};
+use crate::history::{HistoryManager, NotificationRecord, QueuedNotification};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+
+/// Attempts, including the first, before a notification is given up
+pub const MAX_ATTEMPTS: u32 = 8;
+
+/// Delay before the first retry; doubles with each further failure
+const RETRY_DELAY_SECS: i64 = 30;
+
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// How often the retry queue is checked for notifications that are due
+const RETRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Longest a webhook request may take before it counts as failed
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where a notification is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Desktop,
+    Email,
+    Webhook,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Desktop => "desktop",
+            Channel::Email => "email",
+            Channel::Webhook => "webhook",
+        }
+    }
+
+    /// Parse `desktop`, `email` or `webhook`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "desktop" => Some(Channel::Desktop),
+            "email" => Some(Channel::Email),
+            "webhook" => Some(Channel::Webhook),
+            _ => None,
+        }
+    }
+}
+
+/// Seconds to wait before the next attempt after `failed` failed attempts
+pub fn retry_delay_secs(failed: u32) -> i64 {
+    let doublings = failed.saturating_sub(1).min(16);
+    (RETRY_DELAY_SECS << doublings).min(MAX_RETRY_DELAY_SECS)
+}
+
+/// Where a queued notification is kept. The IDs of the two are assigned
+/// separately and may overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueueSlot {
+    /// Row of the `notification_queue` table
+    Database(i64),
+    /// Entry of [`Notifier::queue`], used when the database is unavailable
+    Memory(i64),
+}
+
+/// Sends alerts to the configured channels and retries failed sends
+pub struct Notifier {
+    config: NotificationConfig,
+    history: Option<Arc<Mutex<HistoryManager>>>,
+    /// Retry queue when there is no history database, or storing in it failed
+    queue: Vec<QueuedNotification>,
+    next_queue_id: i64,
+}
+
+impl Notifier {
+    pub fn new(config: NotificationConfig, history: Option<Arc<Mutex<HistoryManager>>>) -> Self {
+        Self {
+            config,
+            history,
+            queue: Vec::new(),
+            next_queue_id: 1,
+        }
+    }
+
+    /// Notify every alert received on `rx`, retrying queued notifications
+    /// as they become due, until the sender is dropped
+    pub async fn run(mut self, mut rx: mpsc::Receiver<Alert>) {
+        info!("Starting alert notification processor");
+        let mut retry = tokio::time::interval(RETRY_CHECK_INTERVAL);
+
+        loop {
+            tokio::select! {
+                alert = rx.recv() => match alert {
+                    Some(alert) => self.notify(&alert).await,
+                    None => break,
+                },
+                _ = retry.tick() => self.retry_due(Utc::now()).await,
+            }
+        }
+
+        info!("Alert notification processor terminated");
+    }
+
+    /// Channels enabled in the config
+    fn channels(&self) -> Vec<Channel> {
+        let mut channels = Vec::new();
+        if self.config.desktop {
+            channels.push(Channel::Desktop);
+        }
+        if self.config.email.as_ref().is_some_and(|email| email.enabled) {
+            channels.push(Channel::Email);
+        }
+        if self.config.webhook.as_ref().is_some_and(|webhook| webhook.enabled) {
+            channels.push(Channel::Webhook);
+        }
+        channels
+    }
+
+    /// Send `alert` to every enabled channel, queueing failed email and
+    /// webhook sends for a retry
+    pub async fn notify(&mut self, alert: &Alert) {
+        info!("Processing {:?} alert: {}", alert.severity, alert.message);
+
+        for channel in self.channels() {
+            let Err(error) = self.attempt(channel, alert, 1).await else {
+                continue;
+            };
+            if channel == Channel::Desktop {
+                continue;
+            }
+            let now = Utc::now();
+            self.enqueue(QueuedNotification {
+                id: 0,
+                channel: channel.as_str().to_string(),
+                alert: alert.clone(),
+                attempts: 1,
+                next_attempt: now.timestamp() + retry_delay_secs(1),
+                last_error: Some(error),
+                created_at: now.timestamp(),
+            });
+        }
+    }
+
+    /// Retry the queued notifications due at `now`. Those whose channel has
+    /// been disabled since are dropped.
+    pub async fn retry_due(&mut self, now: DateTime<Utc>) {
+        let channels = self.channels();
+
+        for (slot, mut queued) in self.due(now) {
+            let channel = match Channel::from_name(&queued.channel) {
+                Some(channel) if channels.contains(&channel) => channel,
+                _ => {
+                    warn!("Dropping queued {} notification for alert {}: channel not enabled", queued.channel, queued.alert.id);
+                    self.dequeue(slot);
+                    continue;
+                }
+            };
+
+            let attempt = queued.attempts + 1;
+            match self.attempt(channel, &queued.alert, attempt).await {
+                Ok(()) => self.dequeue(slot),
+                Err(_) if attempt >= MAX_ATTEMPTS => self.dequeue(slot),
+                Err(error) => {
+                    queued.attempts = attempt;
+                    queued.next_attempt = Utc::now().timestamp() + retry_delay_secs(attempt);
+                    queued.last_error = Some(error);
+                    self.requeue(slot, &queued);
+                }
+            }
+        }
+    }
+
+    /// Send `alert` once and record the attempt
+    async fn attempt(&self, channel: Channel, alert: &Alert, attempt: u32) -> std::result::Result<(), String> {
+        debug!("Sending {} notification for alert {} (attempt {})", channel.as_str(), alert.id, attempt);
+        let started = Instant::now();
+        let result = self.send(channel, alert).await.map_err(|e| e.to_string());
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let status = match result {
+            Ok(()) => {
+                info!("{} notification sent for alert {}", channel.as_str(), alert.id);
+                "sent"
+            }
+            Err(ref e) if channel == Channel::Desktop || attempt >= MAX_ATTEMPTS => {
+                error!("Failed to send {} notification for alert {}, giving up: {}", channel.as_str(), alert.id, e);
+                "failed"
+            }
+            Err(ref e) => {
+                warn!("Failed to send {} notification for alert {} (attempt {}), will retry: {}", channel.as_str(), alert.id, attempt, e);
+                "retrying"
+            }
+        };
+
+        if let Some(ref history) = self.history {
+            let record = NotificationRecord {
+                timestamp: Utc::now().timestamp(),
+                alert_id: alert.id,
+                channel: channel.as_str().to_string(),
+                attempt,
+                status: status.to_string(),
+                error: result.clone().err(),
+                latency_ms,
+            };
+            if let Err(e) = history.lock().unwrap().record_notification(&record) {
+                warn!("Failed to record notification attempt: {}", e);
+            }
+        }
+
+        result
+    }
+
+    async fn send(&self, channel: Channel, alert: &Alert) -> Result<()> {
+        match channel {
+            Channel::Desktop => send_desktop_notification(alert),
+            Channel::Email => match self.config.email {
+                Some(ref email_config) => send_email_notification(alert, email_config).await,
+                None => anyhow::bail!("Email notifications are not configured"),
+            },
+            Channel::Webhook => match self.config.webhook {
+                Some(ref webhook_config) => send_webhook_notification(alert, webhook_config).await,
+                None => anyhow::bail!("Webhook notifications are not configured"),
+            },
+        }
+    }
+
+    /// Add to the retry queue; falls back to memory if the database fails
+    fn enqueue(&mut self, mut queued: QueuedNotification) {
+        if let Some(ref history) = self.history {
+            match history.lock().unwrap().enqueue_notification(&queued) {
+                Ok(_) => return,
+                Err(e) => warn!("Failed to store queued notification, keeping it in memory: {}", e),
+            }
+        }
+        queued.id = self.next_queue_id;
+        self.next_queue_id += 1;
+        self.queue.push(queued);
+    }
+
+    /// Queued notifications due at `now` with where each is kept, oldest
+    /// first
+    fn due(&self, now: DateTime<Utc>) -> Vec<(QueueSlot, QueuedNotification)> {
+        let mut due = Vec::new();
+        if let Some(ref history) = self.history {
+            match history.lock().unwrap().get_queued_notifications(Some(now)) {
+                Ok(queued) => due.extend(queued.into_iter().map(|q| (QueueSlot::Database(q.id), q))),
+                Err(e) => warn!("Failed to read the notification queue: {}", e),
+            }
+        }
+        due.extend(self.queue.iter()
+            .filter(|q| q.next_attempt <= now.timestamp())
+            .map(|q| (QueueSlot::Memory(q.id), q.clone())));
+        due
+    }
+
+    /// Store the new attempt count and due time of a queued notification
+    fn requeue(&mut self, slot: QueueSlot, queued: &QueuedNotification) {
+        match slot {
+            QueueSlot::Memory(id) => {
+                if let Some(entry) = self.queue.iter_mut().find(|q| q.id == id) {
+                    *entry = queued.clone();
+                }
+            }
+            QueueSlot::Database(id) => {
+                if let Some(ref history) = self.history {
+                    if let Err(e) = history.lock().unwrap().update_queued_notification(queued) {
+                        warn!("Failed to update queued notification {}: {}", id, e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn dequeue(&mut self, slot: QueueSlot) {
+        match slot {
+            QueueSlot::Memory(id) => self.queue.retain(|q| q.id != id),
+            QueueSlot::Database(id) => {
+                if let Some(ref history) = self.history {
+                    if let Err(e) = history.lock().unwrap().delete_queued_notification(id) {
+                        warn!("Failed to remove queued notification {}: {}", id, e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Send desktop notification
+fn send_desktop_notification(alert: &Alert) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        use notify_rust::{Notification, Urgency};
+
+        let urgency = match alert.severity {
+            AlertSeverity::Critical => Urgency::Critical,
+            AlertSeverity::Warning => Urgency::Normal,
+            AlertSeverity::Info => Urgency::Low,
+        };
+
+        Notification::new()
+            .summary("Process Manager Alert")
+            .body(&alert.message)
+            .urgency(urgency)
+            .timeout(5000)
+            .show()
+            .context("Failed to show desktop notification")?;
+    }
+
+    Ok(())
+}
+
+/// Send email notification as one message to every recipient, so a retry
+/// never mails someone twice
+async fn send_email_notification(alert: &Alert, config: &EmailConfig) -> Result<()> {
+    use lettre::{
+        AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+        transport::smtp::authentication::Credentials,
+    };
+
+    let subject = format!(
+        "[{}] Process Manager Alert: {}",
+        match alert.severity {
+            AlertSeverity::Critical => "CRITICAL",
+            AlertSeverity::Warning => "WARNING",
+            AlertSeverity::Info => "INFO",
+        },
+        alert.process_name
+    );
+
+    let mut body = format!(
+        "Alert Details:\n\n\
+         Type: {:?}\n\
+         Process: {} (PID: {})\n\
+         Message: {}\n\
+         Time: {}\n\
+         Value: {:?}\n\
+         Threshold: {:?}\n",
+        alert.alert_type,
+        alert.process_name,
+        alert.pid,
+        alert.message,
+        alert.timestamp,
+        alert.value,
+        alert.threshold,
+    );
+    if let Some(ref lifecycle) = alert.lifecycle {
+        body.push_str(&format!("Command: {}\nRule: {}\n", lifecycle.command, lifecycle.rule));
+        if let Some(last_seen) = lifecycle.last_seen {
+            body.push_str(&format!("Last seen: {}\n", last_seen));
+        }
+        if let Some(previous_pid) = lifecycle.previous_pid {
+            body.push_str(&format!("Previous PID: {}\n", previous_pid));
+        }
+    }
+
+    if config.to.is_empty() {
+        anyhow::bail!("No email recipients configured");
+    }
+    let mut builder = Message::builder()
+        .from(config.from.parse()?)
+        .subject(&subject);
+    for recipient in &config.to {
+        builder = builder.to(recipient.parse()?);
+    }
+    let email = builder.body(body)?;
+
+    let creds = Credentials::new(
+        config.username.clone(),
+        config.password.clone(),
+    );
+
+    let mailer = AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_server)?
+        .credentials(creds)
+        .port(config.smtp_port)
+        .build();
+
+    mailer.send(email).await?;
+
+    Ok(())
+}
+
+/// Send webhook notification. Retries carry the same `id`, so receivers can
+/// drop duplicates.
+async fn send_webhook_notification(alert: &Alert, config: &WebhookConfig) -> Result<()> {
+    let client = reqwest::Client::new();
+    let mut request = client.post(&config.url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&serde_json::json!({
+            "id": alert.id,
+            "alert_type": format!("{:?}", alert.alert_type),
+            "severity": format!("{:?}", alert.severity),
+            "rule": alert.rule,
+            "process_name": alert.process_name,
+            "pid": alert.pid,
+            "message": alert.message,
+            "timestamp": alert.timestamp.to_rfc3339(),
+            "value": alert.value,
+            "threshold": alert.threshold,
+            "lifecycle": alert.lifecycle,
+        }));
+
+    for (key, value) in &config.headers {
+        request = request.header(key, value);
+    }
+
+    let response = request.send().await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Webhook returned error status: {}", response.status());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertType;
+    use std::collections::HashMap;
+
+    fn alert(id: u64) -> Alert {
+        Alert {
+            id,
+            alert_type: AlertType::HighCpu,
+            severity: AlertSeverity::Warning,
+            rule: "cpu".to_string(),
+            process_name: "stress".to_string(),
+            pid: 42,
+            user: "dev".to_string(),
+            message: "High CPU usage".to_string(),
+            timestamp: Utc::now(),
+            value: Some(97.0),
+            threshold: Some(80.0),
+            lifecycle: None,
+            acknowledged: None,
+            suppressed_by: None,
+        }
+    }
+
+    /// A webhook nothing listens on, so every send fails quickly
+    fn unreachable_webhook() -> NotificationConfig {
+        NotificationConfig {
+            desktop: false,
+            email: None,
+            webhook: Some(WebhookConfig {
+                enabled: true,
+                url: "http://127.0.0.1:9/alerts".to_string(),
+                headers: HashMap::new(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(4), 240);
+        assert_eq!(retry_delay_secs(7), 1920);
+        assert_eq!(retry_delay_secs(MAX_ATTEMPTS), MAX_RETRY_DELAY_SECS);
+        assert_eq!(retry_delay_secs(100), MAX_RETRY_DELAY_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_failed_webhook_is_retried_from_durable_queue() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let history = Arc::new(Mutex::new(HistoryManager::new(dir.path().join("alerts.db").to_str().unwrap())?));
+
+        let mut notifier = Notifier::new(unreachable_webhook(), Some(history.clone()));
+        notifier.notify(&alert(7)).await;
+
+        let queued = history.lock().unwrap().get_queued_notifications(None)?;
+        assert_eq!(queued.len(), 1);
+        assert_eq!((queued[0].channel.as_str(), queued[0].attempts, queued[0].alert.id), ("webhook", 1, 7));
+        assert!(queued[0].last_error.is_some());
+        assert!(queued[0].next_attempt > Utc::now().timestamp());
+
+        // Nothing is due yet
+        notifier.retry_due(Utc::now()).await;
+        assert_eq!(history.lock().unwrap().get_queued_notifications(None)?[0].attempts, 1);
+
+        // A new notifier, as after a restart, picks up the queue
+        let mut notifier = Notifier::new(unreachable_webhook(), Some(history.clone()));
+        notifier.retry_due(Utc::now() + chrono::Duration::seconds(retry_delay_secs(1))).await;
+        let queued = history.lock().unwrap().get_queued_notifications(None)?;
+        assert_eq!(queued[0].attempts, 2);
+
+        // The last attempt gives up
+        let mut last = queued[0].clone();
+        last.attempts = MAX_ATTEMPTS - 1;
+        history.lock().unwrap().update_queued_notification(&last)?;
+        notifier.retry_due(Utc::now() + chrono::Duration::hours(2)).await;
+        assert!(history.lock().unwrap().get_queued_notifications(None)?.is_empty());
+
+        let log = history.lock().unwrap().get_notification_log(
+            Utc::now() - chrono::Duration::hours(1), Utc::now() + chrono::Duration::hours(1), Some(7), 100,
+        )?;
+        let statuses: Vec<(&str, u32)> = log.iter().map(|r| (r.status.as_str(), r.attempt)).collect();
+        assert_eq!(statuses, vec![("failed", MAX_ATTEMPTS), ("retrying", 2), ("retrying", 1)]);
+        assert!(log.iter().all(|r| r.channel == "webhook" && r.error.is_some()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_and_database_queue_ids_do_not_collide() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let history = Arc::new(Mutex::new(HistoryManager::new(dir.path().join("alerts.db").to_str().unwrap())?));
+        let now = Utc::now().timestamp();
+        let queued = |id: u64, attempts: u32, next_attempt: i64| QueuedNotification {
+            id: 1,
+            channel: "webhook".to_string(),
+            alert: alert(id),
+            attempts,
+            next_attempt,
+            last_error: None,
+            created_at: now,
+        };
+        let stored = history.lock().unwrap().enqueue_notification(&queued(1, 1, now + 600))?;
+        assert_eq!(stored, 1);
+
+        // An in-memory entry with the same ID gives up without touching the stored row
+        let mut notifier = Notifier::new(unreachable_webhook(), Some(history.clone()));
+        notifier.queue.push(queued(2, MAX_ATTEMPTS - 1, now));
+        notifier.retry_due(Utc::now()).await;
+        assert!(notifier.queue.is_empty());
+        let rows = history.lock().unwrap().get_queued_notifications(None)?;
+        assert_eq!((rows.len(), rows[0].alert.id, rows[0].attempts), (1, 1, 1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_queue_in_memory_and_disabled_channels() {
+        let mut notifier = Notifier::new(unreachable_webhook(), None);
+        notifier.notify(&alert(1)).await;
+        assert_eq!(notifier.queue.len(), 1);
+
+        notifier.retry_due(Utc::now() + chrono::Duration::minutes(1)).await;
+        assert_eq!(notifier.queue[0].attempts, 2);
+
+        // Queued notifications for a channel that is no longer enabled are dropped
+        notifier.config.webhook = None;
+        notifier.retry_due(Utc::now() + chrono::Duration::hours(1)).await;
+        assert!(notifier.queue.is_empty());
+    }
+}
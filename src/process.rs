@@ -7,11 +7,14 @@
 //! 
 //! - **Process Discovery**: Enumerate all processes with full metadata
 //! - **Signal Control**: Send SIGTERM, SIGKILL, SIGSTOP, SIGCONT, etc.
-//! - **Resource Metrics**: CPU, memory, threads, file descriptors
+//! - **Resource Metrics**: CPU, memory, threads, open fds against `RLIMIT_NOFILE`
 //! - **Container Detection**: Identify containerized processes
-//! - **Network Tracking**: Connection counts per process
+//! - **Network Tracking**: Connection counts and TCP throughput per process
+//! - **Disk I/O**: Per-second read/write rates from `/proc/<pid>/io`
 //! - **User Information**: UID, GID, username resolution
 //! - **Real-time Updates**: Sub-second refresh capabilities
+//! - **Incremental Refresh**: Static fields are kept for known PIDs; fd, cgroup
+//!   and GPU lookups run per PID on a slower cadence (see [`ENRICHMENT_INTERVAL`])
 //! 
 //! ## Example
 //! 
@@ -35,10 +38,12 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use anyhow::{Context, Result};
-use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+use std::time::{Duration, Instant, SystemTime};
+use anyhow::Result;
+use crate::config::FeatureConfig;
+use crate::network::SocketBytes;
+use crate::procfs::{ticks_to_secs, ProcStat, ProcStatus};
+use sysinfo::{System, SystemExt};
 use users;
 use tracing::{debug, info, error};
 
@@ -57,8 +62,13 @@ pub struct ProcessInfo {
     pub memory_usage: u64, // in KB
     pub memory_percent: f32,
     pub status: String,
+    /// Start time in seconds since the Unix epoch, from the `starttime`
+    /// ticks of `/proc/<pid>/stat` and the boot time
     pub start_time: u64,
+    /// Time since start, on the boot clock
     pub running_time: Duration,
+    /// Cumulative CPU time consumed
+    pub cpu_time: CpuTime,
     pub uid: u32,
     pub gid: u32,
     pub threads: u32,
@@ -70,6 +80,115 @@ pub struct ProcessInfo {
     pub container_id: Option<String>,
     pub cgroup_memory_limit: Option<u64>,
     pub gpu_memory: Option<u64>,
+    /// Disk I/O rates since the previous refresh; `None` on a process's first
+    /// refresh or when `/proc/<pid>/io` is not readable (other users' processes)
+    pub disk_io: Option<DiskIo>,
+    /// TCP throughput since the previous refresh; `None` while network
+    /// monitoring is off, before two socket samples exist, or when the fd
+    /// table is not readable
+    pub network_io: Option<NetworkIo>,
+    /// Number of open file descriptors; `None` when the fd table is not readable
+    pub open_fds: Option<usize>,
+    /// Soft `RLIMIT_NOFILE`
+    pub fd_limit: Option<u64>,
+}
+
+/// Cumulative CPU time of a process in seconds, from `/proc/<pid>/stat`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTime {
+    /// Time scheduled in user mode
+    pub user: f64,
+    /// Time scheduled in kernel mode
+    pub system: f64,
+    /// User plus kernel time of children that have exited and been waited for
+    pub children: f64,
+}
+
+impl CpuTime {
+    pub fn from_stat(stat: &ProcStat) -> Self {
+        let children = (stat.cutime + stat.cstime).max(0) as u64;
+        Self {
+            user: ticks_to_secs(stat.utime),
+            system: ticks_to_secs(stat.stime),
+            children: ticks_to_secs(children),
+        }
+    }
+
+    /// User plus kernel time of the process itself, what `top` shows as TIME+
+    pub fn total(&self) -> f64 {
+        self.user + self.system
+    }
+}
+
+/// Per-second TCP throughput of a process, summed over its sockets
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetworkIo {
+    /// Bytes received per second
+    pub rx_bytes: f64,
+    /// Bytes sent and acknowledged by the peer per second
+    pub tx_bytes: f64,
+}
+
+/// Per-second disk I/O rates for a process, from two `/proc/<pid>/io` samples
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiskIo {
+    /// Bytes fetched from the storage layer per second
+    pub read_bytes: f64,
+    /// Bytes sent to the storage layer per second
+    pub write_bytes: f64,
+    /// read(2)-family syscalls per second
+    pub read_syscalls: f64,
+    /// write(2)-family syscalls per second
+    pub write_syscalls: f64,
+    /// Written bytes per second that never reached disk (e.g. truncated files)
+    pub cancelled_write_bytes: f64,
+}
+
+/// Raw cumulative counters from `/proc/<pid>/io`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct IoCounters {
+    read_bytes: u64,
+    write_bytes: u64,
+    syscr: u64,
+    syscw: u64,
+    cancelled_write_bytes: u64,
+}
+
+impl IoCounters {
+    fn parse(content: &str) -> Option<Self> {
+        let mut counters = IoCounters::default();
+        let mut found = 0;
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let field = match key {
+                "read_bytes" => &mut counters.read_bytes,
+                "write_bytes" => &mut counters.write_bytes,
+                "syscr" => &mut counters.syscr,
+                "syscw" => &mut counters.syscw,
+                "cancelled_write_bytes" => &mut counters.cancelled_write_bytes,
+                _ => continue,
+            };
+            *field = value.trim().parse().ok()?;
+            found += 1;
+        }
+        (found == 5).then_some(counters)
+    }
+
+    /// Rates between an earlier sample and this one
+    fn rates_since(&self, earlier: &IoCounters, elapsed: Duration) -> DiskIo {
+        let secs = elapsed.as_secs_f64();
+        if secs <= 0.0 {
+            return DiskIo::default();
+        }
+        let rate = |now: u64, then: u64| now.saturating_sub(then) as f64 / secs;
+        DiskIo {
+            read_bytes: rate(self.read_bytes, earlier.read_bytes),
+            write_bytes: rate(self.write_bytes, earlier.write_bytes),
+            read_syscalls: rate(self.syscr, earlier.syscr),
+            write_syscalls: rate(self.syscw, earlier.syscw),
+            cancelled_write_bytes: rate(self.cancelled_write_bytes, earlier.cancelled_write_bytes),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -81,15 +200,73 @@ pub enum SortColumn {
     MemoryUsage,
     MemoryPercent,
     StartTime,
+    CpuTime,
+    DiskRead,
+    DiskWrite,
+    ReadSyscalls,
+    WriteSyscalls,
+    CancelledWrite,
+    NetRx,
+    NetTx,
+}
+
+impl SortColumn {
+    /// Parse a column name as used in the config file and the API
+    /// (`pid`, `name`, `user`, `cpu`, `memory`, `memory_percent`, `start_time`,
+    /// `cpu_time`, `disk_read`, `disk_write`, `read_syscalls`, `write_syscalls`, `cancelled_write`,
+    /// `net_rx`, `net_tx`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "pid" => Some(SortColumn::Pid),
+            "name" => Some(SortColumn::Name),
+            "user" => Some(SortColumn::User),
+            "cpu" => Some(SortColumn::CpuUsage),
+            "memory" => Some(SortColumn::MemoryUsage),
+            "memory_percent" => Some(SortColumn::MemoryPercent),
+            "start_time" => Some(SortColumn::StartTime),
+            "cpu_time" => Some(SortColumn::CpuTime),
+            "disk_read" => Some(SortColumn::DiskRead),
+            "disk_write" => Some(SortColumn::DiskWrite),
+            "read_syscalls" => Some(SortColumn::ReadSyscalls),
+            "write_syscalls" => Some(SortColumn::WriteSyscalls),
+            "cancelled_write" => Some(SortColumn::CancelledWrite),
+            "net_rx" => Some(SortColumn::NetRx),
+            "net_tx" => Some(SortColumn::NetTx),
+            _ => None,
+        }
+    }
 }
 
+/// How often the expensive per-process data (fd and socket counts from the fd
+/// table, cgroup and container info, GPU memory) is gathered again for a known PID.
+/// New processes are always enriched on their first refresh.
+pub const ENRICHMENT_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Main process manager that maintains process state and provides control operations.
 /// 
-/// Uses sysinfo for cross-platform process discovery and /proc for Linux-specific details.
+/// Reads processes directly from /proc and uses sysinfo for system-wide CPU and memory figures.
 pub struct ProcessManager {
     system: System,
     processes: HashMap<u32, ProcessInfo>,
     last_update: SystemTime,
+    features: FeatureConfig,
+    /// When network and cgroup data were last gathered, per PID
+    enriched_at: HashMap<u32, Instant>,
+    /// GPU memory (MB) per PID from the last `nvidia-smi` run
+    gpu_memory: HashMap<u32, u64>,
+    gpu_scanned_at: Option<Instant>,
+    /// Resolved user names, by UID
+    usernames: HashMap<u32, String>,
+    /// Last `/proc/<pid>/io` sample per PID, for computing disk I/O rates
+    io_counters: HashMap<u32, (Instant, IoCounters)>,
+    /// Last CPU time sample (user plus kernel ticks) per PID, for computing CPU usage
+    cpu_ticks: HashMap<u32, (Instant, u64)>,
+    /// Socket inodes per PID, gathered with the other fd-table data
+    socket_inodes: HashMap<u32, Vec<u64>>,
+    /// Last `sock_diag` dump of TCP byte counters, by socket inode
+    socket_bytes: Option<(Instant, HashMap<u64, SocketBytes>)>,
+    /// Boot time in seconds since the Unix epoch, for process start times
+    boot_time: u64,
 }
 
 impl ProcessManager {
@@ -97,54 +274,192 @@ impl ProcessManager {
     /// 
     /// Initializes the system information gatherer and performs an initial refresh.
     pub fn new() -> Self {
-        let mut system = System::new_all();
-        system.refresh_all();
+        Self::with_features(FeatureConfig::default())
+    }
+
+    /// Create a process manager that only collects the enabled optional metrics.
+    ///
+    /// Network, container and GPU lookups are skipped entirely for disabled
+    /// features, which also makes each refresh cheaper.
+    pub fn with_features(features: FeatureConfig) -> Self {
+        let mut system = System::new();
+        system.refresh_cpu();
+        system.refresh_memory();
+        let boot_time = system.boot_time();
         
         Self {
             system,
             processes: HashMap::new(),
             last_update: SystemTime::now(),
+            features,
+            enriched_at: HashMap::new(),
+            gpu_memory: HashMap::new(),
+            gpu_scanned_at: None,
+            usernames: HashMap::new(),
+            io_counters: HashMap::new(),
+            cpu_ticks: HashMap::new(),
+            socket_inodes: HashMap::new(),
+            socket_bytes: None,
+            boot_time,
+        }
+    }
+
+    /// Replace the feature toggles used by subsequent refreshes.
+    ///
+    /// If anything changed, cached per-process data is dropped so the next
+    /// refresh collects exactly what the new toggles ask for.
+    pub fn set_features(&mut self, features: FeatureConfig) {
+        let changed = self.features.network_monitoring != features.network_monitoring
+            || self.features.container_detection != features.container_detection
+            || self.features.gpu_monitoring != features.gpu_monitoring;
+        self.features = features;
+        if changed {
+            self.invalidate();
         }
     }
 
+    /// Forget cached per-process data, so the next refresh rebuilds every
+    /// entry from scratch as if all processes were new.
+    pub fn invalidate(&mut self) {
+        self.processes.clear();
+        self.enriched_at.clear();
+        self.gpu_memory.clear();
+        self.gpu_scanned_at = None;
+        self.io_counters.clear();
+        self.cpu_ticks.clear();
+        self.socket_inodes.clear();
+        self.socket_bytes = None;
+    }
+
     /// Refresh process information from the system.
     /// 
     /// Scans all running processes and updates internal state with current metrics.
     /// Should be called periodically to keep data fresh.
     /// 
+    /// The refresh is incremental: a PID seen before with the same start time
+    /// and name keeps its user, command and ownership, and only its
+    /// `/proc/<pid>/stat` is read again, once, for all volatile metrics; CPU
+    /// usage is the change in its CPU time since the previous refresh. Socket counts and cgroup
+    /// info are gathered again per PID every [`ENRICHMENT_INTERVAL`], and GPU
+    /// memory comes from one `nvidia-smi` run on the same cadence. Disk I/O
+    /// rates are computed from `/proc/<pid>/io` against the previous sample,
+    /// and TCP throughput from one `sock_diag` dump per refresh, attributed to
+    /// processes through the socket inodes cached from their fd tables at the
+    /// last enrichment.
+    /// 
     /// # Returns
     /// 
     /// * `Ok(())` on successful refresh
     /// * `Err` if system information cannot be accessed
     pub fn refresh(&mut self) -> Result<()> {
         debug!("Starting process refresh");
-        let start = std::time::Instant::now();
+        let start = Instant::now();
         
-        self.system.refresh_processes();
+        // Processes come from /proc below; sysinfo only provides system-wide figures
         self.system.refresh_cpu();
         self.system.refresh_memory();
         
-        self.processes.clear();
-        let mut errors = 0;
+        if self.features.gpu_monitoring
+            && self.gpu_scanned_at.is_none_or(|t| t.elapsed() >= ENRICHMENT_INTERVAL)
+        {
+            self.gpu_memory = crate::gpu::get_nvidia_process_list()
+                .map(|list| list.into_iter().map(|s| (s.pid, s.gpu_memory_used)).collect())
+                .unwrap_or_default();
+            self.gpu_scanned_at = Some(start);
+        }
+        
+        // Byte counters of all TCP sockets, diffed against the previous dump
+        let socket_bytes = if self.features.network_monitoring {
+            crate::network::tcp_socket_bytes()
+                .map_err(|e| debug!("TCP socket accounting unavailable: {:#}", e))
+                .ok()
+        } else {
+            None
+        };
+        let previous_socket_bytes = self.socket_bytes.take();
         
-        for (pid, process) in self.system.processes() {
-            match self.extract_process_info(pid.as_u32(), process) {
-                Ok(process_info) => {
-                    self.processes.insert(pid.as_u32(), process_info);
+        let mut previous = std::mem::take(&mut self.processes);
+        let mut processes = HashMap::with_capacity(previous.len());
+        let mut reused = 0;
+        
+        let Self { system, features, enriched_at, gpu_memory, usernames, io_counters, cpu_ticks, socket_inodes, boot_time, .. } = self;
+        let total_memory = system.total_memory();
+        
+        for pid in list_pids()? {
+            let Ok(stat) = ProcStat::read(pid) else {
+                continue; // exited
+            };
+            let start_time = stat.start_time(*boot_time);
+            // A PID is only the same process if it started at the same time;
+            // a name change means it exec'd and its static fields are stale
+            let known = previous
+                .remove(&pid)
+                .filter(|p| p.start_time == start_time && p.name == stat.comm);
+            
+            // Counters left by an earlier process with this PID are meaningless
+            if known.is_none() {
+                io_counters.remove(&pid);
+                cpu_ticks.remove(&pid);
+            }
+            let enrich = known.is_none()
+                || enriched_at.get(&pid).is_none_or(|t| start.duration_since(*t) >= ENRICHMENT_INTERVAL);
+            
+            let mut info = match known {
+                Some(info) => {
+                    reused += 1;
+                    info
                 }
-                Err(e) => {
-                    debug!("Failed to extract info for PID {}: {}", pid.as_u32(), e);
-                    errors += 1;
+                None => new_process_info(pid, &stat, start_time, usernames),
+            };
+            update_process_info(&mut info, &stat, total_memory);
+            info.cpu_usage = cpu_ticks
+                .insert(pid, (start, stat.cpu_ticks()))
+                .map_or(0.0, |(at, earlier)| cpu_percent(stat.cpu_ticks().saturating_sub(earlier), start.duration_since(at)));
+            
+            info.disk_io = match read_io_counters(pid) {
+                Some(counters) => io_counters
+                    .insert(pid, (start, counters))
+                    .map(|(at, earlier)| counters.rates_since(&earlier, start.duration_since(at))),
+                None => {
+                    io_counters.remove(&pid);
+                    None
                 }
+            };
+            
+            if enrich {
+                match enrich_process_info(&mut info, features) {
+                    Some(inodes) => socket_inodes.insert(pid, inodes),
+                    None => socket_inodes.remove(&pid),
+                };
+                enriched_at.insert(pid, start);
             }
+            info.network_io = match (&socket_bytes, &previous_socket_bytes, socket_inodes.get(&pid)) {
+                (Some(current), Some((at, earlier)), Some(inodes)) => {
+                    Some(network_rates(inodes, current, earlier, start.duration_since(*at)))
+                }
+                _ => None,
+            };
+            info.gpu_memory = if features.gpu_monitoring {
+                gpu_memory.get(&pid).copied()
+            } else {
+                None
+            };
+            
+            processes.insert(pid, info);
         }
         
+        self.processes = processes;
+        self.enriched_at.retain(|pid, _| self.processes.contains_key(pid));
+        self.io_counters.retain(|pid, _| self.processes.contains_key(pid));
+        self.cpu_ticks.retain(|pid, _| self.processes.contains_key(pid));
+        self.socket_inodes.retain(|pid, _| self.processes.contains_key(pid));
+        self.socket_bytes = socket_bytes.map(|bytes| (start, bytes));
         self.last_update = SystemTime::now();
         let duration = start.elapsed();
         
         info!(
             process_count = self.processes.len(),
-            errors = errors,
+            reused = reused,
             duration_ms = duration.as_millis(),
             "Process refresh completed"
         );
@@ -152,131 +467,6 @@ impl ProcessManager {
         Ok(())
     }
 
-    /// Extract detailed process information from sysinfo Process.
-    /// 
-    /// Combines sysinfo data with /proc filesystem reads for complete process details.
-    fn extract_process_info(&self, pid: u32, process: &sysinfo::Process) -> Result<ProcessInfo> {
-        // Get user info - sysinfo v0.29 doesn't have uid/gid methods on Process
-        let user_id = self.get_process_uid(pid).unwrap_or(0);
-        let group_id = self.get_process_gid(pid).unwrap_or(0);
-        
-        let user = users::get_user_by_uid(user_id)
-            .map(|u| u.name().to_string_lossy().to_string())
-            .unwrap_or_else(|| user_id.to_string());
-
-        let start_time = process.start_time();
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        
-        let running_time = Duration::from_secs(now.saturating_sub(start_time));
-
-        // Try to get additional process info from /proc
-        let (priority, nice, threads) = self.get_proc_info(pid).unwrap_or((0, 0, 1));
-
-        // Get network and container info (optional)
-        let network_connections = crate::network::get_network_stats(pid).ok()
-            .map(|stats| stats.connections);
-        
-        let cgroup_info = crate::network::get_cgroup_info(pid).ok();
-        let is_container = cgroup_info.as_ref().map(|c| c.is_container).unwrap_or(false);
-        let container_id = cgroup_info.as_ref().and_then(|c| c.container_id.clone());
-        let cgroup_memory_limit = cgroup_info.and_then(|c| c.memory_limit);
-        
-        // Get GPU info if available (optional)
-        let gpu_memory = crate::gpu::get_nvidia_process_stats(pid).ok()
-            .map(|stats| stats.gpu_memory_used);
-
-        Ok(ProcessInfo {
-            pid,
-            ppid: process.parent().map(|p| p.as_u32()).unwrap_or(0),
-            name: process.name().to_string(),
-            command: process.cmd().join(" "),
-            user,
-            cpu_usage: process.cpu_usage(),
-            memory_usage: process.memory(),
-            memory_percent: (process.memory() as f32 / self.system.total_memory() as f32) * 100.0,
-            status: format!("{:?}", process.status()),
-            start_time,
-            running_time,
-            uid: user_id,
-            gid: group_id,
-            threads,
-            priority,
-            nice,
-            network_connections,
-            is_container,
-            container_id,
-            cgroup_memory_limit,
-            gpu_memory,
-        })
-    }
-
-    fn get_process_uid(&self, pid: u32) -> Result<u32> {
-        let status_path = format!("/proc/{}/status", pid);
-        if !Path::new(&status_path).exists() {
-            return Ok(0);
-        }
-
-        let content = fs::read_to_string(&status_path)
-            .context("Failed to read /proc/{pid}/status")?;
-        
-        for line in content.lines() {
-            if line.starts_with("Uid:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() > 1 {
-                    return Ok(parts[1].parse::<u32>().unwrap_or(0));
-                }
-            }
-        }
-        
-        Ok(0)
-    }
-
-    fn get_process_gid(&self, pid: u32) -> Result<u32> {
-        let status_path = format!("/proc/{}/status", pid);
-        if !Path::new(&status_path).exists() {
-            return Ok(0);
-        }
-
-        let content = fs::read_to_string(&status_path)
-            .context("Failed to read /proc/{pid}/status")?;
-        
-        for line in content.lines() {
-            if line.starts_with("Gid:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() > 1 {
-                    return Ok(parts[1].parse::<u32>().unwrap_or(0));
-                }
-            }
-        }
-        
-        Ok(0)
-    }
-
-    fn get_proc_info(&self, pid: u32) -> Result<(i32, i32, u32)> {
-        let stat_path = format!("/proc/{}/stat", pid);
-        if !Path::new(&stat_path).exists() {
-            return Ok((0, 0, 1));
-        }
-
-        let content = fs::read_to_string(&stat_path)
-            .context("Failed to read /proc/{pid}/stat")?;
-        
-        let fields: Vec<&str> = content.split_whitespace().collect();
-        
-        if fields.len() < 20 {
-            return Ok((0, 0, 1));
-        }
-
-        let priority = fields[17].parse::<i32>().unwrap_or(0);
-        let nice = fields[18].parse::<i32>().unwrap_or(0);
-        let threads = fields[19].parse::<u32>().unwrap_or(1);
-
-        Ok((priority, nice, threads))
-    }
-
     pub fn get_processes(&self) -> Vec<&ProcessInfo> {
         self.processes.values().collect()
     }
@@ -317,27 +507,26 @@ impl ProcessManager {
     /// 
     /// This function uses unsafe libc::kill() to send signals.
     pub fn kill_process(&self, pid: u32, signal: i32) -> Result<()> {
-        debug!("Attempting to send signal {} to PID {}", signal, pid);
-        
-        unsafe {
-            let result = libc::kill(pid as libc::pid_t, signal);
-            if result == -1 {
-                let errno = *libc::__errno_location();
-                error!(
-                    pid = pid,
-                    signal = signal,
-                    errno = errno,
-                    "Failed to send signal to process"
-                );
-                return Err(anyhow::anyhow!(
-                    "Failed to send signal {} to process {} (errno: {})", 
-                    signal, pid, errno
-                ));
-            }
-        }
-        
-        debug!("Successfully sent signal {} to PID {}", signal, pid);
-        Ok(())
+        send_signal(pid, signal)
+    }
+
+    /// Send a signal to a process and all of its descendants.
+    ///
+    /// Descendants are resolved from the last refresh; see [`signal_subtree`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Root of the subtree to signal
+    /// * `signal` - Signal number (e.g., libc::SIGTERM)
+    /// * `dry_run` - If true, only report which processes would be signalled
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<SignalOutcome>)` - One outcome per PID, in the order signalled
+    /// * `Err` if `pid` is not a known process
+    pub fn kill_process_tree(&self, pid: u32, signal: i32, dry_run: bool) -> Result<Vec<SignalOutcome>> {
+        let processes: Vec<ProcessInfo> = self.processes.values().cloned().collect();
+        signal_subtree(&processes, pid, signal, dry_run)
     }
 
     /// Filter processes based on criteria.
@@ -369,25 +558,7 @@ impl ProcessManager {
     /// Sorted vector of all processes
     pub fn sort_processes(&self, column: SortColumn, ascending: bool) -> Vec<ProcessInfo> {
         let mut processes: Vec<ProcessInfo> = self.processes.values().cloned().collect();
-        
-        processes.sort_by(|a, b| {
-            let cmp = match column {
-                SortColumn::Pid => a.pid.cmp(&b.pid),
-                SortColumn::Name => a.name.cmp(&b.name),
-                SortColumn::User => a.user.cmp(&b.user),
-                SortColumn::CpuUsage => a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal),
-                SortColumn::MemoryUsage => a.memory_usage.cmp(&b.memory_usage),
-                SortColumn::MemoryPercent => a.memory_percent.partial_cmp(&b.memory_percent).unwrap_or(std::cmp::Ordering::Equal),
-                SortColumn::StartTime => a.start_time.cmp(&b.start_time),
-            };
-            
-            if ascending {
-                cmp
-            } else {
-                cmp.reverse()
-            }
-        });
-        
+        sort_process_list(&mut processes, column, ascending);
         processes
     }
 
@@ -411,6 +582,15 @@ impl ProcessManager {
     }
 }
 
+/// Result of signalling a single process as part of a subtree operation
+#[derive(Debug, Clone)]
+pub struct SignalOutcome {
+    pub pid: u32,
+    pub name: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SystemInfo {
     pub total_memory: u64,
@@ -482,6 +662,338 @@ impl ProcessFilter {
     }
 }
 
+/// Static fields of a process seen for the first time; volatile metrics are
+/// filled in by [`update_process_info`]
+fn new_process_info(
+    pid: u32,
+    stat: &ProcStat,
+    start_time: u64,
+    usernames: &mut HashMap<u32, String>,
+) -> ProcessInfo {
+    let (uid, gid) = ProcStatus::read(pid)
+        .map(|status| (status.uid[0], status.gid[0]))
+        .unwrap_or((0, 0));
+    let user = usernames
+        .entry(uid)
+        .or_insert_with(|| {
+            users::get_user_by_uid(uid)
+                .map(|u| u.name().to_string_lossy().to_string())
+                .unwrap_or_else(|| uid.to_string())
+        })
+        .clone();
+    
+    ProcessInfo {
+        pid,
+        ppid: 0,
+        name: stat.comm.clone(),
+        command: read_command(pid),
+        user,
+        cpu_usage: 0.0,
+        memory_usage: 0,
+        memory_percent: 0.0,
+        status: String::new(),
+        start_time,
+        running_time: Duration::ZERO,
+        cpu_time: CpuTime::default(),
+        uid,
+        gid,
+        threads: 1,
+        priority: 0,
+        nice: 0,
+        network_connections: None,
+        is_container: false,
+        container_id: None,
+        cgroup_memory_limit: None,
+        gpu_memory: None,
+        disk_io: None,
+        network_io: None,
+        open_fds: None,
+        fd_limit: None,
+    }
+}
+
+/// Update the fields that change from one refresh to the next from the
+/// process's `/proc/<pid>/stat`; CPU usage is computed by the caller
+fn update_process_info(info: &mut ProcessInfo, stat: &ProcStat, total_memory: u64) {
+    info.ppid = stat.ppid;
+    info.memory_usage = stat.rss_bytes();
+    info.memory_percent = (info.memory_usage as f32 / total_memory as f32) * 100.0;
+    info.status = format!("{:?}", sysinfo::ProcessStatus::from(stat.state));
+    info.running_time = stat.running_time();
+    info.cpu_time = CpuTime::from_stat(stat);
+    info.threads = stat.num_threads.max(1) as u32;
+    info.priority = stat.priority as i32;
+    info.nice = stat.nice as i32;
+}
+
+/// CPU usage in percent of one core, from the CPU ticks used over `elapsed`
+fn cpu_percent(ticks: u64, elapsed: Duration) -> f32 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0.0;
+    }
+    (ticks_to_secs(ticks) / secs * 100.0) as f32
+}
+
+/// PIDs of all processes (not threads) currently in `/proc`
+fn list_pids() -> Result<Vec<u32>> {
+    let pids = fs::read_dir("/proc")?
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    Ok(pids)
+}
+
+/// Command line from `/proc/<pid>/cmdline`, arguments joined by spaces;
+/// empty for kernel threads
+fn read_command(pid: u32) -> String {
+    fs::read(format!("/proc/{}/cmdline", pid))
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(String::from_utf8_lossy)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default()
+}
+
+/// Gather the expensive optional data for one process: fd count and limit,
+/// socket count from the fd table and cgroup/container info.
+///
+/// Returns the process's socket inodes when network monitoring is enabled.
+fn enrich_process_info(info: &mut ProcessInfo, features: &FeatureConfig) -> Option<Vec<u64>> {
+    let inodes = if features.network_monitoring {
+        Some(crate::network::socket_inodes(info.pid))
+    } else {
+        None
+    };
+    info.network_connections = inodes.as_ref().map(|inodes| inodes.len());
+    info.open_fds = crate::fds::count_fds(info.pid);
+    info.fd_limit = crate::fds::fd_limit(info.pid);
+    
+    let cgroup_info = if features.container_detection {
+        crate::network::get_cgroup_info(info.pid).ok()
+    } else {
+        None
+    };
+    info.is_container = cgroup_info.as_ref().map(|c| c.is_container).unwrap_or(false);
+    info.container_id = cgroup_info.as_ref().and_then(|c| c.container_id.clone());
+    info.cgroup_memory_limit = cgroup_info.and_then(|c| c.memory_limit);
+    inodes
+}
+
+/// TCP throughput of the sockets in `inodes` between two `sock_diag` dumps.
+///
+/// A socket missing from the earlier dump was opened in between, so all of
+/// its bytes count towards this interval.
+fn network_rates(
+    inodes: &[u64],
+    current: &HashMap<u64, SocketBytes>,
+    earlier: &HashMap<u64, SocketBytes>,
+    elapsed: Duration,
+) -> NetworkIo {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return NetworkIo::default();
+    }
+    let (mut rx, mut tx) = (0u64, 0u64);
+    for inode in inodes {
+        if let Some(now) = current.get(inode) {
+            let then = earlier.get(inode).copied().unwrap_or_default();
+            rx += now.rx_bytes.saturating_sub(then.rx_bytes);
+            tx += now.tx_bytes.saturating_sub(then.tx_bytes);
+        }
+    }
+    NetworkIo {
+        rx_bytes: rx as f64 / secs,
+        tx_bytes: tx as f64 / secs,
+    }
+}
+
+/// Cumulative I/O counters from `/proc/<pid>/io`, if readable
+fn read_io_counters(pid: u32) -> Option<IoCounters> {
+    IoCounters::parse(&fs::read_to_string(format!("/proc/{}/io", pid)).ok()?)
+}
+
+/// Send a signal to a process.
+///
+/// This is what [`ProcessManager::kill_process`] does; it needs no process
+/// list, so callers that only hold a snapshot can use it directly.
+pub fn send_signal(pid: u32, signal: i32) -> Result<()> {
+    debug!("Attempting to send signal {} to PID {}", signal, pid);
+    
+    unsafe {
+        let result = libc::kill(pid as libc::pid_t, signal);
+        if result == -1 {
+            let errno = *libc::__errno_location();
+            error!(
+                pid = pid,
+                signal = signal,
+                errno = errno,
+                "Failed to send signal to process"
+            );
+            return Err(anyhow::anyhow!(
+                "Failed to send signal {} to process {} (errno: {})", 
+                signal, pid, errno
+            ));
+        }
+    }
+    
+    debug!("Successfully sent signal {} to PID {}", signal, pid);
+    Ok(())
+}
+
+/// `processes` with parent links re-read from `/proc`, so a subtree built
+/// from the result includes children forked since the sample was taken and
+/// leaves out processes that have exited. Processes missing from the sample
+/// are added with only their PID, parent and name filled in.
+pub fn with_current_parents(processes: &[ProcessInfo]) -> Vec<ProcessInfo> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return processes.to_vec();
+    };
+    let sampled: HashMap<u32, &ProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+
+    let mut current = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(stat) = ProcStat::read(pid) else {
+            continue; // exited
+        };
+        let mut info = match sampled.get(&pid) {
+            Some(process) => (*process).clone(),
+            None => ProcessInfo {
+                pid,
+                ppid: 0,
+                name: stat.comm.clone(),
+                command: String::new(),
+                user: String::new(),
+                cpu_usage: 0.0,
+                memory_usage: 0,
+                memory_percent: 0.0,
+                status: String::new(),
+                start_time: 0,
+                running_time: Duration::ZERO,
+                cpu_time: CpuTime::default(),
+                uid: 0,
+                gid: 0,
+                threads: 1,
+                priority: 0,
+                nice: 0,
+                network_connections: None,
+                is_container: false,
+                container_id: None,
+                cgroup_memory_limit: None,
+                gpu_memory: None,
+                disk_io: None,
+                network_io: None,
+                open_fds: None,
+                fd_limit: None,
+            },
+        };
+        info.ppid = stat.ppid;
+        current.push(info);
+    }
+    current.sort_by_key(|p| p.pid);
+    current
+}
+
+/// Send a signal to `pid` and all of its descendants in `processes`.
+///
+/// Targets are signalled leaves first, finishing with `pid` itself, so a
+/// parent never outlives the children it would otherwise reap or respawn.
+/// Failures on one PID do not stop the rest.
+///
+/// # Returns
+///
+/// * `Ok(Vec<SignalOutcome>)` - One outcome per PID, in the order signalled
+/// * `Err` if `pid` is not in `processes`
+pub fn signal_subtree(processes: &[ProcessInfo], pid: u32, signal: i32, dry_run: bool) -> Result<Vec<SignalOutcome>> {
+    let trees = crate::tree::ProcessTree::build_tree(processes);
+    let subtree = trees
+        .iter()
+        .find_map(|tree| tree.find_process(pid))
+        .ok_or_else(|| anyhow::anyhow!("Process {} not found", pid))?;
+
+    let targets = subtree.get_pids_leaves_first();
+    info!(
+        pid = pid,
+        signal = signal,
+        count = targets.len(),
+        dry_run = dry_run,
+        "Signalling process subtree"
+    );
+
+    let by_pid: HashMap<u32, &ProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+    let outcomes = targets
+        .into_iter()
+        .map(|target| {
+            let name = by_pid
+                .get(&target)
+                .map(|p| p.name.clone())
+                .unwrap_or_default();
+            let result = if dry_run {
+                Ok(())
+            } else {
+                send_signal(target, signal)
+            };
+            SignalOutcome {
+                pid: target,
+                name,
+                success: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            }
+        })
+        .collect();
+
+    Ok(outcomes)
+}
+
+/// Sort a list of processes in place by `column`.
+pub fn sort_process_list(processes: &mut [ProcessInfo], column: SortColumn, ascending: bool) {
+    processes.sort_by(|a, b| {
+        let cmp = match column {
+            SortColumn::Pid => a.pid.cmp(&b.pid),
+            SortColumn::Name => a.name.cmp(&b.name),
+            SortColumn::User => a.user.cmp(&b.user),
+            SortColumn::CpuUsage => a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal),
+            SortColumn::MemoryUsage => a.memory_usage.cmp(&b.memory_usage),
+            SortColumn::MemoryPercent => a.memory_percent.partial_cmp(&b.memory_percent).unwrap_or(std::cmp::Ordering::Equal),
+            SortColumn::StartTime => a.start_time.cmp(&b.start_time),
+            SortColumn::CpuTime => a.cpu_time.total().partial_cmp(&b.cpu_time.total()).unwrap_or(std::cmp::Ordering::Equal),
+            SortColumn::DiskRead => cmp_disk_io(a, b, |io| io.read_bytes),
+            SortColumn::DiskWrite => cmp_disk_io(a, b, |io| io.write_bytes),
+            SortColumn::ReadSyscalls => cmp_disk_io(a, b, |io| io.read_syscalls),
+            SortColumn::WriteSyscalls => cmp_disk_io(a, b, |io| io.write_syscalls),
+            SortColumn::CancelledWrite => cmp_disk_io(a, b, |io| io.cancelled_write_bytes),
+            SortColumn::NetRx => cmp_network_io(a, b, |io| io.rx_bytes),
+            SortColumn::NetTx => cmp_network_io(a, b, |io| io.tx_bytes),
+        };
+        
+        if ascending {
+            cmp
+        } else {
+            cmp.reverse()
+        }
+    });
+}
+
+/// Compare one disk I/O rate; processes without I/O data sort below any rate
+fn cmp_disk_io(a: &ProcessInfo, b: &ProcessInfo, rate: fn(&DiskIo) -> f64) -> std::cmp::Ordering {
+    a.disk_io.as_ref().map(rate)
+        .partial_cmp(&b.disk_io.as_ref().map(rate))
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Compare one network rate; processes without socket data sort below any rate
+fn cmp_network_io(a: &ProcessInfo, b: &ProcessInfo, rate: fn(&NetworkIo) -> f64) -> std::cmp::Ordering {
+    a.network_io.as_ref().map(rate)
+        .partial_cmp(&b.network_io.as_ref().map(rate))
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
 // Signal constants
 pub mod signals {
     pub const SIGTERM: i32 = 15;
@@ -498,6 +1010,25 @@ pub mod signals {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_with_current_parents_finds_new_children() {
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let me = std::process::id();
+
+        // An empty sample still yields the child and its parent link
+        let processes = with_current_parents(&[]);
+        let found = processes.iter().find(|p| p.pid == child.id()).map(|p| (p.ppid, p.name.clone()));
+        let outcomes = signal_subtree(&processes, me, 15, true).unwrap();
+        child.kill().ok();
+        child.wait().ok();
+
+        assert_eq!(found, Some((me, "sleep".to_string())));
+        let pids: Vec<u32> = outcomes.iter().map(|o| o.pid).collect();
+        assert!(pids.contains(&child.id()));
+        assert_eq!(pids.last(), Some(&me));
+    }
 
     #[test]
     fn test_process_manager_creation() {
@@ -514,6 +1045,105 @@ mod tests {
         assert!(manager.processes.len() > 0, "Should have at least one process");
     }
 
+    #[test]
+    fn test_incremental_refresh_reuses_known_processes() {
+        let features = FeatureConfig {
+            gpu_monitoring: false,
+            network_monitoring: true,
+            ..FeatureConfig::default()
+        };
+        let mut manager = ProcessManager::with_features(features.clone());
+        manager.refresh().unwrap();
+
+        let pid = std::process::id();
+        let first_enriched = manager.enriched_at[&pid];
+        let first = manager.get_process(pid).unwrap().clone();
+        assert!(first.network_connections.is_some());
+        assert!(first.open_fds.is_some_and(|n| n > 0));
+        assert!(first.fd_limit.is_some());
+
+        manager.refresh().unwrap();
+        let second = manager.get_process(pid).unwrap();
+        assert_eq!(second.command, first.command);
+        assert_eq!(second.user, first.user);
+        // Enrichment is not due again until ENRICHMENT_INTERVAL has passed
+        assert_eq!(manager.enriched_at[&pid], first_enriched);
+
+        // Turning a feature off drops cached data and its values
+        manager.set_features(FeatureConfig {
+            network_monitoring: false,
+            ..features
+        });
+        manager.refresh().unwrap();
+        assert!(manager.get_process(pid).unwrap().network_connections.is_none());
+    }
+
+    #[test]
+    fn test_cpu_time_from_stat() {
+        let ticks = crate::procfs::clock_ticks_per_sec();
+        let stat = ProcStat {
+            utime: 90 * ticks,
+            stime: 30 * ticks,
+            cutime: 5 * ticks as i64,
+            cstime: ticks as i64,
+            ..Default::default()
+        };
+        let cpu_time = CpuTime::from_stat(&stat);
+        assert_eq!(cpu_time.user, 90.0);
+        assert_eq!(cpu_time.system, 30.0);
+        assert_eq!(cpu_time.total(), 120.0);
+        assert_eq!(cpu_time.children, 6.0);
+    }
+
+    #[test]
+    fn test_cpu_usage_from_stat_deltas() {
+        let ticks = crate::procfs::clock_ticks_per_sec();
+        assert_eq!(cpu_percent(ticks / 2, Duration::from_secs(1)), 50.0);
+        assert_eq!(cpu_percent(ticks * 4, Duration::from_secs(2)), 200.0);
+        assert_eq!(cpu_percent(ticks, Duration::ZERO), 0.0);
+
+        let mut manager = ProcessManager::new();
+        manager.refresh().unwrap();
+        let pid = std::process::id();
+        assert_eq!(manager.get_process(pid).unwrap().cpu_usage, 0.0);
+
+        let busy_until = Instant::now() + Duration::from_millis(300);
+        while Instant::now() < busy_until {
+            std::hint::spin_loop();
+        }
+        manager.refresh().unwrap();
+        let own = manager.get_process(pid).unwrap();
+        assert!(own.cpu_usage > 0.0);
+        assert!(own.memory_usage > 0);
+        assert!(!own.command.is_empty());
+    }
+
+    #[test]
+    fn test_refresh_own_process_times() {
+        let mut manager = ProcessManager::new();
+        manager.refresh().unwrap();
+        let own = manager.get_process(std::process::id()).unwrap();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert!(own.start_time <= now + 1);
+        assert!(own.running_time.as_secs() <= now - own.start_time + 2);
+        assert!(own.cpu_time.total() > 0.0);
+        assert!(own.threads >= 1);
+        assert!((-20..=19).contains(&own.nice));
+    }
+
+    #[test]
+    fn test_kill_process_tree_dry_run() {
+        let mut manager = ProcessManager::new();
+        manager.refresh().unwrap();
+
+        let pid = std::process::id();
+        let outcomes = manager.kill_process_tree(pid, signals::SIGTERM, true).unwrap();
+        assert_eq!(outcomes.last().map(|o| o.pid), Some(pid));
+        assert!(outcomes.iter().all(|o| o.success));
+
+        assert!(manager.kill_process_tree(u32::MAX, signals::SIGTERM, true).is_err());
+    }
+
     #[test]
     fn test_signal_constants() {
         assert_eq!(signals::SIGTERM, 15);
@@ -537,6 +1167,85 @@ mod tests {
         assert_eq!(col1, col2);
     }
 
+    #[test]
+    fn test_sort_column_from_name() {
+        assert_eq!(SortColumn::from_name("cpu"), Some(SortColumn::CpuUsage));
+        assert_eq!(SortColumn::from_name("start_time"), Some(SortColumn::StartTime));
+        assert_eq!(SortColumn::from_name("disk_write"), Some(SortColumn::DiskWrite));
+        assert_eq!(SortColumn::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_io_counters_rates() {
+        let earlier = IoCounters::parse(
+            "rchar: 100\nwchar: 200\nsyscr: 10\nsyscw: 20\nread_bytes: 4096\nwrite_bytes: 8192\ncancelled_write_bytes: 0\n"
+        ).unwrap();
+        let later = IoCounters::parse(
+            "rchar: 900\nwchar: 900\nsyscr: 30\nsyscw: 60\nread_bytes: 12288\nwrite_bytes: 8192\ncancelled_write_bytes: 2048\n"
+        ).unwrap();
+        let io = later.rates_since(&earlier, Duration::from_secs(2));
+        assert_eq!(io.read_bytes, 4096.0);
+        assert_eq!(io.write_bytes, 0.0);
+        assert_eq!(io.read_syscalls, 10.0);
+        assert_eq!(io.write_syscalls, 20.0);
+        assert_eq!(io.cancelled_write_bytes, 1024.0);
+        assert!(IoCounters::parse("rchar: 1\n").is_none());
+    }
+
+    #[test]
+    fn test_socket_inodes_follow_enrichment_cadence() {
+        let mut manager = ProcessManager::with_features(FeatureConfig {
+            network_monitoring: true,
+            ..FeatureConfig::default()
+        });
+        manager.refresh().unwrap();
+        let pid = std::process::id();
+        let before = manager.socket_inodes[&pid].clone();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let inode = {
+            use std::os::fd::AsRawFd;
+            use std::os::unix::fs::MetadataExt;
+            std::fs::metadata(format!("/proc/{}/fd/{}", pid, listener.as_raw_fd())).unwrap().ino()
+        };
+
+        // The fd table is not walked again before enrichment is due
+        manager.refresh().unwrap();
+        assert_eq!(manager.socket_inodes[&pid], before);
+
+        let due = Instant::now().checked_sub(ENRICHMENT_INTERVAL).unwrap();
+        manager.enriched_at.insert(pid, due);
+        manager.refresh().unwrap();
+        assert!(manager.socket_inodes[&pid].contains(&inode));
+    }
+
+    #[test]
+    fn test_network_rates_count_new_sockets_in_full() {
+        let bytes = |rx, tx| SocketBytes { rx_bytes: rx, tx_bytes: tx };
+        let earlier = HashMap::from([(1, bytes(1000, 500)), (9, bytes(7, 7))]);
+        let current = HashMap::from([(1, bytes(3000, 900)), (2, bytes(400, 100)), (9, bytes(99, 99))]);
+        // Inode 3 has closed since, inode 9 belongs to another process
+        let io = network_rates(&[1, 2, 3], &current, &earlier, Duration::from_secs(2));
+        assert_eq!(io.rx_bytes, 1200.0);
+        assert_eq!(io.tx_bytes, 250.0);
+    }
+
+    #[test]
+    fn test_sort_by_disk_io_puts_unknown_last() {
+        let mut manager = ProcessManager::new();
+        manager.refresh().unwrap();
+        let mut processes = manager.sort_processes(SortColumn::Pid, true);
+        for (i, p) in processes.iter_mut().enumerate() {
+            p.disk_io = (i % 2 == 0).then(|| DiskIo { write_bytes: i as f64, ..DiskIo::default() });
+        }
+        sort_process_list(&mut processes, SortColumn::DiskWrite, false);
+        let known = processes.iter().take_while(|p| p.disk_io.is_some()).count();
+        assert!(processes[known..].iter().all(|p| p.disk_io.is_none()));
+        assert!(processes[..known].windows(2).all(|w| {
+            w[0].disk_io.unwrap().write_bytes >= w[1].disk_io.unwrap().write_bytes
+        }));
+    }
+
     #[test]
     fn test_get_system_info() {
         let manager = ProcessManager::new();
@@ -0,0 +1,519 @@
+//! # /proc Stat and Status Parsing
+//!
+//! Typed parsers for `/proc/<pid>/stat` and `/proc/<pid>/status`, shared by
+//! every module that needs kernel process counters.
+//!
+//! The `comm` field of `stat` is the executable name in parentheses and may
+//! itself contain spaces and parentheses (`(Web Content)`, `(a) (b)`), so the
+//! line cannot simply be split on whitespace: the name runs from the first
+//! `(` to the *last* `)`, and numbered fields follow.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use process_manager::procfs::{ProcStat, ProcStatus};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let stat = ProcStat::read(1234)?;
+//! println!("{} utime={} stime={} cpu={}", stat.comm, stat.utime, stat.stime, stat.processor);
+//! let status = ProcStatus::read(1234)?;
+//! println!("uid={} rss={:?} kB", status.uid[0], status.vm_rss);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::str::{FromStr, SplitWhitespace};
+use std::time::Duration;
+
+/// All fields of `/proc/<pid>/stat`, as documented in proc(5).
+///
+/// Times are in clock ticks (`sysconf(_SC_CLK_TCK)`), `rss` is in pages and
+/// `vsize` in bytes. Fields added by kernels newer than 2.6.24 (after
+/// `cguest_time`) are 0 when absent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcStat {
+    /// (1) Process or thread ID
+    pub pid: u32,
+    /// (2) Executable name, without the parentheses
+    pub comm: String,
+    /// (3) Single-letter state (`R`, `S`, `D`, `Z`, `T`, `t`, `X`, `I`, ...)
+    pub state: char,
+    /// (4) Parent PID
+    pub ppid: u32,
+    /// (5) Process group ID
+    pub pgrp: u32,
+    /// (6) Session ID
+    pub session: u32,
+    /// (7) Controlling terminal device number, 0 for none
+    pub tty_nr: i32,
+    /// (8) Foreground process group of the controlling terminal, -1 for none
+    pub tpgid: i32,
+    /// (9) Kernel flags word (`PF_*`)
+    pub flags: u32,
+    /// (10) Minor faults
+    pub minflt: u64,
+    /// (11) Minor faults of waited-for children
+    pub cminflt: u64,
+    /// (12) Major faults
+    pub majflt: u64,
+    /// (13) Major faults of waited-for children
+    pub cmajflt: u64,
+    /// (14) Time scheduled in user mode
+    pub utime: u64,
+    /// (15) Time scheduled in kernel mode
+    pub stime: u64,
+    /// (16) User time of waited-for children
+    pub cutime: i64,
+    /// (17) Kernel time of waited-for children
+    pub cstime: i64,
+    /// (18) Kernel priority; negative for real-time processes
+    pub priority: i64,
+    /// (19) Nice value, -20 to 19
+    pub nice: i64,
+    /// (20) Number of threads
+    pub num_threads: i64,
+    /// (21) Obsolete, always 0
+    pub itrealvalue: i64,
+    /// (22) Start time after boot
+    pub starttime: u64,
+    /// (23) Virtual memory size in bytes
+    pub vsize: u64,
+    /// (24) Resident set size in pages
+    pub rss: i64,
+    /// (25) Soft limit on the RSS in bytes
+    pub rsslim: u64,
+    /// (26) Address above which program text can run
+    pub startcode: u64,
+    /// (27) Address below which program text can run
+    pub endcode: u64,
+    /// (28) Address of the start (bottom) of the stack
+    pub startstack: u64,
+    /// (29) Current stack pointer
+    pub kstkesp: u64,
+    /// (30) Current instruction pointer
+    pub kstkeip: u64,
+    /// (31) Obsolete pending signal bitmap
+    pub signal: u64,
+    /// (32) Obsolete blocked signal bitmap
+    pub blocked: u64,
+    /// (33) Obsolete ignored signal bitmap
+    pub sigignore: u64,
+    /// (34) Obsolete caught signal bitmap
+    pub sigcatch: u64,
+    /// (35) Wait channel; 0 or a placeholder on modern kernels
+    pub wchan: u64,
+    /// (36) Not maintained
+    pub nswap: u64,
+    /// (37) Not maintained
+    pub cnswap: u64,
+    /// (38) Signal sent to the parent when this process dies
+    pub exit_signal: i32,
+    /// (39) CPU the process last ran on
+    pub processor: u32,
+    /// (40) Real-time priority, 1-99 for real-time policies, else 0
+    pub rt_priority: u32,
+    /// (41) Scheduling policy (`SCHED_*`)
+    pub policy: u32,
+    /// (42) Aggregated block I/O delays
+    pub delayacct_blkio_ticks: u64,
+    /// (43) Guest time
+    pub guest_time: u64,
+    /// (44) Guest time of waited-for children
+    pub cguest_time: i64,
+    /// (45) Address above which program data and BSS are placed
+    pub start_data: u64,
+    /// (46) Address below which program data and BSS are placed
+    pub end_data: u64,
+    /// (47) Address above which the heap can be expanded with brk
+    pub start_brk: u64,
+    /// (48) Address above which the command-line arguments are placed
+    pub arg_start: u64,
+    /// (49) Address below which the command-line arguments are placed
+    pub arg_end: u64,
+    /// (50) Address above which the environment is placed
+    pub env_start: u64,
+    /// (51) Address below which the environment is placed
+    pub env_end: u64,
+    /// (52) Exit status as reported by waitpid
+    pub exit_code: i32,
+}
+
+impl ProcStat {
+    /// Read `/proc/<pid>/stat`
+    pub fn read(pid: u32) -> Result<Self> {
+        read_file(&format!("/proc/{}/stat", pid), Self::parse)
+    }
+
+    /// Read `/proc/<pid>/task/<tid>/stat`, the stat of a single thread
+    pub fn read_task(pid: u32, tid: u32) -> Result<Self> {
+        read_file(&format!("/proc/{}/task/{}/stat", pid, tid), Self::parse)
+    }
+
+    /// Parse the contents of a `stat` file.
+    ///
+    /// # Returns
+    ///
+    /// * `Err` if the name is not parenthesised, or a field up to `policy` is
+    ///   missing or not a number
+    pub fn parse(content: &str) -> Result<Self> {
+        let open = content.find('(').context("Invalid stat format: missing '('")?;
+        let close = content.rfind(')').context("Invalid stat format: missing ')'")?;
+        if close < open {
+            return Err(anyhow!("Invalid stat format: unbalanced parentheses"));
+        }
+        let pid = content[..open]
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid stat pid: {:?}", content[..open].trim()))?;
+
+        let mut fields = Fields { iter: content[close + 1..].split_whitespace(), number: 2 };
+        let state: String = fields.next()?;
+        Ok(Self {
+            pid,
+            comm: content[open + 1..close].to_string(),
+            state: state.chars().next().unwrap_or('?'),
+            ppid: fields.next()?,
+            pgrp: fields.next()?,
+            session: fields.next()?,
+            tty_nr: fields.next()?,
+            tpgid: fields.next()?,
+            flags: fields.next()?,
+            minflt: fields.next()?,
+            cminflt: fields.next()?,
+            majflt: fields.next()?,
+            cmajflt: fields.next()?,
+            utime: fields.next()?,
+            stime: fields.next()?,
+            cutime: fields.next()?,
+            cstime: fields.next()?,
+            priority: fields.next()?,
+            nice: fields.next()?,
+            num_threads: fields.next()?,
+            itrealvalue: fields.next()?,
+            starttime: fields.next()?,
+            vsize: fields.next()?,
+            rss: fields.next()?,
+            rsslim: fields.next()?,
+            startcode: fields.next()?,
+            endcode: fields.next()?,
+            startstack: fields.next()?,
+            kstkesp: fields.next()?,
+            kstkeip: fields.next()?,
+            signal: fields.next()?,
+            blocked: fields.next()?,
+            sigignore: fields.next()?,
+            sigcatch: fields.next()?,
+            wchan: fields.next()?,
+            nswap: fields.next()?,
+            cnswap: fields.next()?,
+            exit_signal: fields.next()?,
+            processor: fields.next()?,
+            rt_priority: fields.next()?,
+            policy: fields.next()?,
+            delayacct_blkio_ticks: fields.next_or_default()?,
+            guest_time: fields.next_or_default()?,
+            cguest_time: fields.next_or_default()?,
+            start_data: fields.next_or_default()?,
+            end_data: fields.next_or_default()?,
+            start_brk: fields.next_or_default()?,
+            arg_start: fields.next_or_default()?,
+            arg_end: fields.next_or_default()?,
+            env_start: fields.next_or_default()?,
+            env_end: fields.next_or_default()?,
+            exit_code: fields.next_or_default()?,
+        })
+    }
+
+    /// User plus kernel time, in clock ticks
+    pub fn cpu_ticks(&self) -> u64 {
+        self.utime + self.stime
+    }
+
+    /// Resident set size in bytes
+    pub fn rss_bytes(&self) -> u64 {
+        self.rss.max(0) as u64 * page_size()
+    }
+
+    /// Start time in seconds since the Unix epoch, given the boot time (the
+    /// `btime` line of `/proc/stat`)
+    pub fn start_time(&self, boot_time: u64) -> u64 {
+        boot_time + self.starttime / clock_ticks_per_sec()
+    }
+
+    /// Time since the process started, measured on the boot clock so that
+    /// wall-clock changes do not affect it
+    pub fn running_time(&self) -> Duration {
+        uptime().saturating_sub(Duration::from_secs_f64(ticks_to_secs(self.starttime)))
+    }
+}
+
+/// Kernel clock ticks per second (`sysconf(_SC_CLK_TCK)`), the unit of the
+/// times in `stat`
+pub fn clock_ticks_per_sec() -> u64 {
+    // SAFETY: sysconf has no preconditions
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 { ticks as u64 } else { 100 }
+}
+
+/// Size of a memory page in bytes (`sysconf(_SC_PAGESIZE)`), the unit of `rss`
+pub fn page_size() -> u64 {
+    // SAFETY: sysconf has no preconditions
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 { size as u64 } else { 4096 }
+}
+
+/// Convert clock ticks to seconds
+pub fn ticks_to_secs(ticks: u64) -> f64 {
+    ticks as f64 / clock_ticks_per_sec() as f64
+}
+
+/// Time since boot, including time spent suspended (`CLOCK_BOOTTIME`, the
+/// clock `starttime` is measured on)
+pub fn uptime() -> Duration {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: ts is a valid, writable timespec
+    if unsafe { libc::clock_gettime(libc::CLOCK_BOOTTIME, &mut ts) } != 0 {
+        return Duration::ZERO;
+    }
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// Selected fields of `/proc/<pid>/status`, plus every raw `Key: value` pair.
+///
+/// Memory sizes are in kB and are `None` where the kernel omits them (kernel
+/// threads have no `Vm*` lines).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcStatus {
+    pub name: String,
+    /// Single-letter state, the `S` of `S (sleeping)`
+    pub state: char,
+    pub tgid: u32,
+    pub pid: u32,
+    pub ppid: u32,
+    pub tracer_pid: u32,
+    /// Real, effective, saved and filesystem user IDs
+    pub uid: [u32; 4],
+    /// Real, effective, saved and filesystem group IDs
+    pub gid: [u32; 4],
+    pub threads: Option<u32>,
+    pub vm_peak: Option<u64>,
+    pub vm_size: Option<u64>,
+    pub vm_hwm: Option<u64>,
+    pub vm_rss: Option<u64>,
+    pub vm_swap: Option<u64>,
+    pub voluntary_ctxt_switches: u64,
+    pub nonvoluntary_ctxt_switches: u64,
+    pub cpus_allowed_list: Option<String>,
+    /// Every line, keyed by the text before the first `:`, with the value trimmed
+    pub fields: HashMap<String, String>,
+}
+
+impl ProcStatus {
+    /// Read `/proc/<pid>/status`
+    pub fn read(pid: u32) -> Result<Self> {
+        read_file(&format!("/proc/{}/status", pid), |content| Ok(Self::parse(content)))
+    }
+
+    /// Read `/proc/<pid>/task/<tid>/status`, the status of a single thread
+    pub fn read_task(pid: u32, tid: u32) -> Result<Self> {
+        read_file(&format!("/proc/{}/task/{}/status", pid, tid), |content| Ok(Self::parse(content)))
+    }
+
+    /// Parse the contents of a `status` file; unknown or malformed lines are
+    /// kept in [`fields`](Self::fields) only
+    pub fn parse(content: &str) -> Self {
+        let fields: HashMap<String, String> = content
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+            .collect();
+
+        let kb = |key: &str| {
+            fields
+                .get(key)
+                .and_then(|v| v.trim_end_matches("kB").trim().parse().ok())
+        };
+        let ids = |key: &str| {
+            let mut ids = [0u32; 4];
+            if let Some(value) = fields.get(key) {
+                for (slot, id) in ids.iter_mut().zip(value.split_whitespace()) {
+                    *slot = id.parse().unwrap_or(0);
+                }
+            }
+            ids
+        };
+
+        Self {
+            name: fields.get("Name").cloned().unwrap_or_default(),
+            state: fields.get("State").and_then(|s| s.chars().next()).unwrap_or('?'),
+            tgid: parse_field(&fields, "Tgid").unwrap_or(0),
+            pid: parse_field(&fields, "Pid").unwrap_or(0),
+            ppid: parse_field(&fields, "PPid").unwrap_or(0),
+            tracer_pid: parse_field(&fields, "TracerPid").unwrap_or(0),
+            uid: ids("Uid"),
+            gid: ids("Gid"),
+            threads: parse_field(&fields, "Threads"),
+            vm_peak: kb("VmPeak"),
+            vm_size: kb("VmSize"),
+            vm_hwm: kb("VmHWM"),
+            vm_rss: kb("VmRSS"),
+            vm_swap: kb("VmSwap"),
+            voluntary_ctxt_switches: parse_field(&fields, "voluntary_ctxt_switches").unwrap_or(0),
+            nonvoluntary_ctxt_switches: parse_field(&fields, "nonvoluntary_ctxt_switches").unwrap_or(0),
+            cpus_allowed_list: fields.get("Cpus_allowed_list").cloned(),
+            fields,
+        }
+    }
+}
+
+fn parse_field<T: FromStr>(fields: &HashMap<String, String>, key: &str) -> Option<T> {
+    fields.get(key).and_then(|v| v.parse().ok())
+}
+
+fn read_file<T>(path: &str, parse: impl FnOnce(&str) -> Result<T>) -> Result<T> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+    parse(&content).with_context(|| format!("Failed to parse {}", path))
+}
+
+/// Numbered whitespace-separated fields following the `comm` of a stat line
+struct Fields<'a> {
+    iter: SplitWhitespace<'a>,
+    /// proc(5) number of the last field taken
+    number: usize,
+}
+
+impl Fields<'_> {
+    fn next<T: FromStr>(&mut self) -> Result<T> {
+        self.next_opt()?
+            .ok_or_else(|| anyhow!("Missing stat field {}", self.number))
+    }
+
+    fn next_or_default<T: FromStr + Default>(&mut self) -> Result<T> {
+        Ok(self.next_opt()?.unwrap_or_default())
+    }
+
+    fn next_opt<T: FromStr>(&mut self) -> Result<Option<T>> {
+        self.number += 1;
+        match self.iter.next() {
+            Some(raw) => raw
+                .parse()
+                .map(Some)
+                .map_err(|_| anyhow!("Invalid stat field {}: {:?}", self.number, raw)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat_name_with_space() {
+        let stat = ProcStat::parse(include_str!("../tests/fixtures/proc/stat_web_content")).unwrap();
+        assert_eq!(stat.pid, 48213);
+        assert_eq!(stat.comm, "Web Content");
+        assert_eq!(stat.state, 'S');
+        assert_eq!((stat.ppid, stat.pgrp, stat.session), (48011, 47990, 47990));
+        assert_eq!((stat.tty_nr, stat.tpgid), (0, -1));
+        assert_eq!((stat.minflt, stat.majflt), (2816345, 1204));
+        assert_eq!((stat.utime, stat.stime), (91237, 11872));
+        assert_eq!(stat.cpu_ticks(), 103109);
+        assert_eq!((stat.priority, stat.nice), (25, 5));
+        assert_eq!(stat.num_threads, 31);
+        assert_eq!(stat.starttime, 8817443);
+        assert_eq!(stat.vsize, 3129126912);
+        assert_eq!(stat.rss, 98304);
+        assert_eq!(stat.rsslim, u64::MAX);
+        assert_eq!(stat.exit_signal, 17);
+        assert_eq!((stat.processor, stat.rt_priority, stat.policy), (6, 0, 0));
+        assert_eq!(stat.delayacct_blkio_ticks, 12);
+        assert_eq!(stat.env_end, 140727473135582);
+        assert_eq!(stat.exit_code, 0);
+    }
+
+    #[test]
+    fn test_parse_stat_name_with_parentheses() {
+        let stat = ProcStat::parse(include_str!("../tests/fixtures/proc/stat_parenthesized")).unwrap();
+        assert_eq!(stat.pid, 9921);
+        assert_eq!(stat.comm, "a) (b) c");
+        assert_eq!(stat.state, 'R');
+        assert_eq!(stat.tty_nr, 34816);
+        assert_eq!((stat.utime, stat.stime), (7, 3));
+        // SCHED_RR at real-time priority 50
+        assert_eq!(stat.priority, -51);
+        assert_eq!((stat.processor, stat.rt_priority, stat.policy), (2, 50, 2));
+    }
+
+    #[test]
+    fn test_parse_stat_old_kernel() {
+        // Kernel thread on a kernel without the fields after cguest_time
+        let stat = ProcStat::parse(include_str!("../tests/fixtures/proc/stat_old_kernel")).unwrap();
+        assert_eq!(stat.comm, "kthreadd");
+        assert_eq!((stat.ppid, stat.vsize, stat.rss), (0, 0, 0));
+        assert_eq!(stat.stime, 12);
+        assert_eq!((stat.start_data, stat.env_end, stat.exit_code), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_start_time_from_ticks() {
+        let stat = ProcStat { starttime: 90 * clock_ticks_per_sec() + 1, ..Default::default() };
+        assert_eq!(stat.start_time(1_700_000_000), 1_700_000_090);
+        assert!((ticks_to_secs(clock_ticks_per_sec() * 3) - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_stat_errors() {
+        assert!(ProcStat::parse("garbage").is_err());
+        assert!(ProcStat::parse("1 (x) S 0 1 1").is_err());
+        assert!(ProcStat::parse("1 (x) S zero 1 1 0 -1 0 0 0 0 0 0 0 0 0 20 0 1 0 1 0 0 0").is_err());
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let status = ProcStatus::parse(include_str!("../tests/fixtures/proc/status_web_content"));
+        assert_eq!(status.name, "Web Content");
+        assert_eq!(status.state, 'S');
+        assert_eq!((status.tgid, status.pid, status.ppid), (48213, 48213, 48011));
+        assert_eq!(status.uid, [1000; 4]);
+        assert_eq!(status.gid, [1000; 4]);
+        assert_eq!(status.threads, Some(31));
+        assert_eq!(status.vm_peak, Some(3218004));
+        assert_eq!(status.vm_rss, Some(393216));
+        assert_eq!(status.vm_swap, Some(2048));
+        assert_eq!(status.voluntary_ctxt_switches, 1523904);
+        assert_eq!(status.nonvoluntary_ctxt_switches, 88123);
+        assert_eq!(status.cpus_allowed_list.as_deref(), Some("0-7"));
+        assert_eq!(status.fields.get("Seccomp").map(String::as_str), Some("2"));
+        assert_eq!(
+            status.fields.get("Speculation_Store_Bypass").map(String::as_str),
+            Some("thread force mitigated")
+        );
+    }
+
+    #[test]
+    fn test_parse_status_kernel_thread() {
+        let status = ProcStatus::parse("Name:\tkthreadd\nState:\tS (sleeping)\nPid:\t2\nPPid:\t0\nThreads:\t1\n");
+        assert_eq!(status.name, "kthreadd");
+        assert_eq!(status.vm_rss, None);
+        assert_eq!(status.uid, [0; 4]);
+        assert_eq!(status.voluntary_ctxt_switches, 0);
+    }
+
+    #[test]
+    fn test_read_own_process() {
+        let pid = std::process::id();
+        let stat = ProcStat::read(pid).unwrap();
+        let status = ProcStatus::read(pid).unwrap();
+        assert_eq!(stat.pid, pid);
+        assert_eq!(status.pid, pid);
+        assert_eq!(stat.ppid, status.ppid);
+        assert!(stat.num_threads >= 1);
+        assert!(stat.running_time() < uptime());
+        assert!(ProcStat::read_task(pid, pid).is_ok());
+        assert!(ProcStat::read(u32::MAX).is_err());
+    }
+}
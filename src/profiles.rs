@@ -63,6 +63,24 @@ pub struct ViewProfile {
     pub highlight_rules: Vec<HighlightRule>,
 }
 
+impl ViewProfile {
+    /// Profile with the default table columns, sorted by CPU usage and no filters
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            columns: crate::columns::DEFAULT_COLUMNS.iter().map(|c| c.to_string()).collect(),
+            sort_by: "cpu".to_string(),
+            sort_order: SortOrder::Descending,
+            filters: vec![],
+            refresh_interval: 1000,
+            tree_mode: false,
+            show_threads: false,
+            highlight_rules: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SortOrder {
     Ascending,
@@ -128,6 +146,12 @@ impl ViewProfileManager {
         Ok(manager)
     }
     
+    /// Default directory for saved profiles, next to the configuration file
+    /// (`~/.config/process-manager`)
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("process-manager"))
+    }
+    
     /// Load default built-in profiles
     fn load_default_profiles(&mut self) {
         // System Overview
@@ -175,8 +199,8 @@ impl ViewProfileManager {
                     "name".to_string(),
                     "memory".to_string(),
                     "memory_percent".to_string(),
-                    "rss".to_string(),
-                    "vms".to_string(),
+                    "threads".to_string(),
+                    "command".to_string(),
                 ],
                 sort_by: "memory".to_string(),
                 sort_order: SortOrder::Descending,
@@ -232,11 +256,11 @@ impl ViewProfileManager {
                 columns: vec![
                     "pid".to_string(),
                     "name".to_string(),
-                    "network_rx".to_string(),
-                    "network_tx".to_string(),
+                    "net_rx".to_string(),
+                    "net_tx".to_string(),
                     "connections".to_string(),
                 ],
-                sort_by: "network_tx".to_string(),
+                sort_by: "net_tx".to_string(),
                 sort_order: SortOrder::Descending,
                 filters: vec![
                     ProcessFilter {
@@ -429,6 +453,31 @@ mod tests {
         assert!(manager.get_profile("memory_intensive").is_some());
     }
 
+    #[test]
+    fn test_builtin_profile_columns_exist() {
+        let temp_dir = tempdir().unwrap();
+        let manager = ViewProfileManager::new(temp_dir.path().to_path_buf()).unwrap();
+        
+        for profile in manager.get_all_profiles().values() {
+            for column in &profile.columns {
+                assert!(crate::columns::find(column).is_some(), "{}: {}", profile.name, column);
+            }
+        }
+    }
+
+    #[test]
+    fn test_save_and_reload_columns() {
+        let temp_dir = tempdir().unwrap();
+        let mut manager = ViewProfileManager::new(temp_dir.path().to_path_buf()).unwrap();
+        
+        let mut profile = ViewProfile::new("Billing", "CPU accounting");
+        profile.columns = vec!["pid".to_string(), "cpu_time".to_string(), "name".to_string()];
+        manager.add_profile("billing".to_string(), profile).unwrap();
+        
+        let reloaded = ViewProfileManager::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(reloaded.get_profile("billing").unwrap().columns, vec!["pid", "cpu_time", "name"]);
+    }
+
     #[test]
     fn test_filter_matching() {
         let temp_dir = tempdir().unwrap();
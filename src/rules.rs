@@ -0,0 +1,615 @@
+//! # Alert Rule Expressions
+//!
+//! A small boolean language over process fields, used by `[[alerts.rules]]`
+//! and `GET /api/alerts/rules/check`.
+//!
+//! ```text
+//! name ~ "^postgres" and memory_percent > 20 and user != "root"
+//! (disk_write_rate > 50M or net_tx_rate > 10M) and not is_container
+//! ```
+//!
+//! ## Syntax
+//!
+//! ```text
+//! expr       := and ("or" and)*
+//! and        := unary ("and" unary)*
+//! unary      := "not" unary | "(" expr ")" | comparison | bool_field
+//! comparison := field op literal
+//! op         := ">" | ">=" | "<" | "<=" | "==" | "!=" | "~" | "!~"
+//! literal    := number[unit] | "string" | true | false
+//! ```
+//!
+//! - Numbers take an optional size suffix (`K`, `M`, `G`, `T`, powers of
+//!   1024) or duration suffix (`s`, `m`, `h`, `d`)
+//! - `~` and `!~` match a regex against a text field
+//! - A comparison with a value that is not available (e.g. `disk_read_rate`
+//!   before a process has two samples) is false
+//!
+//! Field names, operand types and regexes are checked when the expression is
+//! parsed, so [`Expr::parse`] doubles as the validator. [`FIELDS`] lists every
+//! field.
+
+use crate::process::ProcessInfo;
+use regex::Regex;
+use std::fmt;
+
+/// Type of a field's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Number,
+    Text,
+    Bool,
+}
+
+impl fmt::Display for FieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldKind::Number => write!(f, "number"),
+            FieldKind::Text => write!(f, "text"),
+            FieldKind::Bool => write!(f, "bool"),
+        }
+    }
+}
+
+/// Value of a field for one process
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value<'a> {
+    Number(f64),
+    Text(&'a str),
+    Bool(bool),
+}
+
+/// A process field usable in expressions
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub description: &'static str,
+    get: fn(&ProcessInfo) -> Option<Value<'_>>,
+}
+
+impl Field {
+    /// Value of this field for `process`, or `None` when not available
+    pub fn value<'a>(&self, process: &'a ProcessInfo) -> Option<Value<'a>> {
+        (self.get)(process)
+    }
+}
+
+fn number(value: impl Into<f64>) -> Option<Value<'static>> {
+    Some(Value::Number(value.into()))
+}
+
+/// Every field expressions can refer to
+pub static FIELDS: &[Field] = &[
+    Field { name: "pid", kind: FieldKind::Number, description: "Process ID", get: |p| number(p.pid) },
+    Field { name: "ppid", kind: FieldKind::Number, description: "Parent process ID", get: |p| number(p.ppid) },
+    Field { name: "name", kind: FieldKind::Text, description: "Process name", get: |p| Some(Value::Text(&p.name)) },
+    Field { name: "command", kind: FieldKind::Text, description: "Full command line", get: |p| Some(Value::Text(&p.command)) },
+    Field { name: "user", kind: FieldKind::Text, description: "Owner user name", get: |p| Some(Value::Text(&p.user)) },
+    Field { name: "status", kind: FieldKind::Text, description: "Process state, e.g. Run, Sleep, Zombie", get: |p| Some(Value::Text(&p.status)) },
+    Field { name: "uid", kind: FieldKind::Number, description: "Real user ID", get: |p| number(p.uid) },
+    Field { name: "gid", kind: FieldKind::Number, description: "Real group ID", get: |p| number(p.gid) },
+    Field { name: "cpu_usage", kind: FieldKind::Number, description: "CPU usage in percent of one core", get: |p| number(p.cpu_usage) },
+    Field { name: "memory_usage", kind: FieldKind::Number, description: "Resident memory in bytes", get: |p| number(p.memory_usage as f64) },
+    Field { name: "memory_percent", kind: FieldKind::Number, description: "Resident memory in percent of RAM", get: |p| number(p.memory_percent) },
+    Field { name: "start_time", kind: FieldKind::Number, description: "Start time, seconds since the Unix epoch", get: |p| number(p.start_time as f64) },
+    Field { name: "running_time", kind: FieldKind::Number, description: "Seconds since start", get: |p| number(p.running_time.as_secs_f64()) },
+    Field { name: "cpu_time", kind: FieldKind::Number, description: "Cumulative user plus system CPU seconds", get: |p| number(p.cpu_time.user + p.cpu_time.system) },
+    Field { name: "cpu_user_time", kind: FieldKind::Number, description: "Cumulative user CPU seconds", get: |p| number(p.cpu_time.user) },
+    Field { name: "cpu_system_time", kind: FieldKind::Number, description: "Cumulative system CPU seconds", get: |p| number(p.cpu_time.system) },
+    Field { name: "threads", kind: FieldKind::Number, description: "Number of threads", get: |p| number(p.threads) },
+    Field { name: "priority", kind: FieldKind::Number, description: "Kernel scheduling priority", get: |p| number(p.priority) },
+    Field { name: "nice", kind: FieldKind::Number, description: "Nice value", get: |p| number(p.nice) },
+    Field { name: "network_connections", kind: FieldKind::Number, description: "Open network sockets", get: |p| p.network_connections.and_then(|n| number(n as f64)) },
+    Field { name: "is_container", kind: FieldKind::Bool, description: "Runs inside a container", get: |p| Some(Value::Bool(p.is_container)) },
+    Field { name: "container_id", kind: FieldKind::Text, description: "Container ID", get: |p| p.container_id.as_deref().map(Value::Text) },
+    Field { name: "cgroup_memory_limit", kind: FieldKind::Number, description: "cgroup memory limit in bytes", get: |p| p.cgroup_memory_limit.and_then(|n| number(n as f64)) },
+    Field { name: "gpu_memory", kind: FieldKind::Number, description: "GPU memory in bytes", get: |p| p.gpu_memory.and_then(|mb| number((mb * 1024 * 1024) as f64)) },
+    Field { name: "disk_read_rate", kind: FieldKind::Number, description: "Disk reads in bytes per second", get: |p| p.disk_io.and_then(|io| number(io.read_bytes)) },
+    Field { name: "disk_write_rate", kind: FieldKind::Number, description: "Disk writes in bytes per second", get: |p| p.disk_io.and_then(|io| number(io.write_bytes)) },
+    Field { name: "net_rx_rate", kind: FieldKind::Number, description: "TCP bytes received per second", get: |p| p.network_io.and_then(|io| number(io.rx_bytes)) },
+    Field { name: "net_tx_rate", kind: FieldKind::Number, description: "TCP bytes sent per second", get: |p| p.network_io.and_then(|io| number(io.tx_bytes)) },
+    Field { name: "open_fds", kind: FieldKind::Number, description: "Open file descriptors", get: |p| p.open_fds.and_then(|n| number(n as f64)) },
+    Field { name: "fd_limit", kind: FieldKind::Number, description: "Soft open file limit", get: |p| p.fd_limit.and_then(|n| number(n as f64)) },
+    Field {
+        name: "fd_usage_percent",
+        kind: FieldKind::Number,
+        description: "Open file descriptors in percent of the soft limit",
+        get: |p| match (p.open_fds, p.fd_limit) {
+            (Some(open), Some(limit)) if limit > 0 => number(open as f64 * 100.0 / limit as f64),
+            _ => None,
+        },
+    },
+];
+
+/// Look up a field by name
+pub fn find_field(name: &str) -> Option<&'static Field> {
+    FIELDS.iter().find(|field| field.name == name)
+}
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Match,
+    NotMatch,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Match => "~",
+            Op::NotMatch => "!~",
+        }
+    }
+}
+
+/// Right-hand side of a comparison
+#[derive(Debug, Clone)]
+pub enum Literal {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Regex(Regex),
+}
+
+/// A parsed rule expression
+#[derive(Debug, Clone)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare {
+        field: &'static Field,
+        op: Op,
+        value: Literal,
+    },
+}
+
+impl Expr {
+    /// Parse and type-check an expression
+    pub fn parse(source: &str) -> Result<Expr, ParseError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, next: 0, end: source.len() };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some((token, position)) => Err(ParseError::new(position, format!("unexpected {}", token))),
+        }
+    }
+
+    /// Whether `process` satisfies the expression
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        match self {
+            Expr::And(left, right) => left.matches(process) && right.matches(process),
+            Expr::Or(left, right) => left.matches(process) || right.matches(process),
+            Expr::Not(inner) => !inner.matches(process),
+            Expr::Compare { field, op, value } => match (field.value(process), value) {
+                (Some(Value::Number(actual)), Literal::Number(expected)) => match op {
+                    Op::Gt => actual > *expected,
+                    Op::Ge => actual >= *expected,
+                    Op::Lt => actual < *expected,
+                    Op::Le => actual <= *expected,
+                    Op::Eq => actual == *expected,
+                    Op::Ne => actual != *expected,
+                    Op::Match | Op::NotMatch => false,
+                },
+                (Some(Value::Text(actual)), Literal::Text(expected)) => match op {
+                    Op::Eq => actual == expected,
+                    Op::Ne => actual != expected,
+                    _ => false,
+                },
+                (Some(Value::Text(actual)), Literal::Regex(regex)) => regex.is_match(actual) == (*op == Op::Match),
+                (Some(Value::Bool(actual)), Literal::Bool(expected)) => (actual == *expected) == (*op == Op::Eq),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Why an expression was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset in the expression
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self { position, message: message.into() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at column {}: {}", self.position + 1, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a duration such as `90`, `30s`, `5m`, `1h30m` or `2d` into seconds
+pub fn parse_duration(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("duration must not be empty".to_string());
+    }
+    if let Ok(secs) = text.parse::<u64>() {
+        return Ok(secs);
+    }
+
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            _ => return Err(format!("invalid duration {:?}: unknown unit '{}'", text, c)),
+        };
+        let amount: u64 = digits.parse()
+            .map_err(|_| format!("invalid duration {:?}: expected a number before '{}'", text, c))?;
+        total = amount
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("invalid duration {:?}: duration too large", text))?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(format!("invalid duration {:?}: missing unit after {}", text, digits));
+    }
+    Ok(total)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Text(String),
+    Op(Op),
+    LParen,
+    RParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Number(n) => write!(f, "number {}", n),
+            Token::Text(text) => write!(f, "string {:?}", text),
+            Token::Op(op) => write!(f, "'{}'", op.symbol()),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push((Token::LParen, start));
+                i += 1;
+            }
+            ')' => {
+                tokens.push((Token::RParen, start));
+                i += 1;
+            }
+            '>' | '<' | '=' | '!' | '~' => {
+                let (op, len) = match (c, next) {
+                    ('>', Some('=')) => (Op::Ge, 2),
+                    ('>', _) => (Op::Gt, 1),
+                    ('<', Some('=')) => (Op::Le, 2),
+                    ('<', _) => (Op::Lt, 1),
+                    ('=', Some('=')) => (Op::Eq, 2),
+                    ('!', Some('=')) => (Op::Ne, 2),
+                    ('!', Some('~')) => (Op::NotMatch, 2),
+                    ('~', _) => (Op::Match, 1),
+                    _ => return Err(ParseError::new(start, format!("unknown operator '{}'", c))),
+                };
+                tokens.push((Token::Op(op), start));
+                i += len;
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(ParseError::new(start, "unterminated string")),
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => {
+                            match chars.get(i + 1) {
+                                Some((_, escaped)) => text.push(*escaped),
+                                None => return Err(ParseError::new(start, "unterminated string")),
+                            }
+                            i += 2;
+                        }
+                        Some((_, c)) => {
+                            text.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push((Token::Text(text), start));
+                i += 1;
+            }
+            _ if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit() || n == '.')) || c == '.' => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end].1.is_ascii_digit() || chars[end].1 == '.') {
+                    end += 1;
+                }
+                let digits: String = chars[i..end].iter().map(|(_, c)| c).collect();
+                let mut value: f64 = digits.parse()
+                    .map_err(|_| ParseError::new(start, format!("invalid number {:?}", digits)))?;
+
+                let unit_start = end;
+                while end < chars.len() && chars[end].1.is_ascii_alphabetic() {
+                    end += 1;
+                }
+                let unit: String = chars[unit_start..end].iter().map(|(_, c)| c).collect();
+                value *= match unit.as_str() {
+                    "" => 1.0,
+                    "K" => 1024.0,
+                    "M" => 1024.0 * 1024.0,
+                    "G" => 1024.0 * 1024.0 * 1024.0,
+                    "T" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+                    "s" => 1.0,
+                    "m" => 60.0,
+                    "h" => 3600.0,
+                    "d" => 86400.0,
+                    _ => return Err(ParseError::new(chars[unit_start].0, format!("unknown unit {:?}", unit))),
+                };
+                tokens.push((Token::Number(value), start));
+                i = end;
+            }
+            _ if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end].1.is_ascii_alphanumeric() || chars[end].1 == '_') {
+                    end += 1;
+                }
+                let ident: String = chars[i..end].iter().map(|(_, c)| c).collect();
+                tokens.push((Token::Ident(ident), start));
+                i = end;
+            }
+            _ => return Err(ParseError::new(start, format!("unexpected character '{}'", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    /// Length of the source, reported for errors at the end of input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(&Token, usize)> {
+        self.tokens.get(self.next).map(|(token, position)| (token, *position))
+    }
+
+    fn advance(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn expect_more(&mut self, what: &str) -> Result<(Token, usize), ParseError> {
+        self.advance()
+            .ok_or_else(|| ParseError::new(self.end, format!("expected {}, found end of expression", what)))
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some((Token::Ident(ident), _)) if ident == word) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.and()?;
+        while self.keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.unary()?;
+        while self.keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.keyword("not") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+
+        match self.expect_more("a field, 'not' or '('")? {
+            (Token::LParen, position) => {
+                let expr = self.or()?;
+                match self.advance() {
+                    Some((Token::RParen, _)) => Ok(expr),
+                    Some((token, at)) => Err(ParseError::new(at, format!("expected ')', found {}", token))),
+                    None => Err(ParseError::new(position, "unclosed '('")),
+                }
+            }
+            (Token::Ident(name), position) => self.comparison(&name, position),
+            (token, position) => Err(ParseError::new(position, format!("expected a field, found {}", token))),
+        }
+    }
+
+    fn comparison(&mut self, name: &str, position: usize) -> Result<Expr, ParseError> {
+        let field = find_field(name)
+            .ok_or_else(|| ParseError::new(position, format!("unknown field '{}'", name)))?;
+
+        // A bool field on its own means `field == true`
+        let op = match self.peek() {
+            Some((Token::Op(op), _)) => *op,
+            _ if field.kind == FieldKind::Bool => {
+                return Ok(Expr::Compare { field, op: Op::Eq, value: Literal::Bool(true) });
+            }
+            _ => {
+                let (token, at) = self.expect_more("an operator")?;
+                return Err(ParseError::new(at, format!("expected an operator after '{}', found {}", name, token)));
+            }
+        };
+        self.next += 1;
+
+        let (token, at) = self.expect_more("a value")?;
+        let value = match (field.kind, op, token) {
+            (FieldKind::Text, Op::Match | Op::NotMatch, Token::Text(pattern)) => {
+                let regex = Regex::new(&pattern)
+                    .map_err(|e| ParseError::new(at, format!("invalid regex: {}", e)))?;
+                Literal::Regex(regex)
+            }
+            (FieldKind::Text, Op::Eq | Op::Ne, Token::Text(text)) => Literal::Text(text),
+            (FieldKind::Number, Op::Match | Op::NotMatch, _) => {
+                return Err(ParseError::new(at, format!("'{}' only applies to text fields; '{}' is a number", op.symbol(), name)));
+            }
+            (FieldKind::Number, _, Token::Number(n)) => Literal::Number(n),
+            (FieldKind::Bool, Op::Eq | Op::Ne, Token::Ident(word)) if word == "true" || word == "false" => {
+                Literal::Bool(word == "true")
+            }
+            (kind, _, token) => {
+                return Err(ParseError::new(at, format!("'{}' {} {} is not valid for a {} field", name, op.symbol(), token, kind)));
+            }
+        };
+
+        Ok(Expr::Compare { field, op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::{DiskIo, ProcessInfo};
+    use std::time::Duration;
+
+    fn postgres() -> ProcessInfo {
+        ProcessInfo {
+            pid: 4242,
+            ppid: 1,
+            name: "postgres".to_string(),
+            command: "postgres: checkpointer".to_string(),
+            user: "postgres".to_string(),
+            cpu_usage: 12.5,
+            memory_usage: 3 * 1024 * 1024 * 1024,
+            memory_percent: 25.0,
+            status: "Sleep".to_string(),
+            start_time: 1_700_000_000,
+            running_time: Duration::from_secs(7200),
+            cpu_time: Default::default(),
+            uid: 999,
+            gid: 999,
+            threads: 1,
+            priority: 20,
+            nice: 0,
+            network_connections: Some(3),
+            is_container: false,
+            container_id: None,
+            cgroup_memory_limit: None,
+            gpu_memory: None,
+            disk_io: Some(DiskIo { write_bytes: 80.0 * 1024.0 * 1024.0, ..Default::default() }),
+            network_io: None,
+            open_fds: Some(900),
+            fd_limit: Some(1024),
+        }
+    }
+
+    fn matches(source: &str) -> bool {
+        Expr::parse(source).unwrap().matches(&postgres())
+    }
+
+    #[test]
+    fn test_example_expression() {
+        assert!(matches(r#"name ~ "^postgres" and memory_percent > 20 and user != "root""#));
+        assert!(!matches(r#"name ~ "^postgres" and memory_percent > 30"#));
+    }
+
+    #[test]
+    fn test_operators_and_precedence() {
+        assert!(matches("cpu_usage >= 12.5 and cpu_usage <= 12.5 and threads == 1"));
+        assert!(matches("pid < 10 or pid > 100 and nice == 0"));
+        assert!(!matches("(pid < 10 or pid > 100) and nice != 0"));
+        assert!(matches(r#"not user == "root" and command !~ "^nginx""#));
+        assert!(matches("not is_container and is_container == false"));
+    }
+
+    #[test]
+    fn test_units_and_derived_fields() {
+        assert!(matches("memory_usage > 2G and memory_usage < 4G"));
+        assert!(matches("disk_write_rate > 50M and running_time > 1h"));
+        assert!(matches("fd_usage_percent > 85"));
+        // `gpu_memory` is collected in MB but compared in bytes
+        let mut gpu = postgres();
+        gpu.gpu_memory = Some(1536);
+        assert!(Expr::parse("gpu_memory > 1G and gpu_memory < 2G").unwrap().matches(&gpu));
+        // Not available: comparisons are false either way
+        assert!(!matches("net_tx_rate > 0"));
+        assert!(!matches("net_tx_rate <= 0"));
+        assert!(!matches(r#"container_id == """#));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let error = |source: &str| Expr::parse(source).unwrap_err();
+
+        assert_eq!(error("nmae == \"x\"").message, "unknown field 'nmae'");
+        assert_eq!(error("cpu_usage > 5 and").position, 17);
+        assert_eq!(error("cpu_usage > \"high\"").position, 12);
+        assert!(error("cpu_usage ~ \"1\"").message.contains("only applies to text"));
+        assert!(error("name > 5").message.contains("not valid for a text field"));
+        assert!(error("name ~ \"(\"").message.starts_with("invalid regex"));
+        assert!(error("(cpu_usage > 5").message.contains("unclosed"));
+        assert!(error("cpu_usage > 5 )").message.contains("unexpected"));
+        assert!(error("memory_usage > 5X").message.contains("unknown unit"));
+        assert!(error("name == \"x").message.contains("unterminated"));
+        assert_eq!(error("cpu_usage = 5").to_string(), "at column 11: unknown operator '='");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(90));
+        assert_eq!(parse_duration("30s"), Ok(30));
+        assert_eq!(parse_duration("5m"), Ok(300));
+        assert_eq!(parse_duration("1h30m"), Ok(5400));
+        assert_eq!(parse_duration("2d"), Ok(172800));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("999999999999999999d").unwrap_err().contains("too large"));
+        assert!(parse_duration("18446744073709551615s1s").unwrap_err().contains("too large"));
+    }
+}
@@ -0,0 +1,300 @@
+//! # Alert Silences and Maintenance Windows
+//!
+//! Both stop matching alerts from being notified. Suppressed alerts are still
+//! recorded by the [`crate::alerts::AlertManager`], with `suppressed_by`
+//! naming the silence or window.
+//!
+//! - A [`Silence`] covers a fixed time range, e.g. the next two hours
+//! - A [`MaintenanceWindow`] recurs every day, or on selected weekdays, in the
+//!   server's local time, e.g. a nightly backup from 02:00 to 03:00
+//!
+//! Which alerts they cover is set by an [`AlertMatcher`].
+
+use crate::alerts::Alert;
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Longest a single silence may last; recurring mutes belong in a [`MaintenanceWindow`]
+pub const MAX_SILENCE_DAYS: i64 = 30;
+
+/// Selects alerts by rule, PID, process name or user; unset fields match any alert
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AlertMatcher {
+    /// Rule name, e.g. `cpu` or `bookmark:nginx:exit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// Matches process names containing this string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_name: Option<String>,
+    /// Owner of the process
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+impl AlertMatcher {
+    /// Whether no field is set, so every alert matches
+    pub fn is_empty(&self) -> bool {
+        self.rule.is_none() && self.pid.is_none() && self.process_name.is_none() && self.user.is_none()
+    }
+
+    pub fn matches(&self, alert: &Alert) -> bool {
+        self.rule.as_ref().is_none_or(|rule| *rule == alert.rule)
+            && self.pid.is_none_or(|pid| pid == alert.pid)
+            && self.process_name.as_ref().is_none_or(|name| alert.process_name.contains(name.as_str()))
+            && self.user.as_ref().is_none_or(|user| *user == alert.user)
+    }
+}
+
+impl fmt::Display for AlertMatcher {
+    /// E.g. `rule=cpu user=backup`, or `any alert` when no field is set
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(ref rule) = self.rule {
+            parts.push(format!("rule={}", rule));
+        }
+        if let Some(pid) = self.pid {
+            parts.push(format!("pid={}", pid));
+        }
+        if let Some(ref name) = self.process_name {
+            parts.push(format!("name~{}", name));
+        }
+        if let Some(ref user) = self.user {
+            parts.push(format!("user={}", user));
+        }
+        if parts.is_empty() {
+            write!(f, "any alert")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+/// Suppresses matching alerts between `starts_at` and `ends_at`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Silence {
+    pub id: i64,
+    #[serde(flatten)]
+    pub matcher: AlertMatcher,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// Who created the silence
+    pub created_by: String,
+    #[serde(default)]
+    pub comment: String,
+}
+
+impl Silence {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.ends_at <= now
+    }
+}
+
+/// Suppresses matching alerts during a daily time range, in local time
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceWindow {
+    /// Set for windows added through the API; `None` for windows from the
+    /// config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub name: String,
+    /// Start time, `HH:MM`
+    pub start: String,
+    /// End time, `HH:MM`; earlier than `start` for windows spanning midnight
+    pub end: String,
+    /// Weekdays the window starts on (`mon` to `sun`); every day when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+    #[serde(flatten)]
+    pub matcher: AlertMatcher,
+}
+
+impl MaintenanceWindow {
+    /// Check the name, times and weekdays
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        let start = parse_time(&self.start)?;
+        let end = parse_time(&self.end)?;
+        if start == end {
+            return Err(format!("start and end are both {}", self.start));
+        }
+        for day in &self.days {
+            parse_weekday(day)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the local time `now` falls in the window. Invalid windows are
+    /// never active.
+    pub fn is_active_at(&self, now: NaiveDateTime) -> bool {
+        let (Ok(start), Ok(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+        let time = now.time();
+        let started_on = if start < end {
+            if time < start || time >= end {
+                return false;
+            }
+            now.date()
+        } else if time >= start {
+            now.date()
+        } else if time < end {
+            // Spans midnight and started yesterday
+            match now.date().pred_opt() {
+                Some(date) => date,
+                None => return false,
+            }
+        } else {
+            return false;
+        };
+        self.days.is_empty()
+            || self.days.iter().any(|day| parse_weekday(day) == Ok(started_on.weekday()))
+    }
+}
+
+fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(text, "%H:%M")
+        .map_err(|_| format!("{:?} is not a time of day (HH:MM)", text))
+}
+
+fn parse_weekday(text: &str) -> Result<Weekday, String> {
+    text.parse::<Weekday>()
+        .map_err(|_| format!("{:?} is not a weekday (mon to sun)", text))
+}
+
+/// What suppresses `alert` at `now`, e.g. `silence 3` or
+/// `maintenance window 'nightly-backup'`
+pub fn suppression(
+    alert: &Alert,
+    silences: &[Silence],
+    windows: &[MaintenanceWindow],
+    now: DateTime<Utc>,
+) -> Option<String> {
+    if let Some(silence) = silences.iter().find(|s| s.is_active(now) && s.matcher.matches(alert)) {
+        return Some(format!("silence {}", silence.id));
+    }
+    let local = now.with_timezone(&Local).naive_local();
+    windows.iter()
+        .find(|w| w.is_active_at(local) && w.matcher.matches(alert))
+        .map(|w| format!("maintenance window '{}'", w.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::{AlertSeverity, AlertType};
+    use chrono::NaiveDate;
+
+    fn alert(rule: &str, name: &str, user: &str) -> Alert {
+        Alert {
+            id: 1,
+            alert_type: AlertType::HighCpu,
+            severity: AlertSeverity::Warning,
+            rule: rule.to_string(),
+            process_name: name.to_string(),
+            pid: 42,
+            user: user.to_string(),
+            message: String::new(),
+            timestamp: Utc::now(),
+            value: None,
+            threshold: None,
+            lifecycle: None,
+            acknowledged: None,
+            suppressed_by: None,
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2026-06-01 is a Monday
+        NaiveDate::from_ymd_opt(2026, 6, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_matcher() {
+        let batch = alert("cpu", "pg_dump", "backup");
+        assert!(AlertMatcher::default().matches(&batch));
+        let matcher = AlertMatcher {
+            process_name: Some("pg_".to_string()),
+            user: Some("backup".to_string()),
+            ..Default::default()
+        };
+        assert!(matcher.matches(&batch));
+        assert!(!matcher.matches(&alert("cpu", "pg_dump", "root")));
+        assert!(!AlertMatcher { rule: Some("memory".to_string()), ..Default::default() }.matches(&batch));
+        assert!(!AlertMatcher { pid: Some(7), ..Default::default() }.matches(&batch));
+        assert_eq!(matcher.to_string(), "name~pg_ user=backup");
+        assert_eq!(AlertMatcher::default().to_string(), "any alert");
+    }
+
+    #[test]
+    fn test_maintenance_window() {
+        let mut window = MaintenanceWindow {
+            id: None,
+            name: "nightly-backup".to_string(),
+            start: "02:00".to_string(),
+            end: "03:00".to_string(),
+            days: Vec::new(),
+            matcher: AlertMatcher::default(),
+        };
+        assert!(window.validate().is_ok());
+        assert!(window.is_active_at(at(1, 2, 0)));
+        assert!(window.is_active_at(at(1, 2, 59)));
+        assert!(!window.is_active_at(at(1, 3, 0)));
+        assert!(!window.is_active_at(at(1, 1, 59)));
+
+        // Spanning midnight, Saturday night only
+        window.start = "23:00".to_string();
+        window.end = "01:30".to_string();
+        window.days = vec!["sat".to_string()];
+        assert!(window.is_active_at(at(6, 23, 30)));
+        assert!(window.is_active_at(at(7, 1, 0)));
+        assert!(!window.is_active_at(at(7, 23, 30)));
+        assert!(!window.is_active_at(at(6, 1, 0)));
+
+        window.days.push("someday".to_string());
+        assert!(window.validate().unwrap_err().contains("not a weekday"));
+        window.days.clear();
+        window.end = "25:00".to_string();
+        assert!(window.validate().is_err());
+        assert!(!window.is_active_at(at(6, 23, 30)));
+    }
+
+    #[test]
+    fn test_suppression() {
+        let now = Utc::now();
+        let silence = Silence {
+            id: 3,
+            matcher: AlertMatcher { rule: Some("cpu".to_string()), ..Default::default() },
+            starts_at: now - chrono::Duration::minutes(5),
+            ends_at: now + chrono::Duration::hours(1),
+            created_by: "ops".to_string(),
+            comment: String::new(),
+        };
+        let local = now.with_timezone(&Local);
+        let window = MaintenanceWindow {
+            id: Some(1),
+            name: "backup".to_string(),
+            start: (local - chrono::Duration::hours(1)).format("%H:%M").to_string(),
+            end: (local + chrono::Duration::hours(1)).format("%H:%M").to_string(),
+            days: Vec::new(),
+            matcher: AlertMatcher { user: Some("backup".to_string()), ..Default::default() },
+        };
+
+        let silenced = alert("cpu", "make", "dev");
+        assert_eq!(suppression(&silenced, std::slice::from_ref(&silence), &[], now).as_deref(), Some("silence 3"));
+        assert_eq!(suppression(&silenced, std::slice::from_ref(&silence), &[], silence.ends_at), None);
+        assert_eq!(suppression(&alert("memory", "make", "dev"), &[silence], &[], now), None);
+
+        let batch = alert("memory", "pg_dump", "backup");
+        assert_eq!(suppression(&batch, &[], std::slice::from_ref(&window), now).as_deref(), Some("maintenance window 'backup'"));
+        assert_eq!(suppression(&batch, &[], &[window], now + chrono::Duration::hours(2)), None);
+    }
+}
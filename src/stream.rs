@@ -0,0 +1,185 @@
+//! # Live Process Stream
+//!
+//! Incremental updates for the `GET /api/processes/stream` Server-Sent Events
+//! endpoint.
+//!
+//! ## Features
+//!
+//! - **Snapshot then deltas**: a client first gets its full filtered list, then
+//!   only the PIDs that appeared, disappeared, or changed since the last tick
+//! - **Field-level changes**: a changed process carries only the fields whose
+//!   values differ
+//! - **Per-client views**: each subscriber diffs against its own filtered view,
+//!   so filters and limits work as they do for `GET /api/processes`
+//!
+//! ## Wire Format
+//!
+//! ```text
+//! event: snapshot
+//! data: {"timestamp":1700000000,"total":245,"processes":[...]}
+//!
+//! event: update
+//! data: {"timestamp":1700000002,"total":246,"added":[...],"removed":[4242],"changed":[{"pid":1,"fields":{"cpu_usage":0.5}}]}
+//! ```
+
+use crate::api::ApiProcessInfo;
+use actix_web::web::Bytes;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Full filtered process list, sent when a client connects
+#[derive(Debug, Serialize)]
+pub struct ProcessSnapshot<'a> {
+    pub timestamp: i64,
+    /// Number of processes on the host before filtering
+    pub total: usize,
+    pub processes: &'a [ApiProcessInfo],
+}
+
+/// Fields of one process that changed since the previous tick
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ProcessChange {
+    pub pid: u32,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Difference between two views of the process list
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ProcessDelta {
+    pub timestamp: i64,
+    /// Number of processes on the host before filtering
+    pub total: usize,
+    pub added: Vec<serde_json::Value>,
+    pub removed: Vec<u32>,
+    pub changed: Vec<ProcessChange>,
+}
+
+impl ProcessDelta {
+    /// Compute what changed between `previous` and `current`.
+    ///
+    /// Processes are matched by PID. Ordering is not part of the delta; clients
+    /// re-sort on their side.
+    pub fn between(previous: &[ApiProcessInfo], current: &[ApiProcessInfo], timestamp: i64, total: usize) -> Self {
+        let previous: HashMap<u32, serde_json::Value> = previous.iter().map(|p| (p.pid, to_value(p))).collect();
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        let mut seen = HashSet::with_capacity(current.len());
+
+        for process in current {
+            seen.insert(process.pid);
+            let value = to_value(process);
+            match previous.get(&process.pid) {
+                None => added.push(value),
+                Some(old) => {
+                    let fields = changed_fields(old, &value);
+                    if !fields.is_empty() {
+                        changed.push(ProcessChange { pid: process.pid, fields });
+                    }
+                }
+            }
+        }
+
+        let mut removed: Vec<u32> = previous.keys().filter(|pid| !seen.contains(pid)).copied().collect();
+        removed.sort_unstable();
+
+        Self {
+            timestamp,
+            total,
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    /// True if nothing was added, removed or changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Encode one Server-Sent Event
+pub fn sse_event<T: Serialize>(event: &str, data: &T) -> Bytes {
+    let json = serde_json::to_string(data).unwrap_or_else(|_| "{}".to_string());
+    Bytes::from(format!("event: {}\ndata: {}\n\n", event, json))
+}
+
+/// SSE comment line, sent on ticks without changes so dead connections are noticed
+pub fn sse_keepalive() -> Bytes {
+    Bytes::from_static(b": keepalive\n\n")
+}
+
+fn to_value(process: &ApiProcessInfo) -> serde_json::Value {
+    serde_json::to_value(process).unwrap_or(serde_json::Value::Null)
+}
+
+fn changed_fields(old: &serde_json::Value, new: &serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    let mut fields = serde_json::Map::new();
+    if let (Some(old), Some(new)) = (old.as_object(), new.as_object()) {
+        for (key, value) in new {
+            if old.get(key) != Some(value) {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, cpu: f32) -> ApiProcessInfo {
+        ApiProcessInfo {
+            pid,
+            ppid: 1,
+            name: format!("proc{}", pid),
+            user: "test".to_string(),
+            cpu_usage: cpu,
+            memory_usage: 1024,
+            memory_percent: 0.1,
+            state: "Running".to_string(),
+            command: String::new(),
+            start_time: 0,
+            cpu_time_secs: 0.0,
+            cpu_user_secs: 0.0,
+            cpu_system_secs: 0.0,
+            cpu_children_secs: 0.0,
+            network_connections: None,
+            is_container: false,
+            container_id: None,
+            gpu_memory: None,
+            net_rx_bytes_per_sec: None,
+            net_tx_bytes_per_sec: None,
+            open_fds: None,
+            fd_limit: None,
+        }
+    }
+
+    #[test]
+    fn test_delta_added_removed_changed() {
+        let previous = vec![process(1, 0.0), process(2, 5.0), process(3, 1.0)];
+        let current = vec![process(1, 0.0), process(2, 7.5), process(4, 0.0)];
+
+        let delta = ProcessDelta::between(&previous, &current, 100, 3);
+        assert_eq!(delta.added.len(), 1);
+        assert_eq!(delta.added[0]["pid"], 4);
+        assert_eq!(delta.removed, vec![3]);
+        assert_eq!(delta.changed.len(), 1);
+        assert_eq!(delta.changed[0].pid, 2);
+        assert_eq!(delta.changed[0].fields.len(), 1);
+        assert_eq!(delta.changed[0].fields["cpu_usage"], 7.5);
+    }
+
+    #[test]
+    fn test_delta_empty_when_unchanged() {
+        let list = vec![process(1, 2.0), process(2, 3.0)];
+        assert!(ProcessDelta::between(&list, &list, 0, 2).is_empty());
+    }
+
+    #[test]
+    fn test_sse_event_format() {
+        let bytes = sse_event("update", &serde_json::json!({"removed": [1]}));
+        assert_eq!(&bytes[..], b"event: update\ndata: {\"removed\":[1]}\n\n");
+    }
+}
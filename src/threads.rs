@@ -0,0 +1,219 @@
+//! # Per-Thread Monitoring
+//!
+//! Enumerates the threads of a process from `/proc/<pid>/task/<tid>`, with
+//! per-thread CPU usage, state, last CPU and context switches. Useful for
+//! finding the one hot thread in a JVM, tokio runtime or thread pool.
+//!
+//! ## Features
+//!
+//! - **Thread Identity**: TID and thread name (`comm`, settable with `prctl`)
+//! - **CPU Usage**: Per-thread CPU% between two samples
+//! - **Placement**: The CPU each thread last ran on
+//! - **Context Switches**: Voluntary (blocking) and involuntary (preempted)
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use process_manager::threads::sample_threads;
+//! use std::time::Duration;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let threads = sample_threads(1234, Duration::from_millis(250))?;
+//! for thread in threads.iter().take(5) {
+//!     println!("{:>7} {:<16} {:>5.1}%", thread.tid, thread.name, thread.cpu_usage);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::procfs::{clock_ticks_per_sec, ProcStat, ProcStatus};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// One thread of a process
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    /// Thread name (`comm`), which may differ from the process name
+    pub name: String,
+    /// Single-letter scheduler state (`R`, `S`, `D`, `Z`, ...)
+    pub state: char,
+    /// CPU usage since the previous sample, 100% = one core; 0 on the first sample
+    pub cpu_usage: f32,
+    /// CPU the thread last ran on
+    pub last_cpu: Option<u32>,
+    pub voluntary_ctxt_switches: u64,
+    pub nonvoluntary_ctxt_switches: u64,
+}
+
+/// Raw per-thread counters from one read of `/proc/<pid>/task/<tid>`
+struct ThreadSample {
+    name: String,
+    state: char,
+    /// utime + stime, in clock ticks
+    cpu_ticks: u64,
+    last_cpu: Option<u32>,
+    voluntary_ctxt_switches: u64,
+    nonvoluntary_ctxt_switches: u64,
+}
+
+/// Computes per-thread CPU usage from successive samples.
+///
+/// Keeps the previous sample of every thread of each sampled process; the
+/// first sample of a thread reports 0% CPU.
+#[derive(Debug, Default)]
+pub struct ThreadSampler {
+    /// pid -> (time of the sample, tid -> CPU ticks)
+    previous: HashMap<u32, (Instant, HashMap<u32, u64>)>,
+}
+
+impl ThreadSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the threads of `pid`, ordered by CPU usage (highest first), then TID.
+    ///
+    /// # Returns
+    ///
+    /// * `Err` if the task directory cannot be read (no such process)
+    pub fn sample(&mut self, pid: u32) -> Result<Vec<ThreadInfo>> {
+        let samples = read_threads(pid)?;
+        let now = Instant::now();
+        let ticks_per_sec = clock_ticks_per_sec() as f64;
+
+        let previous = self.previous.get(&pid);
+        let mut threads: Vec<ThreadInfo> = samples
+            .iter()
+            .map(|(tid, sample)| {
+                let cpu_usage = previous
+                    .and_then(|(at, ticks)| {
+                        let before = *ticks.get(tid)?;
+                        let elapsed = now.duration_since(*at).as_secs_f64();
+                        (elapsed > 0.0).then(|| {
+                            sample.cpu_ticks.saturating_sub(before) as f64 / ticks_per_sec / elapsed * 100.0
+                        })
+                    })
+                    .unwrap_or(0.0);
+                ThreadInfo {
+                    tid: *tid,
+                    name: sample.name.clone(),
+                    state: sample.state,
+                    cpu_usage: cpu_usage as f32,
+                    last_cpu: sample.last_cpu,
+                    voluntary_ctxt_switches: sample.voluntary_ctxt_switches,
+                    nonvoluntary_ctxt_switches: sample.nonvoluntary_ctxt_switches,
+                }
+            })
+            .collect();
+        threads.sort_by(|a, b| {
+            b.cpu_usage
+                .partial_cmp(&a.cpu_usage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.tid.cmp(&b.tid))
+        });
+
+        let ticks = samples.iter().map(|(tid, s)| (*tid, s.cpu_ticks)).collect();
+        self.previous.insert(pid, (now, ticks));
+        Ok(threads)
+    }
+
+    /// Drop the samples of processes no longer being watched
+    pub fn retain(&mut self, keep: impl Fn(u32) -> bool) {
+        self.previous.retain(|pid, _| keep(*pid));
+    }
+}
+
+/// Sample the threads of `pid` twice, `interval` apart, for one-shot CPU usage.
+///
+/// Blocks for `interval`.
+pub fn sample_threads(pid: u32, interval: Duration) -> Result<Vec<ThreadInfo>> {
+    let mut sampler = ThreadSampler::new();
+    sampler.sample(pid)?;
+    std::thread::sleep(interval);
+    sampler.sample(pid)
+}
+
+/// Read every thread of `pid`; threads that exit while being read are skipped
+fn read_threads(pid: u32) -> Result<Vec<(u32, ThreadSample)>> {
+    debug!("Reading threads of pid {}", pid);
+    let entries = fs::read_dir(format!("/proc/{}/task", pid))
+        .with_context(|| format!("Failed to read /proc/{}/task", pid))?;
+
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            let tid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let stat = ProcStat::read_task(pid, tid).ok()?;
+            let status = ProcStatus::read_task(pid, tid).ok();
+            Some((tid, ThreadSample::new(&stat, status.as_ref())))
+        })
+        .collect())
+}
+
+impl ThreadSample {
+    /// Counters from a task's `stat` and, when readable, its `status`
+    fn new(stat: &ProcStat, status: Option<&ProcStatus>) -> Self {
+        Self {
+            name: stat.comm.clone(),
+            state: stat.state,
+            cpu_ticks: stat.cpu_ticks(),
+            last_cpu: Some(stat.processor),
+            voluntary_ctxt_switches: status.map_or(0, |s| s.voluntary_ctxt_switches),
+            nonvoluntary_ctxt_switches: status.map_or(0, |s| s.nonvoluntary_ctxt_switches),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_sample_from_proc() {
+        let stat = ProcStat::parse(
+            "4242 (tokio-rt (worker)) R 1 4242 4242 0 -1 4194368 100 0 0 0 \
+             250 50 0 0 20 0 8 0 12345 1000000 500 18446744073709551615 \
+             1 1 0 0 0 0 0 0 0 0 0 0 17 3 0 0 0 0 0",
+        )
+        .unwrap();
+        let status = ProcStatus::parse("Name:\tjava\nState:\tS (sleeping)\nvoluntary_ctxt_switches:\t1523\nnonvoluntary_ctxt_switches:\t42\n");
+
+        let sample = ThreadSample::new(&stat, Some(&status));
+        assert_eq!(sample.name, "tokio-rt (worker)");
+        assert_eq!(sample.state, 'R');
+        assert_eq!(sample.cpu_ticks, 300);
+        assert_eq!(sample.last_cpu, Some(3));
+        assert_eq!((sample.voluntary_ctxt_switches, sample.nonvoluntary_ctxt_switches), (1523, 42));
+
+        let sample = ThreadSample::new(&stat, None);
+        assert_eq!((sample.voluntary_ctxt_switches, sample.nonvoluntary_ctxt_switches), (0, 0));
+    }
+
+    #[test]
+    fn test_sample_own_threads() {
+        let pid = std::process::id();
+        let worker = std::thread::Builder::new()
+            .name("lpm-test-spin".to_string())
+            .spawn(|| {
+                let start = Instant::now();
+                while start.elapsed() < Duration::from_millis(400) {
+                    std::hint::spin_loop();
+                }
+            })
+            .unwrap();
+
+        let threads = sample_threads(pid, Duration::from_millis(200)).unwrap();
+        worker.join().unwrap();
+
+        assert!(threads.iter().any(|t| t.tid == pid));
+        let spinner = threads.iter().find(|t| t.name == "lpm-test-spin").unwrap();
+        assert!(spinner.cpu_usage > 10.0, "spinning thread at {}%", spinner.cpu_usage);
+        assert!(spinner.last_cpu.is_some());
+        assert!(sample_threads(u32::MAX, Duration::ZERO).is_err());
+    }
+}
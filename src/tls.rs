@@ -0,0 +1,225 @@
+//! # API Server TLS
+//!
+//! Builds the rustls server configuration used by the REST API when
+//! `[api.tls]` is enabled.
+//!
+//! ## Features
+//!
+//! - **Certificate files**: PEM certificate chain and private key
+//! - **Mutual TLS**: require client certificates signed by a configured CA
+//! - **Self-signed mode**: generate a certificate for local use on first start
+//!   and reuse it afterwards, so clients only need to trust it once
+//!
+//! ## Example
+//!
+//! ```toml
+//! [api.tls]
+//! enabled = true
+//! cert_path = "/etc/process-manager/cert.pem"
+//! key_path = "/etc/process-manager/key.pem"
+//! client_ca_path = "/etc/process-manager/clients-ca.pem"   # optional, enables mTLS
+//! ```
+
+use crate::config::TlsConfig;
+use anyhow::{anyhow, Context, Result};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Build the server configuration described by `tls`.
+///
+/// # Arguments
+///
+/// * `tls` - The `[api.tls]` settings (must be enabled)
+/// * `bind_address` - Added to the subject names of a self-signed certificate
+///
+/// # Returns
+///
+/// A rustls configuration ready to pass to the HTTP server
+pub fn load_server_config(tls: &TlsConfig, bind_address: &str) -> Result<ServerConfig> {
+    let (cert_path, key_path) = match (&tls.cert_path, &tls.key_path) {
+        (Some(cert), Some(key)) => (PathBuf::from(cert), PathBuf::from(key)),
+        _ if tls.self_signed => ensure_self_signed(&self_signed_dir(), bind_address)?,
+        _ => return Err(anyhow!("api.tls requires cert_path and key_path, or self_signed = true")),
+    };
+
+    let certs = load_certs(&cert_path)?;
+    let key = load_private_key(&key_path)?;
+    info!(cert = ?cert_path, fingerprint = %fingerprint(&certs[0]), "Loaded TLS certificate");
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("Failed to select TLS protocol versions")?;
+
+    let builder = if let Some(ref ca_path) = tls.client_ca_path {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(Path::new(ca_path))? {
+            roots.add(cert).context("Invalid client CA certificate")?;
+        }
+        let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+            .build()
+            .context("Failed to build client certificate verifier")?;
+        info!(client_ca = %ca_path, "Mutual TLS enabled, client certificates are required");
+        builder.with_client_cert_verifier(verifier)
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    builder
+        .with_single_cert(certs, key)
+        .context("TLS certificate and private key do not match")
+}
+
+/// Where self-signed certificates are kept
+pub fn self_signed_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("process-manager")
+        .join("tls")
+}
+
+/// Return the self-signed certificate and key in `dir`, generating them first
+/// if they do not exist yet.
+///
+/// The certificate covers `localhost`, `127.0.0.1`, `::1`, the host name and
+/// the bind address. The key file is created with mode 0600.
+pub fn ensure_self_signed(dir: &Path, bind_address: &str) -> Result<(PathBuf, PathBuf)> {
+    let cert_path = dir.join("self-signed-cert.pem");
+    let key_path = dir.join("self-signed-key.pem");
+
+    if cert_path.exists() && key_path.exists() {
+        return Ok((cert_path, key_path));
+    }
+
+    let mut names = vec!["localhost".to_string(), "127.0.0.1".to_string(), "::1".to_string()];
+    if let Ok(host) = hostname::get() {
+        names.push(host.to_string_lossy().to_string());
+    }
+    if !bind_address.is_empty() && bind_address != "0.0.0.0" && bind_address != "::" {
+        names.push(bind_address.to_string());
+    }
+    names.sort();
+    names.dedup();
+
+    let certified = rcgen::generate_simple_self_signed(names.clone())
+        .context("Failed to generate self-signed certificate")?;
+
+    fs::create_dir_all(dir).context("Failed to create TLS directory")?;
+    fs::write(&cert_path, certified.cert.pem()).context("Failed to write self-signed certificate")?;
+    write_private(&key_path, certified.key_pair.serialize_pem())?;
+
+    warn!(cert = ?cert_path, names = ?names, "Generated self-signed TLS certificate for local use");
+    println!("Generated self-signed certificate: {}", cert_path.display());
+    println!("  SHA-256 fingerprint: {}", fingerprint(certified.cert.der()));
+
+    Ok((cert_path, key_path))
+}
+
+fn write_private(path: &Path, contents: String) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .context("Failed to write self-signed private key")?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let file = fs::File::open(path).with_context(|| format!("Failed to open certificate {:?}", path))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse certificate {:?}", path))?;
+
+    if certs.is_empty() {
+        return Err(anyhow!("No certificates found in {:?}", path));
+    }
+    Ok(certs)
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let file = fs::File::open(path).with_context(|| format!("Failed to open private key {:?}", path))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .with_context(|| format!("Failed to parse private key {:?}", path))?
+        .ok_or_else(|| anyhow!("No private key found in {:?}", path))
+}
+
+/// Colon-separated SHA-256 fingerprint of a DER certificate
+fn fingerprint(cert: &CertificateDer<'_>) -> String {
+    Sha256::digest(cert.as_ref())
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_signed_is_generated_once_and_loads() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (cert, key) = ensure_self_signed(dir.path(), "127.0.0.1").unwrap();
+        let first = fs::read_to_string(&cert).unwrap();
+        let (cert_again, _) = ensure_self_signed(dir.path(), "127.0.0.1").unwrap();
+        assert_eq!(first, fs::read_to_string(cert_again).unwrap());
+
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(&key).unwrap().permissions().mode() & 0o777, 0o600);
+
+        let tls = TlsConfig {
+            enabled: true,
+            cert_path: Some(cert.to_string_lossy().to_string()),
+            key_path: Some(key.to_string_lossy().to_string()),
+            client_ca_path: None,
+            self_signed: false,
+        };
+        assert!(load_server_config(&tls, "127.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn test_mutual_tls_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert, key) = ensure_self_signed(dir.path(), "127.0.0.1").unwrap();
+
+        let ca = rcgen::generate_simple_self_signed(vec!["clients".to_string()]).unwrap();
+        let ca_path = dir.path().join("ca.pem");
+        fs::write(&ca_path, ca.cert.pem()).unwrap();
+
+        let tls = TlsConfig {
+            enabled: true,
+            cert_path: Some(cert.to_string_lossy().to_string()),
+            key_path: Some(key.to_string_lossy().to_string()),
+            client_ca_path: Some(ca_path.to_string_lossy().to_string()),
+            self_signed: false,
+        };
+        assert!(load_server_config(&tls, "127.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn test_missing_files_are_reported() {
+        let tls = TlsConfig {
+            enabled: true,
+            cert_path: Some("/nonexistent/cert.pem".to_string()),
+            key_path: Some("/nonexistent/key.pem".to_string()),
+            client_ca_path: None,
+            self_signed: false,
+        };
+        let err = load_server_config(&tls, "127.0.0.1").unwrap_err();
+        assert!(err.to_string().contains("cert.pem"));
+    }
+}
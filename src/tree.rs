@@ -1,5 +1,5 @@
 use crate::process::ProcessInfo;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, info};
 
 #[derive(Debug, Clone)]
@@ -19,7 +19,7 @@ impl ProcessTree {
         // Build maps for quick lookup
         for process in processes {
             process_map.insert(process.pid, process.clone());
-            children_map.entry(process.ppid).or_insert_with(Vec::new).push(process.pid);
+            children_map.entry(process.ppid).or_default().push(process.pid);
         }
 
         // Find root processes (processes with no parent or parent not in our list)
@@ -111,6 +111,111 @@ impl ProcessTree {
         }
         descendants
     }
+
+    /// PIDs of this process and all descendants, deepest first.
+    ///
+    /// Children always come before their parent and the root is last, which is
+    /// the order to signal a subtree in so that no child is orphaned mid-way.
+    pub fn get_pids_leaves_first(&self) -> Vec<u32> {
+        let mut pids = Vec::new();
+        self.collect_leaves_first(&mut pids);
+        pids
+    }
+
+    fn collect_leaves_first(&self, pids: &mut Vec<u32>) {
+        for child in &self.children {
+            child.collect_leaves_first(pids);
+        }
+        pids.push(self.process.pid);
+    }
+
+    /// Total CPU usage of this process and all of its descendants
+    pub fn subtree_cpu_usage(&self) -> f32 {
+        self.process.cpu_usage + self.children.iter().map(|child| child.subtree_cpu_usage()).sum::<f32>()
+    }
+
+    /// Total memory usage of this process and all of its descendants
+    pub fn subtree_memory_usage(&self) -> u64 {
+        self.process.memory_usage + self.children.iter().map(|child| child.subtree_memory_usage()).sum::<u64>()
+    }
+
+    /// Flatten the tree into display rows, skipping the children of collapsed nodes.
+    ///
+    /// Each row carries its indentation guides (`│`, `├─`, `└─`) and the
+    /// CPU/memory totals of the whole subtree, including hidden descendants.
+    pub fn flatten_visible(&self, collapsed: &HashSet<u32>) -> Vec<TreeRow> {
+        let mut result = Vec::new();
+        self.flatten_visible_recursive(collapsed, "", true, &mut result);
+        result
+    }
+
+    fn flatten_visible_recursive(
+        &self,
+        collapsed: &HashSet<u32>,
+        parent_guides: &str,
+        is_last: bool,
+        result: &mut Vec<TreeRow>,
+    ) {
+        let (prefix, child_guides) = if self.level == 0 {
+            (String::new(), String::new())
+        } else {
+            let branch = if is_last { "└─ " } else { "├─ " };
+            let continuation = if is_last { "   " } else { "│  " };
+            (
+                format!("{}{}", parent_guides, branch),
+                format!("{}{}", parent_guides, continuation),
+            )
+        };
+
+        let is_collapsed = collapsed.contains(&self.process.pid);
+        result.push(TreeRow {
+            process: self.process.clone(),
+            level: self.level,
+            prefix,
+            has_children: !self.children.is_empty(),
+            collapsed: is_collapsed,
+            subtree_cpu: self.subtree_cpu_usage(),
+            subtree_memory: self.subtree_memory_usage(),
+            descendant_count: self.count_processes() - 1,
+        });
+
+        if is_collapsed {
+            return;
+        }
+
+        let last_index = self.children.len().saturating_sub(1);
+        for (i, child) in self.children.iter().enumerate() {
+            child.flatten_visible_recursive(collapsed, &child_guides, i == last_index, result);
+        }
+    }
+
+    /// PIDs of every descendant that has children of its own (excluding this node)
+    pub fn get_descendant_parents(&self) -> Vec<u32> {
+        let mut parents = Vec::new();
+        for child in &self.children {
+            if !child.children.is_empty() {
+                parents.push(child.process.pid);
+                parents.extend(child.get_descendant_parents());
+            }
+        }
+        parents
+    }
+}
+
+/// A single visible row of a flattened process tree
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    pub process: ProcessInfo,
+    pub level: usize,
+    /// Indentation guides to draw before the process name
+    pub prefix: String,
+    pub has_children: bool,
+    pub collapsed: bool,
+    /// CPU usage of the process plus all descendants
+    pub subtree_cpu: f32,
+    /// Memory usage of the process plus all descendants
+    pub subtree_memory: u64,
+    pub descendant_count: usize,
 }
 
 #[cfg(test)]
@@ -132,6 +237,7 @@ mod tests {
             status: "Running".to_string(),
             start_time: 0,
             running_time: Duration::from_secs(100),
+            cpu_time: Default::default(),
             uid: 1000,
             gid: 1000,
             threads: 1,
@@ -142,6 +248,10 @@ mod tests {
             container_id: None,
             cgroup_memory_limit: None,
             gpu_memory: None,
+            disk_io: None,
+            network_io: None,
+            open_fds: None,
+            fd_limit: None,
         }
     }
 
@@ -181,4 +291,67 @@ mod tests {
         assert_eq!(flattened[2].0.pid, 3);
         assert_eq!(flattened[2].1, 1); // level 1
     }
+
+    #[test]
+    fn test_flatten_visible_guides_and_collapse() {
+        let mut processes = vec![
+            create_test_process(1, 0, "init"),
+            create_test_process(2, 1, "supervisor"),
+            create_test_process(3, 2, "worker1"),
+            create_test_process(4, 2, "worker2"),
+            create_test_process(5, 1, "sshd"),
+        ];
+        for p in processes.iter_mut() {
+            p.cpu_usage = p.pid as f32;
+        }
+
+        let trees = ProcessTree::build_tree(&processes);
+        let rows = trees[0].flatten_visible(&HashSet::new());
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].prefix, "");
+        assert_eq!(rows[1].prefix, "├─ ");
+        assert_eq!(rows[2].prefix, "│  ├─ ");
+        assert_eq!(rows[3].prefix, "│  └─ ");
+        assert_eq!(rows[4].prefix, "└─ ");
+        assert_eq!(rows[0].subtree_cpu, 15.0);
+        assert_eq!(rows[1].subtree_cpu, 9.0);
+        assert_eq!(rows[1].subtree_memory, 3 * 1024);
+
+        let collapsed: HashSet<u32> = [2].into_iter().collect();
+        let rows = trees[0].flatten_visible(&collapsed);
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].collapsed);
+        assert_eq!(rows[1].descendant_count, 2);
+        assert_eq!(rows[1].subtree_cpu, 9.0);
+        assert_eq!(rows[2].process.pid, 5);
+    }
+
+    #[test]
+    fn test_get_pids_leaves_first() {
+        let processes = vec![
+            create_test_process(1, 0, "init"),
+            create_test_process(2, 1, "make"),
+            create_test_process(3, 2, "cc1"),
+            create_test_process(4, 2, "ld"),
+            create_test_process(5, 3, "as"),
+        ];
+
+        let trees = ProcessTree::build_tree(&processes);
+        let subtree = trees[0].find_process(2).unwrap();
+        assert_eq!(subtree.get_pids_leaves_first(), vec![5, 3, 4, 2]);
+    }
+
+    #[test]
+    fn test_get_descendant_parents() {
+        let processes = vec![
+            create_test_process(1, 0, "init"),
+            create_test_process(2, 1, "supervisor"),
+            create_test_process(3, 2, "worker"),
+            create_test_process(4, 1, "leaf"),
+        ];
+
+        let trees = ProcessTree::build_tree(&processes);
+        assert_eq!(trees[0].get_descendant_parents(), vec![2]);
+        assert!(trees[0].children[0].get_descendant_parents().is_empty());
+    }
 }
\ No newline at end of file
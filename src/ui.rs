@@ -1,5 +1,17 @@
-use crate::process::{ProcessManager, ProcessFilter, SortColumn, signals};
+use crate::config::Config;
+use crate::process::{ProcessManager, ProcessFilter, ProcessInfo, SortColumn, signals};
+use crate::tree::{ProcessTree, TreeRow};
+use crate::history::{AuditRecord, HistoryManager};
 use crate::logging::log_process_operation;
+use crate::network::{self, SocketEntry};
+use crate::fds::{self, FdEntry};
+use crate::affinity;
+use crate::details::ProcessDetails;
+use crate::groups;
+use crate::profiles::{SortOrder, ViewProfile, ViewProfileManager};
+use crate::columns::{self, ColumnDef};
+use crate::threads::{ThreadInfo, ThreadSampler};
+use crate::silences::{MaintenanceWindow, Silence};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
@@ -12,11 +24,12 @@ use ratatui::{
     text::Line,
     widgets::{
         Block, Borders, Clear, Paragraph, Row, Table, TableState,
-        Wrap, Sparkline,
+        Tabs, Wrap, Sparkline,
     },
     Frame, Terminal,
 };
 use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -29,6 +42,167 @@ pub enum UiError {
     Process(#[from] anyhow::Error),
 }
 
+/// Detail pane shown below the process table for the selected process
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DetailPane {
+    Sockets,
+    Fds,
+}
+
+/// Dialog that changes a scheduling attribute of the selected process
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ControlDialog {
+    Nice,
+    Affinity,
+    Scheduler,
+    IoPriority,
+}
+
+impl ControlDialog {
+    fn title(&self) -> &'static str {
+        match self {
+            ControlDialog::Nice => "Renice",
+            ControlDialog::Affinity => "CPU Affinity",
+            ControlDialog::Scheduler => "Scheduling Policy",
+            ControlDialog::IoPriority => "I/O Priority",
+        }
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            ControlDialog::Nice => "Nice value from -20 (highest priority) to 19 (lowest)",
+            ControlDialog::Affinity => "CPU list, e.g. 0-3,6",
+            ControlDialog::Scheduler => "other | batch | idle | fifo <1-99> | rr <1-99>",
+            ControlDialog::IoPriority => "none | idle | best-effort <0-7> | realtime <0-7>",
+        }
+    }
+}
+
+/// Tabs of the full-screen process detail view
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DetailTab {
+    Overview,
+    Threads,
+    MemoryMap,
+    Scheduling,
+    Group,
+    Container,
+}
+
+impl DetailTab {
+    const ALL: [DetailTab; 6] = [
+        DetailTab::Overview,
+        DetailTab::Threads,
+        DetailTab::MemoryMap,
+        DetailTab::Scheduling,
+        DetailTab::Group,
+        DetailTab::Container,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            DetailTab::Overview => "Overview",
+            DetailTab::Threads => "Threads",
+            DetailTab::MemoryMap => "Memory Map",
+            DetailTab::Scheduling => "Scheduling",
+            DetailTab::Group => "Group/Session",
+            DetailTab::Container => "Container",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
+}
+
+/// Full-screen detail view of one process, opened with Enter
+struct DetailView {
+    pid: u32,
+    tab: DetailTab,
+    scroll: u16,
+    /// Reloaded on every periodic refresh; `Err` once the process has exited
+    details: Result<ProcessDetails, String>,
+    threads: Result<Vec<ThreadInfo>, String>,
+    thread_sampler: ThreadSampler,
+}
+
+impl DetailView {
+    fn open(pid: u32) -> Self {
+        let mut view = Self {
+            pid,
+            tab: DetailTab::Overview,
+            scroll: 0,
+            details: Err(String::new()),
+            threads: Ok(Vec::new()),
+            thread_sampler: ThreadSampler::new(),
+        };
+        view.reload();
+        view
+    }
+
+    fn reload(&mut self) {
+        self.details = ProcessDetails::load(self.pid).map_err(|e| e.to_string());
+        self.threads = self.thread_sampler.sample(self.pid).map_err(|e| format!("{:#}", e));
+    }
+
+    fn select_tab(&mut self, tab: DetailTab) {
+        self.tab = tab;
+        self.scroll = 0;
+    }
+}
+
+/// Column picker opened with 'f': every registry column, the shown ones
+/// first in display order
+struct ColumnPicker {
+    entries: Vec<(&'static ColumnDef, bool)>,
+    cursor: usize,
+}
+
+impl ColumnPicker {
+    fn open(layout: &[&'static ColumnDef]) -> Self {
+        let mut entries: Vec<_> = layout.iter().map(|column| (*column, true)).collect();
+        entries.extend(
+            columns::COLUMNS
+                .iter()
+                .filter(|column| !layout.iter().any(|shown| shown.key == column.key))
+                .map(|column| (column, false)),
+        );
+        Self { entries, cursor: 0 }
+    }
+
+    /// Show or hide the column under the cursor; the last shown column stays
+    fn toggle(&mut self) {
+        let shown = self.entries.iter().filter(|(_, on)| *on).count();
+        if let Some(entry) = self.entries.get_mut(self.cursor) {
+            if !entry.1 || shown > 1 {
+                entry.1 = !entry.1;
+            }
+        }
+    }
+
+    /// Move the column under the cursor up or down, keeping the cursor on it
+    fn move_selected(&mut self, down: bool) {
+        let target = if down { self.cursor + 1 } else { self.cursor.wrapping_sub(1) };
+        if target < self.entries.len() {
+            self.entries.swap(self.cursor, target);
+            self.cursor = target;
+        }
+    }
+
+    fn layout(&self) -> Vec<&'static ColumnDef> {
+        self.entries.iter().filter(|(_, on)| *on).map(|(column, _)| *column).collect()
+    }
+}
+
+/// Alert silences and maintenance windows, opened with 'a'. Silences and
+/// windows added through the API are read from the history database.
+struct SilencesView {
+    silences: Vec<Silence>,
+    windows: Vec<MaintenanceWindow>,
+    /// Why the stored silences and windows could not be read
+    error: Option<String>,
+}
+
 pub struct App {
     process_manager: ProcessManager,
     table_state: TableState,
@@ -39,52 +213,184 @@ pub struct App {
     search_input: String,
     show_help: bool,
     show_tree_view: bool,
+    collapsed_pids: HashSet<u32>,
     selected_process: Option<u32>,
     last_refresh: Instant,
     refresh_interval: Duration,
     status_message: Option<String>,
     show_kill_dialog: bool,
     kill_signal: i32,
+    kill_subtree: bool,
+    /// Open renice/affinity/scheduler/ionice dialog; targets `selected_process`
+    control_dialog: Option<ControlDialog>,
+    control_input: String,
+    /// `control_input` still holds the current setting; the first key replaces it
+    control_input_prefilled: bool,
+    detail_pane: Option<DetailPane>,
+    /// Sockets of the process shown in the socket pane, reloaded on refresh
+    /// and when the selection moves
+    sockets: Option<(u32, Vec<SocketEntry>)>,
+    /// Open fds of the process shown in the fd pane, or why they could not be read
+    fds: Option<(u32, Result<Vec<FdEntry>, String>)>,
+    detail_view: Option<DetailView>,
+    /// Whether processes can be expanded into per-thread rows (flat view)
+    show_threads: bool,
+    expanded_threads: HashSet<u32>,
+    /// Threads of the expanded processes, resampled on every refresh
+    thread_rows: HashMap<u32, Vec<ThreadInfo>>,
+    thread_sampler: ThreadSampler,
+    /// Columns of the flat process table
+    columns: Vec<&'static ColumnDef>,
+    column_picker: Option<ColumnPicker>,
+    /// Key of the view profile in use, where the column layout is saved
+    view_profile: Option<String>,
     show_graphs: bool,
     cpu_history: Vec<u64>,
     memory_history: Vec<u64>,
     max_history_len: usize,
+    /// History database used for the audit log and for reading alert
+    /// silences, if history is enabled
+    audit_log: Option<HistoryManager>,
+    /// `[[alerts.maintenance_windows]]` from the config file
+    config_windows: Vec<MaintenanceWindow>,
+    silences_view: Option<SilencesView>,
+    /// Local login name, recorded as the initiator of audited actions
+    user: String,
 }
 
 impl App {
-    pub fn new() -> Result<Self, UiError> {
-        let mut process_manager = ProcessManager::new();
+    /// Create the TUI state from a resolved configuration.
+    ///
+    /// Sorting, filtering, refresh rate and initial view settings all come from
+    /// `config`; optional metrics follow `config.features`.
+    pub fn new(config: &Config) -> Result<Self, UiError> {
+        let mut process_manager = ProcessManager::with_features(config.features.clone());
         process_manager.refresh()?;
 
+        let mut filter = ProcessFilter::new();
+        filter.username = config.general.user_filter.clone();
+        filter.show_only_user_processes = config.general.show_only_user_processes;
+
+        let audit_log = if config.history.enabled {
+            match HistoryManager::new(&config.history.database_path) {
+                Ok(hm) => Some(hm),
+                Err(e) => {
+                    tracing::warn!("Audit log unavailable, history database failed to open: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let user = users::get_current_username()
+            .and_then(|os_str| os_str.into_string().ok())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut sort_column = SortColumn::from_name(&config.general.default_sort_column)
+            .unwrap_or(SortColumn::CpuUsage);
+        let mut sort_ascending = config.general.sort_ascending;
+        let mut show_tree_view = config.ui.start_in_tree_view;
+        let mut show_threads = false;
+        let mut columns = columns::resolve(&[]);
+        let mut view_profile = None;
+        let mut status_message = None;
+        if let Some(ref key) = config.ui.view_profile {
+            let manager = ViewProfileManager::default_dir()
+                .ok_or_else(|| anyhow::anyhow!("no configuration directory"))
+                .and_then(ViewProfileManager::new);
+            match manager.as_ref().map(|m| m.get_profile(key)) {
+                Ok(Some(profile)) => {
+                    if let Some(column) = SortColumn::from_name(&profile.sort_by) {
+                        sort_column = column;
+                        sort_ascending = matches!(profile.sort_order, SortOrder::Ascending);
+                    }
+                    show_tree_view |= profile.tree_mode;
+                    show_threads = profile.show_threads;
+                    columns = columns::resolve(&profile.columns);
+                    view_profile = Some(key.clone());
+                    status_message = Some(format!("View profile: {}", profile.name));
+                }
+                Ok(None) => status_message = Some(format!("Unknown view profile '{}'", key)),
+                Err(e) => {
+                    tracing::warn!("View profiles unavailable: {}", e);
+                    status_message = Some(format!("View profiles unavailable: {}", e));
+                }
+            }
+        }
+
         Ok(Self {
             process_manager,
             table_state: TableState::default(),
-            sort_column: SortColumn::CpuUsage,
-            sort_ascending: false,
-            filter: ProcessFilter::new(),
+            sort_column,
+            sort_ascending,
+            filter,
             search_mode: false,
             search_input: String::new(),
             show_help: false,
-            show_tree_view: false,
+            show_tree_view,
+            collapsed_pids: HashSet::new(),
             selected_process: None,
             last_refresh: Instant::now(),
-            refresh_interval: Duration::from_secs(2),
-            status_message: None,
+            refresh_interval: Duration::from_secs(config.general.refresh_interval.max(1)),
+            status_message,
             show_kill_dialog: false,
             kill_signal: signals::SIGTERM,
-            show_graphs: true,
+            kill_subtree: false,
+            control_dialog: None,
+            control_input_prefilled: false,
+            control_input: String::new(),
+            detail_pane: None,
+            sockets: None,
+            fds: None,
+            detail_view: None,
+            show_threads,
+            expanded_threads: HashSet::new(),
+            thread_rows: HashMap::new(),
+            thread_sampler: ThreadSampler::new(),
+            columns,
+            column_picker: None,
+            view_profile,
+            show_graphs: config.ui.show_graphs,
             cpu_history: Vec::new(),
             memory_history: Vec::new(),
             max_history_len: 60,
+            audit_log,
+            config_windows: config.alerts.maintenance_windows.clone(),
+            silences_view: None,
+            user,
         })
     }
 
+    /// Persist a process-control action to the audit log
+    fn audit(&self, pid: u32, action: &str, details: String, result: Result<(), String>) {
+        if let Some(ref audit_log) = self.audit_log {
+            let record = AuditRecord::new(
+                &self.user,
+                "tui",
+                self.process_manager.get_process(pid),
+                pid,
+                action,
+                details,
+                result,
+            );
+            if let Err(e) = audit_log.record_audit(&record) {
+                tracing::error!(pid = pid, "Failed to write audit record: {}", e);
+            }
+        }
+    }
+
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<(), UiError> {
         loop {
             // Refresh process data periodically
             if self.last_refresh.elapsed() >= self.refresh_interval {
                 self.process_manager.refresh()?;
                 self.update_history();
+                self.sockets = None;
+                self.fds = None;
+                if let Some(ref mut view) = self.detail_view {
+                    view.reload();
+                }
+                self.refresh_threads();
                 self.last_refresh = Instant::now();
             }
 
@@ -101,9 +407,16 @@ impl App {
         Ok(())
     }
 
-    fn handle_input(&mut self, key: KeyCode, _modifiers: KeyModifiers) -> Result<bool, UiError> {
+    fn handle_input(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<bool, UiError> {
         // Global quit
-        if key == KeyCode::Char('q') && !self.search_mode && !self.show_kill_dialog {
+        if key == KeyCode::Char('q')
+            && !self.search_mode
+            && !self.show_kill_dialog
+            && self.control_dialog.is_none()
+            && self.column_picker.is_none()
+            && self.silences_view.is_none()
+            && self.detail_view.is_none()
+        {
             return Ok(true);
         }
 
@@ -111,10 +424,34 @@ impl App {
             return Ok(self.handle_kill_dialog_input(key));
         }
 
+        if self.control_dialog.is_some() {
+            self.handle_control_dialog_input(key);
+            return Ok(false);
+        }
+
+        if self.column_picker.is_some() {
+            self.handle_column_picker_input(key, modifiers);
+            return Ok(false);
+        }
+
+        if self.silences_view.is_some() {
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('a') => self.silences_view = None,
+                KeyCode::Char('r') => self.silences_view = Some(self.load_silences()),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
         if self.search_mode {
             return Ok(self.handle_search_input(key));
         }
 
+        if self.detail_view.is_some() {
+            self.handle_detail_input(key);
+            return Ok(false);
+        }
+
         match key {
             KeyCode::Char('h') | KeyCode::F(1) => {
                 self.show_help = !self.show_help;
@@ -124,7 +461,67 @@ impl App {
                 self.search_input.clear();
             }
             KeyCode::Char('t') => {
+                let selected = self.get_selected_process_id();
                 self.show_tree_view = !self.show_tree_view;
+                if let Some(pid) = selected {
+                    self.select_pid(pid);
+                }
+            }
+            // Tree view expand/collapse
+            KeyCode::Left if self.show_tree_view => {
+                self.set_selected_collapsed(true);
+            }
+            KeyCode::Right if self.show_tree_view => {
+                self.set_selected_collapsed(false);
+            }
+            KeyCode::Char(' ') if self.show_tree_view => {
+                if let Some(pid) = self.get_selected_process_id() {
+                    let collapsed = self.collapsed_pids.contains(&pid);
+                    self.set_selected_collapsed(!collapsed);
+                }
+            }
+            KeyCode::Char('C') if self.show_tree_view => {
+                self.collapse_below_selected();
+            }
+            KeyCode::Char('E') if self.show_tree_view => {
+                self.collapsed_pids.clear();
+                self.status_message = Some("Expanded all".to_string());
+            }
+            // Thread rows (flat view)
+            KeyCode::Right if self.show_threads => {
+                self.set_selected_threads_expanded(true);
+            }
+            KeyCode::Left if self.show_threads => {
+                self.set_selected_threads_expanded(false);
+            }
+            KeyCode::Char(' ') if self.show_threads => {
+                if let Some(pid) = self.get_selected_process_id() {
+                    let expanded = self.expanded_threads.contains(&pid);
+                    self.set_selected_threads_expanded(!expanded);
+                }
+            }
+            KeyCode::Char('H') => {
+                let selected = self.get_selected_process_id();
+                self.show_threads = !self.show_threads;
+                if let Some(pid) = selected {
+                    self.select_pid(pid);
+                }
+                self.status_message = Some(format!(
+                    "Threads: {}",
+                    if self.show_threads { "ON (→/← expand/collapse)" } else { "OFF" }
+                ));
+            }
+            KeyCode::Char('S') => {
+                self.toggle_detail_pane(DetailPane::Sockets);
+            }
+            KeyCode::Char('F') => {
+                self.toggle_detail_pane(DetailPane::Fds);
+            }
+            KeyCode::Char('f') => {
+                self.column_picker = Some(ColumnPicker::open(&self.columns));
+            }
+            KeyCode::Char('a') => {
+                self.silences_view = Some(self.load_silences());
             }
             KeyCode::Char('g') => {
                 self.show_graphs = !self.show_graphs;
@@ -133,13 +530,23 @@ impl App {
                     if self.show_graphs { "ON" } else { "OFF" }
                 ));
             }
+            KeyCode::Enter => {
+                if let Some(pid) = self.get_selected_process_id() {
+                    self.detail_view = Some(DetailView::open(pid));
+                }
+            }
             KeyCode::Char('k') => {
                 if let Some(selected) = self.get_selected_process_id() {
                     self.selected_process = Some(selected);
                     self.show_kill_dialog = true;
                     self.kill_signal = signals::SIGTERM;
+                    self.kill_subtree = false;
                 }
             }
+            KeyCode::Char('N') => self.open_control_dialog(ControlDialog::Nice),
+            KeyCode::Char('A') => self.open_control_dialog(ControlDialog::Affinity),
+            KeyCode::Char('P') => self.open_control_dialog(ControlDialog::Scheduler),
+            KeyCode::Char('i') => self.open_control_dialog(ControlDialog::IoPriority),
             KeyCode::Up => {
                 self.previous_process();
             }
@@ -173,6 +580,30 @@ impl App {
             KeyCode::Char('s') => {
                 self.set_sort_column(SortColumn::StartTime);
             }
+            KeyCode::Char('T') => {
+                self.set_sort_column(SortColumn::CpuTime);
+            }
+            KeyCode::Char('d') => {
+                self.set_sort_column(SortColumn::DiskRead);
+            }
+            KeyCode::Char('w') => {
+                self.set_sort_column(SortColumn::DiskWrite);
+            }
+            KeyCode::Char('D') => {
+                self.set_sort_column(SortColumn::ReadSyscalls);
+            }
+            KeyCode::Char('W') => {
+                self.set_sort_column(SortColumn::WriteSyscalls);
+            }
+            KeyCode::Char('x') => {
+                self.set_sort_column(SortColumn::CancelledWrite);
+            }
+            KeyCode::Char('I') => {
+                self.set_sort_column(SortColumn::NetRx);
+            }
+            KeyCode::Char('O') => {
+                self.set_sort_column(SortColumn::NetTx);
+            }
             // Filter toggles
             KeyCode::Char('o') => {
                 self.filter.show_only_user_processes = !self.filter.show_only_user_processes;
@@ -187,6 +618,136 @@ impl App {
         Ok(false)
     }
 
+    /// Read the current silences and maintenance windows
+    fn load_silences(&self) -> SilencesView {
+        let mut view = SilencesView {
+            silences: Vec::new(),
+            windows: self.config_windows.clone(),
+            error: None,
+        };
+        match self.audit_log {
+            Some(ref history) => {
+                match (history.get_silences(chrono::Utc::now()), history.get_maintenance_windows()) {
+                    (Ok(silences), Ok(windows)) => {
+                        view.silences = silences;
+                        view.windows.extend(windows);
+                    }
+                    (Err(e), _) | (_, Err(e)) => view.error = Some(e.to_string()),
+                }
+            }
+            None => view.error = Some("history database disabled".to_string()),
+        }
+        view
+    }
+
+    /// Keys of the column picker; changes show immediately, Enter also saves
+    /// the layout to the view profile
+    fn handle_column_picker_input(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+        let Some(ref mut picker) = self.column_picker else {
+            return;
+        };
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('f') => {
+                self.column_picker = None;
+                return;
+            }
+            KeyCode::Enter => {
+                self.column_picker = None;
+                self.save_columns();
+                return;
+            }
+            KeyCode::Up if shift => picker.move_selected(false),
+            KeyCode::Down if shift => picker.move_selected(true),
+            KeyCode::Char('K') => picker.move_selected(false),
+            KeyCode::Char('J') => picker.move_selected(true),
+            KeyCode::Up => picker.cursor = picker.cursor.saturating_sub(1),
+            KeyCode::Down => picker.cursor = (picker.cursor + 1).min(picker.entries.len() - 1),
+            KeyCode::Char(' ') => picker.toggle(),
+            KeyCode::Char('s') => {
+                if let Some(sort) = picker.entries.get(picker.cursor).and_then(|(c, _)| c.sort.clone()) {
+                    self.set_sort_column(sort);
+                }
+            }
+            _ => {}
+        }
+        if let Some(ref picker) = self.column_picker {
+            self.columns = picker.layout();
+        }
+    }
+
+    /// Save the current columns to the active view profile, or to a new
+    /// `custom` profile when none is active
+    fn save_columns(&mut self) {
+        let key = self.view_profile.clone().unwrap_or_else(|| "custom".to_string());
+        let keys: Vec<String> = self.columns.iter().map(|c| c.key.to_string()).collect();
+        let result = ViewProfileManager::default_dir()
+            .ok_or_else(|| anyhow::anyhow!("no configuration directory"))
+            .and_then(ViewProfileManager::new)
+            .and_then(|mut manager| {
+                let mut profile = manager
+                    .get_profile(&key)
+                    .cloned()
+                    .unwrap_or_else(|| ViewProfile::new("Custom", "Saved from the column picker"));
+                profile.columns = keys;
+                manager.add_profile(key.clone(), profile)
+            });
+
+        self.status_message = Some(match result {
+            Ok(()) if self.view_profile.is_some() => format!("Saved columns to view profile '{}'", key),
+            Ok(()) => {
+                self.view_profile = Some(key.clone());
+                format!("Saved columns to new view profile '{}' (start with --profile {})", key, key)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to save columns to view profile '{}': {:#}", key, e);
+                format!("Failed to save columns: {:#}", e)
+            }
+        });
+    }
+
+    fn handle_detail_input(&mut self, key: KeyCode) {
+        let Some(ref mut view) = self.detail_view else {
+            return;
+        };
+        let tab = view.tab.index();
+        let tab_count = DetailTab::ALL.len();
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.detail_view = None,
+            KeyCode::Tab | KeyCode::Right => view.select_tab(DetailTab::ALL[(tab + 1) % tab_count]),
+            KeyCode::BackTab | KeyCode::Left => {
+                view.select_tab(DetailTab::ALL[(tab + tab_count - 1) % tab_count])
+            }
+            KeyCode::Char(c @ '1'..='6') => {
+                view.select_tab(DetailTab::ALL[c as usize - '1' as usize]);
+            }
+            KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Down => view.scroll = view.scroll.saturating_add(1),
+            KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(10),
+            KeyCode::PageDown => view.scroll = view.scroll.saturating_add(10),
+            KeyCode::Home => view.scroll = 0,
+            KeyCode::Char('r') | KeyCode::F(5) => {
+                view.reload();
+                self.status_message = Some(format!("Reloaded details of PID {}", view.pid));
+            }
+            KeyCode::Char('k') => {
+                self.selected_process = Some(view.pid);
+                self.show_kill_dialog = true;
+                self.kill_signal = signals::SIGTERM;
+                self.kill_subtree = false;
+            }
+            KeyCode::Char('N') => self.open_control_dialog(ControlDialog::Nice),
+            KeyCode::Char('A') => self.open_control_dialog(ControlDialog::Affinity),
+            KeyCode::Char('P') => self.open_control_dialog(ControlDialog::Scheduler),
+            KeyCode::Char('i') => self.open_control_dialog(ControlDialog::IoPriority),
+            KeyCode::Char('h') | KeyCode::F(1) => {
+                self.show_help = !self.show_help;
+            }
+            _ => {}
+        }
+    }
+
     fn handle_search_input(&mut self, key: KeyCode) -> bool {
         match key {
             KeyCode::Enter => {
@@ -223,17 +784,30 @@ impl App {
 
     fn handle_kill_dialog_input(&mut self, key: KeyCode) -> bool {
         match key {
+            KeyCode::Enter if self.kill_subtree => {
+                if let Some(pid) = self.selected_process {
+                    self.kill_selected_subtree(pid);
+                }
+                self.show_kill_dialog = false;
+                self.selected_process = None;
+            }
             KeyCode::Enter => {
                 if let Some(pid) = self.selected_process {
                     // Get process info for logging before killing it
                     let process_name = self.process_manager.get_process(pid)
                         .map(|p| p.name.clone())
                         .unwrap_or_else(|| format!("{}", pid));
-                    let user = users::get_current_username()
-                        .and_then(|os_str| os_str.into_string().ok())
-                        .unwrap_or_else(|| "unknown".to_string());
+                    let user = self.user.clone();
                     
-                    match self.process_manager.kill_process(pid, self.kill_signal) {
+                    let result = self.process_manager.kill_process(pid, self.kill_signal);
+                    self.audit(
+                        pid,
+                        "signal",
+                        format!("signal={}", self.kill_signal),
+                        result.as_ref().map(|_| ()).map_err(|e| e.to_string()),
+                    );
+                    
+                    match result {
                         Ok(()) => {
                             self.status_message = Some(format!("Sent signal {} to process {}", self.kill_signal, pid));
                             // Log successful operation
@@ -274,11 +848,226 @@ impl App {
             KeyCode::Char('t') => self.kill_signal = signals::SIGTERM,
             KeyCode::Char('s') => self.kill_signal = signals::SIGSTOP,
             KeyCode::Char('c') => self.kill_signal = signals::SIGCONT,
+            KeyCode::Char('a') => self.kill_subtree = !self.kill_subtree,
             _ => {}
         }
         false
     }
 
+    /// Open `dialog` for the selected process (or the one in the detail view),
+    /// prefilled with its current setting (selected, so typing replaces it)
+    fn open_control_dialog(&mut self, dialog: ControlDialog) {
+        let target = match self.detail_view {
+            Some(ref view) => Some(view.pid),
+            None => self.get_selected_process_id(),
+        };
+        let Some(pid) = target else {
+            return;
+        };
+        let info = match affinity::get_priority_info(pid) {
+            Ok(info) => info,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to read priority of {}: {:#}", pid, e));
+                return;
+            }
+        };
+
+        self.control_input = match dialog {
+            ControlDialog::Nice => info.nice_value.to_string(),
+            ControlDialog::Affinity => affinity::format_affinity_list(&info.cpu_affinity),
+            ControlDialog::Scheduler if info.rt_priority > 0 => {
+                format!("{} {}", info.scheduling_policy, info.rt_priority)
+            }
+            ControlDialog::Scheduler => info.scheduling_policy,
+            ControlDialog::IoPriority => format!("{} {}", info.io_priority_class, info.io_priority_level),
+        };
+        self.control_input_prefilled = true;
+        self.selected_process = Some(pid);
+        self.control_dialog = Some(dialog);
+    }
+
+    fn handle_control_dialog_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => {
+                if let (Some(dialog), Some(pid)) = (self.control_dialog, self.selected_process) {
+                    self.apply_control_dialog(dialog, pid);
+                }
+                self.control_dialog = None;
+                self.selected_process = None;
+            }
+            KeyCode::Esc => {
+                self.control_dialog = None;
+                self.selected_process = None;
+            }
+            KeyCode::Backspace if self.control_input_prefilled => {
+                self.control_input.clear();
+            }
+            KeyCode::Backspace => {
+                self.control_input.pop();
+            }
+            KeyCode::Char(c) => {
+                if self.control_input_prefilled {
+                    self.control_input.clear();
+                }
+                self.control_input.push(c);
+            }
+            _ => {}
+        }
+        self.control_input_prefilled = false;
+    }
+
+    /// Apply the typed value of a control dialog, logging and auditing the change
+    fn apply_control_dialog(&mut self, dialog: ControlDialog, pid: u32) {
+        let input = self.control_input.trim().to_string();
+        let (action, result) = match dialog {
+            ControlDialog::Nice => (
+                "renice",
+                input.parse::<i32>()
+                    .map_err(|_| anyhow::anyhow!("'{}' is not a nice value", input))
+                    .and_then(|nice| affinity::set_nice_value(pid, nice)),
+            ),
+            ControlDialog::Affinity => (
+                "set_affinity",
+                affinity::parse_affinity_string(&input)
+                    .and_then(|cpus| affinity::set_cpu_affinity(pid, &cpus)),
+            ),
+            ControlDialog::Scheduler => (
+                "set_scheduler",
+                affinity::parse_scheduling_string(&input)
+                    .and_then(|(policy, priority)| affinity::set_scheduling_policy(pid, policy, priority)),
+            ),
+            ControlDialog::IoPriority => (
+                "set_ionice",
+                affinity::parse_io_priority_string(&input)
+                    .and_then(|(class, level)| affinity::set_io_priority(pid, class, level)),
+            ),
+        };
+
+        let process_name = self.process_manager.get_process(pid)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| pid.to_string());
+        let details = format!("{}={}", action, input);
+        self.audit(pid, action, details.clone(), result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e)));
+        match result {
+            Ok(()) => {
+                log_process_operation(action, pid, &process_name, &self.user, true, Some(&details));
+                self.status_message = Some(format!("{} of process {} set to {}", dialog.title(), pid, input));
+            }
+            Err(e) => {
+                log_process_operation(action, pid, &process_name, &self.user, false, Some(&format!("error: {:#}", e)));
+                self.status_message = Some(format!("Failed to set {}: {:#}", dialog.title().to_lowercase(), e));
+            }
+        }
+        if let Some(ref mut view) = self.detail_view {
+            view.reload();
+        }
+    }
+
+    /// Signal the selected process and all of its descendants, leaves first
+    fn kill_selected_subtree(&mut self, pid: u32) {
+        let user = self.user.clone();
+
+        let outcomes = match self.process_manager.kill_process_tree(pid, self.kill_signal, false) {
+            Ok(outcomes) => outcomes,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to kill subtree: {}", e));
+                return;
+            }
+        };
+
+        for outcome in &outcomes {
+            let details = match outcome.error {
+                Some(ref error) => format!("subtree of {}, error: {}", pid, error),
+                None => format!("subtree of {}, signal: {}", pid, self.kill_signal),
+            };
+            log_process_operation(
+                "kill_tree",
+                outcome.pid,
+                &outcome.name,
+                &user,
+                outcome.success,
+                Some(&details),
+            );
+            self.audit(
+                outcome.pid,
+                "signal_tree",
+                format!("signal={} root={}", self.kill_signal, pid),
+                outcome.error.clone().map_or(Ok(()), Err),
+            );
+        }
+
+        let failed: Vec<String> = outcomes
+            .iter()
+            .filter(|o| !o.success)
+            .map(|o| o.pid.to_string())
+            .collect();
+        self.status_message = Some(if failed.is_empty() {
+            format!(
+                "Sent signal {} to {} process(es) in subtree of {}",
+                self.kill_signal,
+                outcomes.len(),
+                pid
+            )
+        } else {
+            format!(
+                "Sent signal {} to {}/{} process(es) in subtree of {} (failed: {})",
+                self.kill_signal,
+                outcomes.len() - failed.len(),
+                outcomes.len(),
+                pid,
+                failed.join(", ")
+            )
+        });
+    }
+
+    /// Show `pane` below the process table, or hide it if it is already shown
+    fn toggle_detail_pane(&mut self, pane: DetailPane) {
+        self.detail_pane = if self.detail_pane == Some(pane) { None } else { Some(pane) };
+        self.sockets = None;
+        self.fds = None;
+    }
+
+    /// Show or hide the thread rows of the selected process, keeping it selected
+    fn set_selected_threads_expanded(&mut self, expanded: bool) {
+        let Some(pid) = self.get_selected_process_id() else {
+            return;
+        };
+        if expanded {
+            match self.thread_sampler.sample(pid) {
+                Ok(threads) => {
+                    self.thread_rows.insert(pid, threads);
+                    self.expanded_threads.insert(pid);
+                }
+                Err(e) => self.status_message = Some(format!("Failed to read threads: {:#}", e)),
+            }
+        } else {
+            self.expanded_threads.remove(&pid);
+            self.thread_rows.remove(&pid);
+        }
+        self.select_pid(pid);
+    }
+
+    /// Resample the threads of every expanded process, dropping exited ones
+    fn refresh_threads(&mut self) {
+        let Self { process_manager, expanded_threads, thread_rows, thread_sampler, .. } = self;
+        expanded_threads.retain(|pid| process_manager.get_process(*pid).is_some());
+        thread_rows.clear();
+        for &pid in expanded_threads.iter() {
+            if let Ok(threads) = thread_sampler.sample(pid) {
+                thread_rows.insert(pid, threads);
+            }
+        }
+        thread_sampler.retain(|pid| expanded_threads.contains(&pid));
+    }
+
+    /// Thread rows shown under `pid` in the flat table
+    fn visible_threads(&self, pid: u32) -> &[ThreadInfo] {
+        if !self.show_threads {
+            return &[];
+        }
+        self.thread_rows.get(&pid).map(Vec::as_slice).unwrap_or(&[])
+    }
+
     fn set_sort_column(&mut self, column: SortColumn) {
         if self.sort_column == column {
             self.sort_ascending = !self.sort_ascending;
@@ -289,24 +1078,82 @@ impl App {
     }
 
     fn get_selected_process_id(&self) -> Option<u32> {
-        if let Some(selected) = self.table_state.selected() {
-            let processes = if self.show_tree_view {
-                // For tree view, we'd need to implement tree structure
-                self.process_manager.sort_processes(self.sort_column.clone(), self.sort_ascending)
-            } else {
-                self.process_manager.sort_processes(self.sort_column.clone(), self.sort_ascending)
-            };
-            
-            let filtered_processes: Vec<_> = processes.iter()
-                .filter(|p| self.filter.matches(p))
-                .collect();
-            
-            filtered_processes.get(selected).map(|p| p.pid)
+        let selected = self.table_state.selected()?;
+        self.get_visible_pids().get(selected).copied()
+    }
+
+    /// Processes that pass the current filter, in the current sort order
+    fn get_filtered_processes(&self) -> Vec<ProcessInfo> {
+        self.process_manager
+            .sort_processes(self.sort_column.clone(), self.sort_ascending)
+            .into_iter()
+            .filter(|p| self.filter.matches(p))
+            .collect()
+    }
+
+    /// Build the visible tree rows from the filtered process list.
+    ///
+    /// Processes whose parent is filtered out become roots of their own subtree.
+    fn get_tree_rows(&self) -> Vec<TreeRow> {
+        ProcessTree::build_tree(&self.get_filtered_processes())
+            .iter()
+            .flat_map(|tree| tree.flatten_visible(&self.collapsed_pids))
+            .collect()
+    }
+
+    /// PIDs of the rows currently shown in the table, in display order.
+    ///
+    /// Thread rows carry the PID of their process, so actions on a thread
+    /// row apply to the whole process.
+    fn get_visible_pids(&self) -> Vec<u32> {
+        if self.show_tree_view {
+            self.get_tree_rows().iter().map(|row| row.process.pid).collect()
         } else {
-            None
+            self.get_filtered_processes()
+                .iter()
+                .flat_map(|p| std::iter::repeat_n(p.pid, 1 + self.visible_threads(p.pid).len()))
+                .collect()
+        }
+    }
+
+    /// Move the selection to the row showing `pid`, if it is visible
+    fn select_pid(&mut self, pid: u32) {
+        if let Some(index) = self.get_visible_pids().iter().position(|&p| p == pid) {
+            self.table_state.select(Some(index));
         }
     }
 
+    fn set_selected_collapsed(&mut self, collapsed: bool) {
+        if let Some(pid) = self.get_selected_process_id() {
+            if collapsed {
+                self.collapsed_pids.insert(pid);
+            } else {
+                self.collapsed_pids.remove(&pid);
+            }
+            self.select_pid(pid);
+        }
+    }
+
+    /// Collapse every node below the selected process, leaving the selection itself expanded
+    fn collapse_below_selected(&mut self) {
+        let Some(pid) = self.get_selected_process_id() else {
+            return;
+        };
+
+        let trees = ProcessTree::build_tree(&self.get_filtered_processes());
+        if let Some(node) = trees.iter().find_map(|tree| tree.find_process(pid)) {
+            let parents = node.get_descendant_parents();
+            self.status_message = Some(format!(
+                "Collapsed {} subtree(s) below PID {}",
+                parents.len(),
+                pid
+            ));
+            self.collapsed_pids.extend(parents);
+            self.collapsed_pids.remove(&pid);
+        }
+        self.select_pid(pid);
+    }
+
     fn next_process(&mut self) {
         let len = self.get_filtered_process_count();
         if len > 0 {
@@ -324,8 +1171,7 @@ impl App {
     }
 
     fn get_filtered_process_count(&self) -> usize {
-        let processes = self.process_manager.sort_processes(self.sort_column.clone(), self.sort_ascending);
-        processes.iter().filter(|p| self.filter.matches(p)).count()
+        self.get_visible_pids().len()
     }
 
     fn update_history(&mut self) {
@@ -396,6 +1242,39 @@ impl App {
     }
 
     fn ui(&mut self, f: &mut Frame) {
+        if self.detail_view.is_some() {
+            self.render_detail_view(f);
+        } else {
+            self.render_main_view(f);
+        }
+
+        // Overlays
+        if self.show_help {
+            self.render_help_popup(f);
+        }
+
+        if self.search_mode {
+            self.render_search_popup(f);
+        }
+
+        if self.show_kill_dialog {
+            self.render_kill_dialog(f);
+        }
+
+        if self.control_dialog.is_some() {
+            self.render_control_dialog(f);
+        }
+
+        if self.column_picker.is_some() {
+            self.render_column_picker(f);
+        }
+
+        if self.silences_view.is_some() {
+            self.render_silences(f);
+        }
+    }
+
+    fn render_main_view(&mut self, f: &mut Frame) {
         let main_chunks = if self.show_graphs {
             Layout::default()
                 .direction(Direction::Vertical)
@@ -424,28 +1303,15 @@ impl App {
         if self.show_graphs {
             self.render_system_graphs(f, main_chunks[1]);
             // Process table
-            self.render_process_table(f, main_chunks[2]);
+            self.render_process_area(f, main_chunks[2]);
             // Status bar
             self.render_status_bar(f, main_chunks[3]);
         } else {
             // Process table
-            self.render_process_table(f, main_chunks[1]);
+            self.render_process_area(f, main_chunks[1]);
             // Status bar
             self.render_status_bar(f, main_chunks[2]);
         }
-
-        // Overlays
-        if self.show_help {
-            self.render_help_popup(f);
-        }
-
-        if self.search_mode {
-            self.render_search_popup(f);
-        }
-
-        if self.show_kill_dialog {
-            self.render_kill_dialog(f);
-        }
     }
 
     fn render_system_info(&self, f: &mut Frame, area: Rect) {
@@ -480,58 +1346,460 @@ impl App {
         f.render_widget(paragraph, area);
     }
 
+    /// Process table, with the detail pane below it when one is enabled
+    fn render_process_area(&mut self, f: &mut Frame, area: Rect) {
+        let Some(pane) = self.detail_pane else {
+            self.render_process_table(f, area);
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(12)])
+            .split(area);
+        self.render_process_table(f, chunks[0]);
+        match pane {
+            DetailPane::Sockets => self.render_socket_pane(f, chunks[1]),
+            DetailPane::Fds => self.render_fd_pane(f, chunks[1]),
+        }
+    }
+
+    fn render_socket_pane(&mut self, f: &mut Frame, area: Rect) {
+        let selected = self.get_selected_process_id();
+        if selected != self.sockets.as_ref().map(|(pid, _)| *pid) {
+            self.sockets = selected.map(|pid| (pid, network::get_process_sockets(pid)));
+        }
+
+        let header = Row::new(vec!["Proto", "State", "Local", "Remote", "Send-Q", "Recv-Q"])
+            .style(Style::default().fg(Color::Yellow))
+            .height(1);
+
+        let (title, rows) = match self.sockets {
+            Some((pid, ref sockets)) => {
+                let rows: Vec<Row> = sockets.iter().map(|socket| {
+                    Row::new(vec![
+                        socket.protocol.to_string(),
+                        socket.state.clone(),
+                        socket.local_address.clone(),
+                        socket.remote_address.clone().unwrap_or_else(|| "*".to_string()),
+                        socket.tx_queue.to_string(),
+                        socket.rx_queue.to_string(),
+                    ])
+                }).collect();
+                (format!("Sockets of PID {} ({}) - 'S' to close", pid, sockets.len()), rows)
+            }
+            None => ("Sockets - no process selected".to_string(), Vec::new()),
+        };
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(&[
+                Constraint::Length(6),  // Proto
+                Constraint::Length(12), // State
+                Constraint::Min(24),    // Local
+                Constraint::Min(24),    // Remote
+                Constraint::Length(8),  // Send-Q
+                Constraint::Length(8),  // Recv-Q
+            ]);
+
+        f.render_widget(table, area);
+    }
+
+    fn render_fd_pane(&mut self, f: &mut Frame, area: Rect) {
+        let selected = self.get_selected_process_id();
+        if selected != self.fds.as_ref().map(|(pid, _)| *pid) {
+            self.fds = selected.map(|pid| (pid, fds::list_fds(pid).map_err(|e| format!("{:#}", e))));
+        }
+
+        let header = Row::new(vec!["FD", "Type", "Flags", "Pos", "Path"])
+            .style(Style::default().fg(Color::Yellow))
+            .height(1);
+
+        let (title, rows) = match self.fds {
+            Some((pid, Ok(ref entries))) => {
+                let limit = self.process_manager.get_process(pid)
+                    .and_then(|p| p.fd_limit)
+                    .map(|limit| limit.to_string())
+                    .unwrap_or_else(|| "?".to_string());
+                let rows: Vec<Row> = entries.iter().map(|entry| {
+                    Row::new(vec![
+                        entry.fd.to_string(),
+                        entry.kind.label().to_string(),
+                        entry.flag_names.join("|"),
+                        entry.position.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+                        entry.path.clone(),
+                    ])
+                }).collect();
+                (format!("Open fds of PID {} ({}/{}) - 'F' to close", pid, entries.len(), limit), rows)
+            }
+            Some((pid, Err(ref e))) => (format!("Open fds of PID {} - {}", pid, e), Vec::new()),
+            None => ("Open fds - no process selected".to_string(), Vec::new()),
+        };
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(&[
+                Constraint::Length(5),  // FD
+                Constraint::Length(8),  // Type
+                Constraint::Length(30), // Flags
+                Constraint::Length(10), // Pos
+                Constraint::Min(20),    // Path
+            ]);
+
+        f.render_widget(table, area);
+    }
+
+    /// Full-screen tabbed view of the process opened with Enter
+    fn render_detail_view(&self, f: &mut Frame) {
+        let Some(ref view) = self.detail_view else {
+            return;
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Tabs
+                Constraint::Min(0),    // Tab content
+                Constraint::Length(3), // Status bar
+            ])
+            .split(f.size());
+
+        let name = self.process_manager.get_process(view.pid)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "exited".to_string());
+        let titles: Vec<String> = DetailTab::ALL.iter()
+            .enumerate()
+            .map(|(i, tab)| format!("{} {}", i + 1, tab.title()))
+            .collect();
+        let tabs = Tabs::new(titles)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "PID {} ({}) - Tab/←/→ switch, ↑/↓ scroll, r reload, q/Esc back",
+                view.pid, name
+            )))
+            .select(view.tab.index())
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        f.render_widget(tabs, chunks[0]);
+
+        let lines = match view.details {
+            Ok(ref details) => {
+                let width = chunks[1].width.saturating_sub(2) as usize;
+                self.detail_lines(view, details, width)
+            }
+            Err(ref e) => vec![Line::from(e.clone())],
+        };
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(view.tab.title()))
+            .wrap(Wrap { trim: false })
+            .scroll((view.scroll, 0));
+        f.render_widget(paragraph, chunks[1]);
+
+        self.render_status_bar(f, chunks[2]);
+    }
+
+    /// Text of one detail tab; unreadable sections show their error
+    fn detail_lines(&self, view: &DetailView, details: &ProcessDetails, width: usize) -> Vec<Line<'static>> {
+        let (tab, threads) = (view.tab, &view.threads);
+        let mut lines: Vec<String> = Vec::new();
+        let field = |label: &str, value: String| format!("{:<18}{}", label, value);
+
+        match tab {
+            DetailTab::Overview => {
+                match self.process_manager.get_process(details.pid) {
+                    Some(p) => {
+                        let start = chrono::DateTime::from_timestamp(p.start_time as i64, 0)
+                            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_else(|| p.start_time.to_string());
+                        let running = p.running_time.as_secs();
+                        lines.extend([
+                            field("Name:", p.name.clone()),
+                            field("PID / PPID:", format!("{} / {}", p.pid, p.ppid)),
+                            field("User:", format!("{} (uid {}, gid {})", p.user, p.uid, p.gid)),
+                            field("Status:", p.status.clone()),
+                            field("Command:", p.command.clone()),
+                            field("CPU:", format!("{:.1}%", p.cpu_usage)),
+                            field("CPU time:", format!(
+                                "{} (user {:.2}s, system {:.2}s, children {:.2}s)",
+                                columns::format_cpu_time(p.cpu_time.total()),
+                                p.cpu_time.user,
+                                p.cpu_time.system,
+                                p.cpu_time.children
+                            )),
+                            field("Memory:", format!("{} KB ({:.1}%)", p.memory_usage, p.memory_percent)),
+                            field("Threads:", p.threads.to_string()),
+                            field("Priority / nice:", format!("{} / {}", p.priority, p.nice)),
+                            field("Started:", start),
+                            field("Running:", format!(
+                                "{}d {:02}:{:02}:{:02}",
+                                running / 86400,
+                                (running % 86400) / 3600,
+                                (running % 3600) / 60,
+                                running % 60
+                            )),
+                        ]);
+                        lines.push(field("Disk I/O:", match p.disk_io {
+                            Some(io) => format!(
+                                "read {}/s, write {}/s, {:.0} read ops/s, {:.0} write ops/s, cancelled {}/s",
+                                columns::format_bytes(io.read_bytes),
+                                columns::format_bytes(io.write_bytes),
+                                io.read_syscalls,
+                                io.write_syscalls,
+                                columns::format_bytes(io.cancelled_write_bytes)
+                            ),
+                            None => "-".to_string(),
+                        }));
+                        lines.push(field("Network:", format!(
+                            "{} connection(s), rx {}/s, tx {}/s",
+                            p.network_connections.map(|c| c.to_string()).unwrap_or_else(|| "?".to_string()),
+                            p.network_io.map(|io| columns::format_bytes(io.rx_bytes)).unwrap_or_else(|| "-".to_string()),
+                            p.network_io.map(|io| columns::format_bytes(io.tx_bytes)).unwrap_or_else(|| "-".to_string())
+                        )));
+                        lines.push(field("Open fds:", format!(
+                            "{} / {}",
+                            p.open_fds.map(|n| n.to_string()).unwrap_or_else(|| "?".to_string()),
+                            p.fd_limit.map(|n| n.to_string()).unwrap_or_else(|| "unlimited".to_string())
+                        )));
+                        lines.push(field("Container:", p.container_id.clone().unwrap_or_else(|| "-".to_string())));
+                        lines.push(field("Cgroup mem limit:", p.cgroup_memory_limit
+                            .map(|l| format!("{} KB", l / 1024))
+                            .unwrap_or_else(|| "-".to_string())));
+                        lines.push(field("GPU memory:", p.gpu_memory
+                            .map(|m| format!("{} MB", m))
+                            .unwrap_or_else(|| "-".to_string())));
+                    }
+                    None => lines.push(field("PID:", details.pid.to_string())),
+                }
+                lines.push(field("Executable:", details.exe.clone().unwrap_or_else(|| "-".to_string())));
+                lines.push(field("Working dir:", details.cwd.clone().unwrap_or_else(|| "-".to_string())));
+
+                lines.push(String::new());
+                lines.push("Resource limits:".to_string());
+                match details.limits {
+                    Ok(ref limits) => {
+                        lines.push(format!("  {:<26}{:>14}{:>14}  {}", "Limit", "Soft", "Hard", "Units"));
+                        lines.extend(limits.iter().map(|l| {
+                            format!("  {:<26}{:>14}{:>14}  {}", l.name, l.soft, l.hard, l.units)
+                        }));
+                    }
+                    Err(ref e) => lines.push(format!("  {}", e)),
+                }
+
+                lines.push(String::new());
+                match details.environment {
+                    Ok(ref environment) => {
+                        lines.push(format!("Environment ({}):", environment.len()));
+                        lines.extend(environment.iter().map(|e| format!("  {}", e)));
+                    }
+                    Err(ref e) => lines.push(format!("Environment: {}", e)),
+                }
+            }
+            DetailTab::Threads => match *threads {
+                Ok(ref threads) => {
+                    lines.push(format!("{} thread(s), busiest first", threads.len()));
+                    lines.push(String::new());
+                    lines.push(format!(
+                        "{:>8}  {:<16}  {:<5}  {:>6}  {:>4}  {:>12}  {:>12}",
+                        "TID", "Name", "State", "CPU%", "CPU", "Voluntary", "Involuntary"
+                    ));
+                    lines.extend(threads.iter().map(|t| format!(
+                        "{:>8}  {:<16}  {:<5}  {:>6.1}  {:>4}  {:>12}  {:>12}",
+                        t.tid,
+                        t.name,
+                        t.state,
+                        t.cpu_usage,
+                        t.last_cpu.map(|c| c.to_string()).unwrap_or_else(|| "?".to_string()),
+                        t.voluntary_ctxt_switches,
+                        t.nonvoluntary_ctxt_switches
+                    )));
+                }
+                Err(ref e) => lines.push(e.clone()),
+            },
+            DetailTab::MemoryMap => match details.memory_map {
+                Ok(ref map) => {
+                    lines.extend(map.visualize_ascii(width.max(60)).lines().map(str::to_string));
+                    lines.push(String::new());
+                    let mut libraries: Vec<(String, u64)> = map.get_library_summary().into_iter().collect();
+                    libraries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                    lines.push(format!("Shared libraries ({}):", libraries.len()));
+                    lines.extend(libraries.iter().map(|(path, size)| format!("  {:>10} KB  {}", size / 1024, path)));
+                }
+                Err(ref e) => lines.push(e.clone()),
+            },
+            DetailTab::Scheduling => match details.scheduling {
+                Ok(ref info) => lines.extend([
+                    field("CPU affinity:", affinity::format_affinity_list(&info.cpu_affinity)),
+                    field("Policy:", info.scheduling_policy.clone()),
+                    field("Nice:", info.nice_value.to_string()),
+                    field("Priority:", info.priority.to_string()),
+                    field("RT priority:", info.rt_priority.to_string()),
+                    field("I/O class:", info.io_priority_class.clone()),
+                    field("I/O level:", info.io_priority_level.to_string()),
+                ]),
+                Err(ref e) => lines.push(e.clone()),
+            },
+            DetailTab::Group => match details.group {
+                Ok(ref info) => lines.extend([
+                    field("PID / PPID:", format!("{} / {}", info.pid, info.ppid)),
+                    field("Process group:", info.pgid.to_string()),
+                    field("Session:", info.sid.to_string()),
+                    field("Terminal:", groups::get_tty_name(info.tty_nr)),
+                    field("Foreground group:", info.tpgid.to_string()),
+                    field("Group leader:", if info.is_group_leader { "yes" } else { "no" }.to_string()),
+                    field("Session leader:", if info.is_session_leader { "yes" } else { "no" }.to_string()),
+                    String::new(),
+                    groups::format_group_info(info),
+                ]),
+                Err(ref e) => lines.push(e.clone()),
+            },
+            DetailTab::Container => {
+                let container = &details.container;
+                let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+                lines.push(field("Containerized:", if container.containerized { "yes" } else { "no" }.to_string()));
+                lines.push(field("Container ID:", or_dash(&container.container_id)));
+                lines.push(String::new());
+                lines.push("Namespaces:".to_string());
+                match container.namespaces {
+                    Some(ref ns) => lines.extend([
+                        field("  pid", or_dash(&ns.pid_ns)),
+                        field("  net", or_dash(&ns.net_ns)),
+                        field("  mnt", or_dash(&ns.mnt_ns)),
+                        field("  uts", or_dash(&ns.uts_ns)),
+                        field("  ipc", or_dash(&ns.ipc_ns)),
+                        field("  user", or_dash(&ns.user_ns)),
+                    ]),
+                    None => lines.push("  not readable".to_string()),
+                }
+                lines.push(String::new());
+                lines.push("Cgroup resources:".to_string());
+                match container.resources {
+                    Some(ref r) => lines.extend([
+                        field("  CPU time", format!("{:.1}s", r.cpu_usage)),
+                        field("  Memory", format!("{} KB / {} KB", r.memory_usage / 1024, r.memory_limit / 1024)),
+                        field("  Network", format!("rx {} KB, tx {} KB", r.network_rx / 1024, r.network_tx / 1024)),
+                        field("  Block I/O", format!("read {} KB, write {} KB", r.block_read / 1024, r.block_write / 1024)),
+                    ]),
+                    None => lines.push("  not readable".to_string()),
+                }
+            }
+        }
+
+        lines.into_iter().map(Line::from).collect()
+    }
+
     fn render_process_table(&mut self, f: &mut Frame, area: Rect) {
+        if self.show_tree_view {
+            self.render_tree_table(f, area);
+            return;
+        }
+
         let processes = self.process_manager.sort_processes(self.sort_column.clone(), self.sort_ascending);
         let filtered_processes: Vec<_> = processes.iter()
             .filter(|p| self.filter.matches(p))
             .collect();
 
+        let sort_indicator = if self.sort_ascending { "▲" } else { "▼" };
+        let header = Row::new(self.columns.iter().map(|column| {
+            if column.sort.as_ref() == Some(&self.sort_column) {
+                column.align(format!("{}{}", column.header, sort_indicator))
+            } else {
+                column.align(column.header.to_string())
+            }
+        }).collect::<Vec<_>>())
+        .style(Style::default().fg(Color::Yellow))
+        .height(1);
+
+        let layout = &self.columns;
+        let rows: Vec<Row> = filtered_processes.iter().flat_map(|process| {
+            let threads = self.visible_threads(process.pid);
+            let cells: Vec<String> = layout.iter().map(|column| {
+                let text = (column.format)(process);
+                // Expandable processes get a marker when thread rows are on
+                let text = if column.key != "name" || !self.show_threads || process.threads <= 1 {
+                    text
+                } else if threads.is_empty() {
+                    format!("▸ {}", text)
+                } else {
+                    format!("▾ {}", text)
+                };
+                column.align(text)
+            }).collect();
+
+            let thread_style = Style::default().fg(Color::DarkGray);
+            std::iter::once(Row::new(cells)).chain(threads.iter().map(move |thread| {
+                let cells: Vec<String> = layout.iter().map(|column| {
+                    column.thread.map(|format| column.align(format(thread))).unwrap_or_default()
+                }).collect();
+                Row::new(cells).style(thread_style)
+            }))
+        }).collect();
+
+        let user_info = match self.filter.username {
+            Some(ref user) => format!(" - User: {}", user),
+            None => String::new(),
+        };
+        let title = format!(
+            "Processes ({}) - Sort: {:?} {}{} - Press 'h' for help",
+            filtered_processes.len(),
+            self.sort_column,
+            sort_indicator,
+            user_info
+        );
+
+        let widths: Vec<Constraint> = self.columns.iter().map(|column| {
+            if column.fill {
+                Constraint::Min(column.width)
+            } else {
+                Constraint::Length(column.width)
+            }
+        }).collect();
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .widths(&widths);
+
+        f.render_stateful_widget(table, area, &mut self.table_state);
+    }
+
+    fn render_tree_table(&mut self, f: &mut Frame, area: Rect) {
+        let tree_rows = self.get_tree_rows();
+
         let header = Row::new(vec![
-            "PID", "User", "CPU%", "Mem%", "Memory", "Net", "Status", "Name", "Command"
+            "PID", "User", "CPU%", "ΣCPU%", "Mem%", "ΣMemory", "Status", "Tree", "Command"
         ])
         .style(Style::default().fg(Color::Yellow))
         .height(1);
 
-        let rows: Vec<Row> = filtered_processes.iter().map(|process| {
-            // Format network connections
-            let net_info = if let Some(connections) = process.network_connections {
-                format!("{}", connections)
+        let rows: Vec<Row> = tree_rows.iter().map(|row| {
+            let process = &row.process;
+            let marker = if !row.has_children {
+                "  "
+            } else if row.collapsed {
+                "▸ "
             } else {
-                "-".to_string()
+                "▾ "
             };
-            
-            // Format status with container/GPU indicators
-            let mut status_str = process.status.clone();
-            if process.is_container {
-                status_str.push_str(" 🐳");
-            }
-            if process.gpu_memory.is_some() {
-                status_str.push_str(" 🎮");
+            let mut tree_label = format!("{}{}{}", row.prefix, marker, process.name);
+            if row.collapsed {
+                tree_label.push_str(&format!(" (+{})", row.descendant_count));
             }
-            
+
             Row::new(vec![
                 process.pid.to_string(),
                 process.user.clone(),
                 format!("{:.1}", process.cpu_usage),
+                format!("{:.1}", row.subtree_cpu),
                 format!("{:.1}", process.memory_percent),
-                format!("{} KB", process.memory_usage),
-                net_info,
-                status_str,
-                process.name.clone(),
-                if process.command.len() > 40 {
-                    format!("{}...", &process.command[..37])
-                } else {
-                    process.command.clone()
-                },
+                format!("{} KB", row.subtree_memory),
+                process.status.clone(),
+                tree_label,
+                process.command.clone(),
             ])
         }).collect();
 
-        let sort_indicator = if self.sort_ascending { "▲" } else { "▼" };
         let title = format!(
-            "Processes ({}) - Sort: {:?} {} - Press 'h' for help",
-            filtered_processes.len(),
-            self.sort_column,
-            sort_indicator
+            "Process Tree ({}) - ←/→ collapse/expand, Space toggle, C collapse below, E expand all",
+            tree_rows.len()
         );
 
         let table = Table::new(rows)
@@ -542,11 +1810,11 @@ impl App {
                 Constraint::Length(8),  // PID
                 Constraint::Length(10), // User
                 Constraint::Length(6),  // CPU%
+                Constraint::Length(7),  // ΣCPU%
                 Constraint::Length(6),  // Mem%
-                Constraint::Length(10), // Memory
-                Constraint::Length(5),  // Net
+                Constraint::Length(12), // ΣMemory
                 Constraint::Length(8),  // State
-                Constraint::Length(12), // Name
+                Constraint::Length(40), // Tree
                 Constraint::Min(20),    // Command
             ]);
 
@@ -585,14 +1853,50 @@ impl App {
             Line::from("  c          Sort by CPU usage"),
             Line::from("  m          Sort by Memory usage"),
             Line::from("  s          Sort by Start time"),
+            Line::from("  T          Sort by CPU time (TIME+)"),
+            Line::from("  d / w      Sort by Disk read / write rate"),
+            Line::from("  D / W      Sort by Read / write syscalls"),
+            Line::from("  x          Sort by Cancelled writes"),
+            Line::from("  I / O      Sort by Network receive / send rate"),
             Line::from(""),
             Line::from("Actions:"),
+            Line::from("  Enter      Open detail view for selected process"),
             Line::from("  k          Kill selected process"),
+            Line::from("  N          Renice selected process"),
+            Line::from("  A          Set CPU affinity of selected process"),
+            Line::from("  P          Set scheduling policy of selected process"),
+            Line::from("  i          Set I/O priority (ionice) of selected process"),
             Line::from("  /          Search processes"),
             Line::from("  t          Toggle tree view"),
+            Line::from("  ←/→        Collapse/expand node (tree view)"),
+            Line::from("  Space      Toggle node (tree view)"),
+            Line::from("  C          Collapse all below selected (tree view)"),
+            Line::from("  E          Expand all (tree view)"),
+            Line::from("  H          Toggle thread rows (flat view)"),
+            Line::from("  →/←/Space  Expand/collapse threads of selected process"),
+            Line::from("  S          Toggle socket pane for selected process"),
+            Line::from("  F          Toggle open fd pane for selected process"),
+            Line::from("  f          Choose, reorder and save table columns"),
+            Line::from("  a          Show alert silences and maintenance windows"),
             Line::from("  g          Toggle system graphs"),
             Line::from("  o          Toggle user processes only"),
             Line::from(""),
+            Line::from("Detail View:"),
+            Line::from("  Tab/←/→    Switch tab (1-6 jump to a tab)"),
+            Line::from("  ↑/↓ PgUp/PgDn  Scroll"),
+            Line::from("  r          Reload details"),
+            Line::from("  k          Kill the shown process"),
+            Line::from("  N/A/P/i    Renice / affinity / policy / ionice the shown process"),
+            Line::from("  q/Esc      Back to process list"),
+            Line::from(""),
+            Line::from("Column Picker:"),
+            Line::from("  ↑/↓        Move cursor"),
+            Line::from("  Space      Show/hide column"),
+            Line::from("  J/K        Move column down/up (also Shift+↓/↑)"),
+            Line::from("  s          Sort by column"),
+            Line::from("  Enter      Save layout to the view profile"),
+            Line::from("  Esc        Close without saving"),
+            Line::from(""),
             Line::from("Kill Dialog Signals:"),
             Line::from("  t          SIGTERM (15) - Graceful termination"),
             Line::from("  9          SIGKILL (9) - Force kill"),
@@ -600,6 +1904,7 @@ impl App {
             Line::from("  2          SIGINT (2) - Interrupt"),
             Line::from("  s          SIGSTOP (19) - Stop process"),
             Line::from("  c          SIGCONT (18) - Continue process"),
+            Line::from("  a          Toggle signalling the whole subtree"),
             Line::from(""),
             Line::from("Press 'h' or F1 to close this help"),
         ];
@@ -623,8 +1928,148 @@ impl App {
         f.render_widget(paragraph, area);
     }
 
+    fn render_control_dialog(&self, f: &mut Frame) {
+        let (Some(dialog), Some(pid)) = (self.control_dialog, self.selected_process) else {
+            return;
+        };
+        let area = centered_rect(50, 30, f.size());
+
+        let name = self.process_manager.get_process(pid)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+        let input = if self.control_input_prefilled {
+            format!("{} (current, type to replace)", self.control_input)
+        } else {
+            self.control_input.clone()
+        };
+        let text = format!(
+            "PID: {}\nName: {}\n\n{}\n\n> {}\n\nPress Enter to apply, Esc to cancel",
+            pid,
+            name,
+            dialog.hint(),
+            input
+        );
+        let paragraph = Paragraph::new(text)
+            .block(Block::default().borders(Borders::ALL).title(dialog.title()))
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_column_picker(&self, f: &mut Frame) {
+        let Some(ref picker) = self.column_picker else {
+            return;
+        };
+        let area = centered_rect(60, 70, f.size());
+
+        let lines: Vec<Line> = picker.entries.iter().enumerate().map(|(i, (column, shown))| {
+            let text = format!(
+                "{} {:<10} {:<16} {}{}",
+                if *shown { "[x]" } else { "[ ]" },
+                column.header,
+                column.key,
+                column.description,
+                if column.sort.is_some() { " (sortable)" } else { "" }
+            );
+            if i == picker.cursor {
+                Line::styled(text, Style::default().add_modifier(Modifier::REVERSED))
+            } else if *shown {
+                Line::styled(text, Style::default().fg(Color::Green))
+            } else {
+                Line::from(text)
+            }
+        }).collect();
+
+        // Keep the cursor in view
+        let height = area.height.saturating_sub(2) as usize;
+        let scroll = picker.cursor.saturating_sub(height.saturating_sub(1));
+        let title = format!(
+            "Columns - {} - Space show/hide, J/K move, s sort, Enter save, Esc close",
+            self.view_profile.as_deref().unwrap_or("no profile")
+        );
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .scroll((scroll as u16, 0));
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_silences(&self, f: &mut Frame) {
+        let Some(ref view) = self.silences_view else {
+            return;
+        };
+        let area = centered_rect(70, 60, f.size());
+        let now = chrono::Utc::now();
+        let local_now = chrono::Local::now().naive_local();
+        let active = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![Line::from("Maintenance windows (local time):")];
+        if view.windows.is_empty() {
+            lines.push(Line::from("  none"));
+        }
+        for window in &view.windows {
+            let text = format!(
+                "  {} {:<20} {}-{} {:<14} {}{}",
+                if window.is_active_at(local_now) { "ACTIVE " } else { "       " },
+                window.name,
+                window.start,
+                window.end,
+                if window.days.is_empty() { "every day".to_string() } else { window.days.join(",") },
+                window.matcher,
+                if window.id.is_none() { " (config)" } else { "" },
+            );
+            if window.is_active_at(local_now) {
+                lines.push(Line::styled(text, active));
+            } else {
+                lines.push(Line::from(text));
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Silences:"));
+        if view.silences.is_empty() {
+            lines.push(Line::from("  none"));
+        }
+        for silence in &view.silences {
+            let text = format!(
+                "  {} #{:<4} {} until {} ({} left) by {}{}",
+                if silence.is_active(now) { "ACTIVE " } else { "PENDING" },
+                silence.id,
+                silence.matcher,
+                silence.ends_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                columns::format_elapsed((silence.ends_at - now).num_seconds().max(0) as u64),
+                silence.created_by,
+                if silence.comment.is_empty() { String::new() } else { format!(": {}", silence.comment) },
+            );
+            if silence.is_active(now) {
+                lines.push(Line::styled(text, active));
+            } else {
+                lines.push(Line::from(text));
+            }
+        }
+
+        if let Some(ref error) = view.error {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                format!("Silences and API windows unavailable: {}", error),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Manage with the API: /api/alerts/silences, /api/alerts/maintenance-windows"));
+
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Alert Silences - r reload, Esc close"))
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+
     fn render_kill_dialog(&self, f: &mut Frame) {
-        let area = centered_rect(40, 30, f.size());
+        let area = centered_rect(50, 40, f.size());
         
         let process_info = if let Some(pid) = self.selected_process {
             if let Some(process) = self.process_manager.get_process(pid) {
@@ -647,13 +2092,31 @@ impl App {
             _ => "Unknown",
         };
 
+        let target_info = match self.selected_process {
+            Some(pid) if self.kill_subtree => {
+                match self.process_manager.kill_process_tree(pid, self.kill_signal, true) {
+                    Ok(targets) => {
+                        let pids: Vec<String> = targets.iter().map(|t| t.pid.to_string()).collect();
+                        format!("\nTargets ({}, leaves first): {}", pids.len(), pids.join(" "))
+                    }
+                    Err(e) => format!("\nTargets: {}", e),
+                }
+            }
+            _ => String::new(),
+        };
+
         let text = format!(
-            "{}\n\nSignal: {} ({})\n\nPress Enter to confirm, Esc to cancel\nKeys: t=TERM, 9=KILL, 1=HUP, 2=INT, s=STOP, c=CONT",
-            process_info, self.kill_signal, signal_name
+            "{}\n\nSignal: {} ({})\nScope: {}{}\n\nPress Enter to confirm, Esc to cancel\nKeys: t=TERM, 9=KILL, 1=HUP, 2=INT, s=STOP, c=CONT, a=toggle subtree",
+            process_info,
+            self.kill_signal,
+            signal_name,
+            if self.kill_subtree { "process and all descendants" } else { "process only" },
+            target_info
         );
 
+        let title = if self.kill_subtree { "Kill Process Subtree" } else { "Kill Process" };
         let paragraph = Paragraph::new(text)
-            .block(Block::default().borders(Borders::ALL).title("Kill Process"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(Wrap { trim: true });
 
         f.render_widget(Clear, area);
@@ -661,6 +2124,7 @@ impl App {
     }
 }
 
+/// Format a bytes-per-second rate compactly for a table cell
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -681,7 +2145,7 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-pub fn run_app() -> Result<(), UiError> {
+pub fn run_app(config: &Config) -> Result<(), UiError> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -690,7 +2154,7 @@ pub fn run_app() -> Result<(), UiError> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run it
-    let mut app = App::new()?;
+    let mut app = App::new(config)?;
     let res = app.run(&mut terminal);
 
     // Restore terminal
@@ -89,12 +89,16 @@ mod alerts_tests {
     #[tokio::test]
     async fn test_alert_rule_creation() {
         let rule = AlertRule {
+            name: "cpu".to_string(),
             enabled: true,
             alert_type: AlertType::HighCpu,
             threshold: 80.0,
             duration_secs: 5,
             cooldown_secs: 60,
             process_filter: None,
+            command_pattern: None,
+            expr: None,
+            severity: None,
         };
         
         let config = NotificationConfig {
@@ -7,22 +7,35 @@ import type {
   HealthResponse,
   HistoryRecord,
   SortColumn,
+  ProcessSnapshot,
+  ProcessDelta,
 } from '../types';
 
 // API base URL - uses relative path so it works with both dev proxy and production
 const API_BASE = '/api';
 
+// Optional bearer token for servers with [api.auth] enabled.
+// Set it from the browser console: localStorage.setItem('lpm_api_token', '...')
+// Servers with basic-auth users instead trigger the browser's login prompt.
+const TOKEN_STORAGE_KEY = 'lpm_api_token';
+
+function authHeaders(): Record<string, string> {
+  const token = localStorage.getItem(TOKEN_STORAGE_KEY);
+  return token ? { Authorization: `Bearer ${token}` } : {};
+}
+
 // Generic fetch wrapper with error handling
 async function fetchApi<T>(endpoint: string, options?: RequestInit): Promise<T> {
   const url = `${API_BASE}${endpoint}`;
 
   try {
     const response = await fetch(url, {
+      ...options,
       headers: {
         'Content-Type': 'application/json',
+        ...authHeaders(),
         ...options?.headers,
       },
-      ...options,
     });
 
     if (!response.ok) {
@@ -58,7 +71,7 @@ export interface GetProcessesParams {
   limit?: number;
 }
 
-export async function getProcesses(params?: GetProcessesParams): Promise<ProcessListResponse> {
+function processQuery(params?: GetProcessesParams): string {
   const searchParams = new URLSearchParams();
 
   if (params?.sortBy) searchParams.set('sort_by', params.sortBy);
@@ -68,7 +81,58 @@ export async function getProcesses(params?: GetProcessesParams): Promise<Process
   if (params?.limit) searchParams.set('limit', String(params.limit));
 
   const query = searchParams.toString();
-  return fetchApi<ProcessListResponse>(`/processes${query ? `?${query}` : ''}`);
+  return query ? `?${query}` : '';
+}
+
+export async function getProcesses(params?: GetProcessesParams): Promise<ProcessListResponse> {
+  return fetchApi<ProcessListResponse>(`/processes${processQuery(params)}`);
+}
+
+// Live process updates over Server-Sent Events.
+// Uses fetch instead of EventSource so the Authorization header can be sent.
+export interface StreamHandlers {
+  onSnapshot: (snapshot: ProcessSnapshot) => void;
+  onUpdate: (delta: ProcessDelta) => void;
+}
+
+export async function streamProcesses(
+  params: GetProcessesParams | undefined,
+  handlers: StreamHandlers,
+  signal: AbortSignal
+): Promise<void> {
+  const response = await fetch(`${API_BASE}/processes/stream${processQuery(params)}`, {
+    headers: { Accept: 'text/event-stream', ...authHeaders() },
+    signal,
+  });
+  if (!response.ok || !response.body) {
+    throw new Error(`Stream unavailable: HTTP ${response.status}`);
+  }
+
+  const reader = response.body.pipeThrough(new TextDecoderStream()).getReader();
+  let buffer = '';
+
+  for (;;) {
+    const { value, done } = await reader.read();
+    if (done) return;
+    buffer += value;
+
+    let boundary;
+    while ((boundary = buffer.indexOf('\n\n')) >= 0) {
+      const block = buffer.slice(0, boundary);
+      buffer = buffer.slice(boundary + 2);
+
+      let event = 'message';
+      let data = '';
+      for (const line of block.split('\n')) {
+        if (line.startsWith('event: ')) event = line.slice(7);
+        else if (line.startsWith('data: ')) data += line.slice(6);
+      }
+      if (!data) continue; // keepalive comment
+
+      if (event === 'snapshot') handlers.onSnapshot(JSON.parse(data));
+      else if (event === 'update') handlers.onUpdate(JSON.parse(data));
+    }
+  }
 }
 
 // Get single process
@@ -108,6 +172,7 @@ export const api = {
   checkHealth,
   getSystemInfo,
   getProcesses,
+  streamProcesses,
   getProcess,
   killProcess,
   getProcessHistory,
@@ -1,13 +1,82 @@
+import { useEffect, useState } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { api, type GetProcessesParams } from '../api/client';
-import type { KillRequest } from '../types';
+import type { KillRequest, ProcessInfo, ProcessListResponse } from '../types';
 
-// Hook for fetching processes
+// Same ordering as the server applies for GET /api/processes
+function sortProcesses(processes: ProcessInfo[], params?: GetProcessesParams): ProcessInfo[] {
+  const column = params?.sortBy ?? 'cpu';
+  const direction = params?.ascending ? 1 : -1;
+  const key = (p: ProcessInfo): number | string => {
+    switch (column) {
+      case 'pid': return p.pid;
+      case 'name': return p.name;
+      case 'user': return p.user;
+      case 'memory': return p.memory_usage;
+      case 'start_time': return p.start_time;
+      default: return p.cpu_usage;
+    }
+  };
+  return processes.sort((a, b) => (key(a) < key(b) ? -direction : key(a) > key(b) ? direction : 0));
+}
+
+// Hook for fetching processes.
+// While auto-refresh is on, the list is kept current from the live stream
+// and polling only takes over if the stream is unavailable.
 export function useProcesses(params?: GetProcessesParams, refetchInterval = 2000) {
+  const queryClient = useQueryClient();
+  const [streaming, setStreaming] = useState(false);
+  const live = refetchInterval > 0;
+  const paramsKey = JSON.stringify(params ?? {});
+
+  useEffect(() => {
+    if (!live) return;
+
+    const controller = new AbortController();
+    const queryKey = ['processes', params];
+    let byPid = new Map<number, ProcessInfo>();
+
+    const publish = (total: number) => {
+      const processes = sortProcesses([...byPid.values()], params);
+      queryClient.setQueryData<ProcessListResponse>(queryKey, {
+        processes,
+        total,
+        filtered: processes.length,
+      });
+    };
+
+    api
+      .streamProcesses(
+        params,
+        {
+          onSnapshot: (snapshot) => {
+            byPid = new Map(snapshot.processes.map((p) => [p.pid, p]));
+            setStreaming(true);
+            publish(snapshot.total);
+          },
+          onUpdate: (delta) => {
+            delta.removed.forEach((pid) => byPid.delete(pid));
+            delta.added.forEach((p) => byPid.set(p.pid, p));
+            delta.changed.forEach(({ pid, fields }) => {
+              const existing = byPid.get(pid);
+              if (existing) byPid.set(pid, { ...existing, ...fields });
+            });
+            publish(delta.total);
+          },
+        },
+        controller.signal
+      )
+      .catch(() => undefined)
+      .finally(() => setStreaming(false));
+
+    return () => controller.abort();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [live, paramsKey, queryClient]);
+
   return useQuery({
     queryKey: ['processes', params],
     queryFn: () => api.getProcesses(params),
-    refetchInterval,
+    refetchInterval: streaming ? false : refetchInterval,
     staleTime: 1000,
   });
 }
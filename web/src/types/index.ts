@@ -67,6 +67,21 @@ export interface HealthResponse {
 // Sort configuration
 export type SortColumn = 'pid' | 'name' | 'user' | 'cpu' | 'memory' | 'start_time';
 
+// Events from GET /api/processes/stream
+export interface ProcessSnapshot {
+  timestamp: number;
+  total: number;
+  processes: ProcessInfo[];
+}
+
+export interface ProcessDelta {
+  timestamp: number;
+  total: number;
+  added: ProcessInfo[];
+  removed: number[];
+  changed: { pid: number; fields: Partial<ProcessInfo> }[];
+}
+
 export interface SortConfig {
   column: SortColumn;
   ascending: boolean;